@@ -0,0 +1,90 @@
+//! Compares the interpreter and the virtual machine on the same programs, run it with `cargo bench`.
+//! Each program is run a few times by each engine, and the best time is reported.
+
+use std::time::{Duration, Instant};
+
+use ocypode_lang::{
+    parser::OYParser,
+    runtime::{interpreter::Interpreter, vm::Vm},
+};
+
+/// The number of runs of each program, the best time of them is reported.
+const RUNS: usize = 5;
+
+/// The benchmarked programs, with their names. Each program returns `42` as its exit code.
+const PROGRAMS: &[(&str, &str)] = &[
+    (
+        "recursion",
+        "~fib<n>{<
+            if &n < 2 {< return n; >};
+            return fib<&n - 1> + fib<n - 2>;
+        >}
+        ~main<argc><argv>{<
+            return fib<22> - 17669;
+        >}",
+    ),
+    (
+        "tail calls",
+        "~count<n><acc>{<
+            if &n == 0 {< return acc; >};
+            return count<&n - 1><acc + 1>;
+        >}
+        ~main<argc><argv>{<
+            return count<200000><0> - 199958;
+        >}",
+    ),
+    (
+        "loops",
+        "~fill<n><numbers>{<
+            if &n == 0 {< return numbers; >};
+            return fill<&n - 1><push<numbers><n>>;
+        >}
+        ~main<argc><argv>{<
+            numbers = fill<2000><[]>;
+            for round in [1, 2, 3, 4, 5, 6, 7, 8, 9, 10] {<
+                for n in &numbers {< x = &n * 2 + &round; >};
+            >};
+            return len<numbers> - 1958;
+        >}",
+    ),
+];
+
+/// Runs the program by the given engine and returns the best time of the runs.
+fn measure(source: &str, run: impl Fn(&str) -> u8) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            assert_eq!(run(source), 42);
+            start.elapsed()
+        })
+        .min()
+        .expect("The program is run at least once")
+}
+
+fn main() {
+    println!(
+        "{:<12} {:>12} {:>12} {:>8}",
+        "program", "interpreter", "vm", "speedup"
+    );
+    for (name, source) in PROGRAMS {
+        let interpreter = measure(source, |source| {
+            Interpreter::new()
+                .with_max_call_depth(5000)
+                .interpret(OYParser::parse_program(source).unwrap(), 0, vec![])
+                .unwrap()
+        });
+        let vm = measure(source, |source| {
+            Vm::new()
+                .with_max_call_depth(5000)
+                .interpret(OYParser::parse_program(source).unwrap(), 0, vec![])
+                .unwrap()
+        });
+        println!(
+            "{:<12} {:>10.1}ms {:>10.1}ms {:>7.2}x",
+            name,
+            interpreter.as_secs_f64() * 1000.0,
+            vm.as_secs_f64() * 1000.0,
+            interpreter.as_secs_f64() / vm.as_secs_f64()
+        );
+    }
+}
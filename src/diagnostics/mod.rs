@@ -1,6 +1,6 @@
 pub mod parser;
 pub mod runtime;
-use crate::errors::{Error, ErrorKind};
+use crate::errors::{CallSite, Error, ErrorKind};
 use miette::{GraphicalReportHandler, JSONReportHandler};
 use std::{env, fmt::Display};
 
@@ -36,6 +36,22 @@ impl<T: Default> Diagnostic<T> {
     }
 }
 
+impl<T> Diagnostic<T> {
+    /// Add the traceback of the runtime error to the diagnostic, it's shown after the help message.
+    pub fn with_traceback(self, traceback: Option<String>) -> Self {
+        match traceback {
+            Some(traceback) => Self {
+                diagnostic: Box::new(Traceback {
+                    diagnostic: self.diagnostic,
+                    traceback,
+                }),
+                handler: self.handler,
+            },
+            None => self,
+        }
+    }
+}
+
 impl Diagnostic<GraphicalReportHandler> {
     /// Display the diagnostic as a ASCII string
     pub fn as_ascii(mut self) -> Self {
@@ -65,6 +81,116 @@ impl Display for Diagnostic<JSONReportHandler> {
     }
 }
 
+/// A diagnostic with the traceback of its runtime error.
+#[derive(Debug)]
+struct Traceback {
+    diagnostic: Box<dyn miette::Diagnostic>,
+    traceback: String,
+}
+
+impl Display for Traceback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.diagnostic.fmt(f)
+    }
+}
+
+impl std::error::Error for Traceback {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.diagnostic.source()
+    }
+}
+
+impl miette::Diagnostic for Traceback {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.diagnostic.code()
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.diagnostic.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(match self.diagnostic.help() {
+            Some(help) => format!("{help}\n\n{}", self.traceback),
+            None => self.traceback.clone(),
+        }))
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.diagnostic.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.diagnostic.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        self.diagnostic.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn miette::Diagnostic> + 'a>> {
+        self.diagnostic.related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn miette::Diagnostic> {
+        self.diagnostic.diagnostic_source()
+    }
+}
+
+/// Returns the traceback of the given calls, from the main function to the function where the error happened,
+/// or `None` if the error happened in the main function. The `locate` function returns the location of an offset.
+/// The consecutive calls from the same call site (a recursion) are merged.
+pub fn traceback(calls: &[CallSite], locate: impl Fn(usize) -> String) -> Option<String> {
+    if calls.len() < 2 {
+        return None;
+    }
+    let mut lines: Vec<(String, usize)> = Vec::new();
+    for call in calls {
+        let function = match &call.name {
+            Some(name) => format!("`{name}`"),
+            None => format!(
+                "anonymous function defined at {}",
+                locate(call.function_span.offset())
+            ),
+        };
+        let line = match call.span {
+            Some(span) => format!("{function} called at {}", locate(span.offset())),
+            None => function,
+        };
+        match lines.last_mut() {
+            Some((last, count)) if last == &line => *count += 1,
+            _ => lines.push((line, 1)),
+        }
+    }
+    let mut lines: Vec<_> = lines
+        .into_iter()
+        .map(|(line, count)| match count {
+            1 => format!("  {line}"),
+            count => format!("  {line} (x {count})"),
+        })
+        .collect();
+    // A long traceback of a mutual recursion is shortened to its start and its end.
+    if lines.len() > 20 {
+        lines.splice(10..lines.len() - 10, ["  ...".to_owned()]);
+    }
+    Some(format!(
+        "Traceback, from the main function:\n{}",
+        lines.join("\n")
+    ))
+}
+
+/// Returns the location of the given offset in the source, as `name:line:column`.
+pub fn location(source: &str, source_name: &str, offset: usize) -> String {
+    let before = source.get(..offset).unwrap_or(source);
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
+        + 1;
+    format!("{source_name}:{line}:{column}")
+}
+
 pub fn as_diagnostic<T: Default>(err: Error, source: String, source_name: String) -> Diagnostic<T> {
     match err.kind {
         ErrorKind::InvalidName(ref name, ref reason, ref valid_name, ref statement_type) => {
@@ -97,6 +223,22 @@ pub fn as_diagnostic<T: Default>(err: Error, source: String, source_name: String
                 span: err.span,
             }))
         }
+        ErrorKind::UseAfterMove(name, moved_span) => {
+            Diagnostic::new(Box::new(runtime::idents::UseAfterMove {
+                src: miette::NamedSource::new(source_name, source),
+                name,
+                moved_span: moved_span.into(),
+                span: err.span,
+            }))
+        }
+        ErrorKind::UseAfterMaybeMove(name, moved_span) => {
+            Diagnostic::new(Box::new(runtime::idents::UseAfterMaybeMove {
+                src: miette::NamedSource::new(source_name, source),
+                name,
+                moved_span: moved_span.into(),
+                span: err.span,
+            }))
+        }
         ErrorKind::AlreadyDeclared(name, old_decl) => {
             Diagnostic::new(Box::new(runtime::AlreadyDeclared {
                 src: miette::NamedSource::new(source_name, source),
@@ -146,6 +288,47 @@ pub fn as_diagnostic<T: Default>(err: Error, source: String, source_name: String
                 span: err.span,
             }))
         }
+        ErrorKind::UnknownArgument(name, func_span, func_name) => {
+            Diagnostic::new(Box::new(runtime::functions::UnknownArgument {
+                src: miette::NamedSource::new(source_name, source),
+                name,
+                func_name,
+                func_span: func_span.into(),
+                span: err.span,
+            }))
+        }
+        ErrorKind::DuplicateArgument(name, first_arg) => {
+            Diagnostic::new(Box::new(runtime::functions::DuplicateArgument {
+                src: miette::NamedSource::new(source_name, source),
+                name,
+                first_arg: first_arg.into(),
+                span: err.span,
+            }))
+        }
+        ErrorKind::MissingArgument(name, func_span, func_name) => {
+            Diagnostic::new(Box::new(runtime::functions::MissingArgument {
+                src: miette::NamedSource::new(source_name, source),
+                name,
+                func_name,
+                func_span: func_span.into(),
+                span: err.span,
+            }))
+        }
+        ErrorKind::StackOverflow(max_call_depth) => {
+            Diagnostic::new(Box::new(runtime::functions::StackOverflow {
+                src: miette::NamedSource::new(source_name, source),
+                max_call_depth,
+                span: err.span,
+            }))
+        }
+        ErrorKind::StackUnavailable(max_call_depth, stack_size, reason) => {
+            Diagnostic::new(Box::new(runtime::functions::StackUnavailable {
+                src: miette::NamedSource::new(source_name, source),
+                max_call_depth,
+                stack_size,
+                reason,
+            }))
+        }
         ErrorKind::UnexpectedType(expected, actual) => {
             Diagnostic::new(Box::new(runtime::types::UnexpectedType {
                 src: miette::NamedSource::new(source_name, source),
@@ -154,6 +337,20 @@ pub fn as_diagnostic<T: Default>(err: Error, source: String, source_name: String
                 span: err.span,
             }))
         }
+        ErrorKind::MismatchedType(expected, found, annotation) => {
+            Diagnostic::new(Box::new(runtime::types::MismatchedType {
+                src: miette::NamedSource::new(source_name, source),
+                expected,
+                found,
+                annotation: annotation.map(Into::into),
+                span: err.span,
+            }))
+        }
+        ErrorKind::UnknownType(name) => Diagnostic::new(Box::new(runtime::types::UnknownType {
+            src: miette::NamedSource::new(source_name, source),
+            name,
+            span: err.span,
+        })),
         ErrorKind::MultiplePackedParams(func_name) => {
             Diagnostic::new(Box::new(parser::params::MultiplePackedParams {
                 src: miette::NamedSource::new(source_name, source),
@@ -168,6 +365,21 @@ pub fn as_diagnostic<T: Default>(err: Error, source: String, source_name: String
                 span: err.span,
             }))
         }
+        ErrorKind::PackedParamWithDefault(param_name) => {
+            Diagnostic::new(Box::new(parser::params::PackedParamWithDefault {
+                src: miette::NamedSource::new(source_name, source),
+                param_name,
+                span: err.span,
+            }))
+        }
+        ErrorKind::RequiredParamAfterDefault(param_name, default_name) => {
+            Diagnostic::new(Box::new(parser::params::RequiredParamAfterDefault {
+                src: miette::NamedSource::new(source_name, source),
+                param_name,
+                default_name,
+                span: err.span,
+            }))
+        }
         ErrorKind::MultipleParamsWithTheSameName(param_name, func_name) => {
             Diagnostic::new(Box::new(parser::params::MultipleParamsWithTheSameName {
                 src: miette::NamedSource::new(source_name, source),
@@ -191,6 +403,89 @@ pub fn as_diagnostic<T: Default>(err: Error, source: String, source_name: String
                 span: err.span,
             }))
         }
+        ErrorKind::OutsideLoop(keyword) => Diagnostic::new(Box::new(parser::loops::OutsideLoop {
+            src: miette::NamedSource::new(source_name, source),
+            keyword,
+            span: err.span,
+        })),
+        ErrorKind::InvalidOperands(operator, left, right) => {
+            let expected = match operator.as_str() {
+                "<" | "<=" | ">" | ">=" => "two numbers or two strings",
+                _ => "numbers, an `int` or a `float`",
+            }
+            .to_owned();
+            Diagnostic::new(Box::new(runtime::types::InvalidOperands {
+                src: miette::NamedSource::new(source_name, source),
+                operator,
+                expected,
+                left,
+                right,
+                span: err.span,
+            }))
+        }
+        ErrorKind::InvalidImport(path, reason) => {
+            Diagnostic::new(Box::new(parser::imports::InvalidImport {
+                src: miette::NamedSource::new(source_name, source),
+                path,
+                reason,
+                span: err.span,
+            }))
+        }
+        ErrorKind::CircularImport(chain) => {
+            Diagnostic::new(Box::new(parser::imports::CircularImport {
+                src: miette::NamedSource::new(source_name, source),
+                chain: chain.join(" -> "),
+                span: err.span,
+            }))
+        }
+        ErrorKind::NoMatchingArm(value) => {
+            Diagnostic::new(Box::new(runtime::patterns::NoMatchingArm {
+                src: miette::NamedSource::new(source_name, source),
+                value,
+                span: err.span,
+            }))
+        }
+        ErrorKind::UnknownField(record, field, fields) => {
+            Diagnostic::new(Box::new(runtime::records::UnknownField {
+                src: miette::NamedSource::new(source_name, source),
+                record,
+                field,
+                fields: fields
+                    .iter()
+                    .map(|field| format!("`{field}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                span: err.span,
+            }))
+        }
+        ErrorKind::MissingFields(record, fields) => {
+            Diagnostic::new(Box::new(runtime::records::MissingFields {
+                src: miette::NamedSource::new(source_name, source),
+                record,
+                fields: fields
+                    .iter()
+                    .map(|field| format!("`{field}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                span: err.span,
+            }))
+        }
+        ErrorKind::UnreachableArm(previous_arm) => {
+            Diagnostic::new(Box::new(parser::patterns::UnreachableArm {
+                src: miette::NamedSource::new(source_name, source),
+                previous_arm: previous_arm.into(),
+                span: err.span,
+            }))
+        }
+        ErrorKind::DivisionByZero => Diagnostic::new(Box::new(runtime::DivisionByZero {
+            src: miette::NamedSource::new(source_name, source),
+            span: err.span,
+        })),
+        ErrorKind::Thrown(value) => Diagnostic::new(Box::new(runtime::Thrown {
+            src: miette::NamedSource::new(source_name, source),
+            value: value.to_string(),
+            span: err.span,
+        })),
         ErrorKind::Runtime(reason) => Diagnostic::new(Box::new(runtime::RuntimeError {
             src: miette::NamedSource::new(source_name, source),
             reason,
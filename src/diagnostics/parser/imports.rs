@@ -0,0 +1,39 @@
+use miette::{Diagnostic, NamedSource};
+
+/// Invalid import diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("parser::imports::invalid_import"),
+    help("The path is relative to the directory of the importer file.")
+)]
+#[error("Cannot import `{path}`")]
+pub struct InvalidImport {
+    /// The source code.
+    #[source_code]
+    pub src: NamedSource,
+    /// The path of the imported file.
+    pub path: String,
+    /// The reason.
+    pub reason: String,
+    /// The span of the import statement.
+    #[label("{reason}")]
+    pub span: miette::SourceSpan,
+}
+
+/// Circular import diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("parser::imports::circular_import"),
+    help("Move the shared functions to another file, and import it from both files.")
+)]
+#[error("Circular import")]
+pub struct CircularImport {
+    /// The source code.
+    #[source_code]
+    pub src: NamedSource,
+    /// The import chain, e.g. `a.oy -> b.oy -> a.oy`.
+    pub chain: String,
+    /// The span of the import statement.
+    #[label("This import creates a cycle: {chain}")]
+    pub span: miette::SourceSpan,
+}
@@ -0,0 +1,19 @@
+use miette::{Diagnostic, NamedSource};
+
+/// Loop control statement outside of a loop diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("parser::loops::outside_loop"),
+    help("`{keyword}` can only be used inside a `loop`, `while` or `for` block.")
+)]
+#[error("`{keyword}` outside of a loop")]
+pub struct OutsideLoop {
+    /// The source code.
+    #[source_code]
+    pub src: NamedSource,
+    /// The statement keyword.
+    pub keyword: String,
+    /// The span of the statement.
+    #[label("`{keyword}` outside of a loop")]
+    pub span: miette::SourceSpan,
+}
@@ -1,7 +1,10 @@
 use miette::{Diagnostic, NamedSource};
 
 pub mod definitions;
+pub mod imports;
+pub mod loops;
 pub mod params;
+pub mod patterns;
 
 /// Syntax error diagnostic.
 #[derive(Debug, Diagnostic, thiserror::Error)]
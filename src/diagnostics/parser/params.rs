@@ -30,6 +30,44 @@ pub struct PackedParamNotLast {
     pub span: miette::SourceSpan,
 }
 
+/// Packed parameter with a default value diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("parser::params::packed_with_default"),
+    help("The packed parameter is an empty array when it has no arguments, remove its default value.")
+)]
+#[error("Packed parameter with a default value")]
+pub struct PackedParamWithDefault {
+    /// The source code.
+    #[source_code]
+    pub src: NamedSource,
+    /// The name of the packed parameter.
+    pub param_name: String,
+    /// The span of the syntax error.
+    #[label("packed parameter `{param_name}` can't have a default value")]
+    pub span: miette::SourceSpan,
+}
+
+/// Required parameter after a parameter with a default value diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("parser::params::required_after_default"),
+    help("Move the parameters with default values after the required parameters.")
+)]
+#[error("Required parameter after a parameter with a default value")]
+pub struct RequiredParamAfterDefault {
+    /// The source code.
+    #[source_code]
+    pub src: NamedSource,
+    /// The name of the required parameter.
+    pub param_name: String,
+    /// The name of the parameter with a default value.
+    pub default_name: String,
+    /// The span of the syntax error.
+    #[label("required parameter `{param_name}` is after the parameter `{default_name}` that has a default value")]
+    pub span: miette::SourceSpan,
+}
+
 /// Multiple parameters with the same name diagnostic.
 #[derive(Debug, Diagnostic, thiserror::Error)]
 #[diagnostic(code("parser::params::multiple_same_name"))]
@@ -0,0 +1,18 @@
+use miette::{Diagnostic, NamedSource};
+
+/// Unreachable match arm warning diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("parser::patterns::unreachable_arm"),
+    severity(Warning),
+    help("Remove the arm, or move it before the arm that matches its values.")
+)]
+#[error("Unreachable match arm")]
+pub struct UnreachableArm {
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("This arm matches all the values of the unreachable arm")]
+    pub(crate) previous_arm: miette::SourceSpan,
+    #[label("This arm is unreachable")]
+    pub(crate) span: miette::SourceSpan,
+}
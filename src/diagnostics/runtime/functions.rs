@@ -45,3 +45,86 @@ pub struct UncorrectArguments {
     #[label("Uncorrect arguments for function `{func_name}`")]
     pub(crate) span: miette::SourceSpan,
 }
+
+/// Unknown named argument error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::functions::unknown_argument)"),
+    help("Use the name of one of the parameters of `{func_name}`.")
+)]
+#[error("Unknown argument")]
+pub struct UnknownArgument {
+    pub(crate) name: String,
+    pub(crate) func_name: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Function `{func_name}` is defined here")]
+    pub(crate) func_span: miette::SourceSpan,
+    #[label("Function `{func_name}` has no parameter `{name}`")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Duplicate argument error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::functions::duplicate_argument)"),
+    help("Remove one of the arguments of `{name}`.")
+)]
+#[error("Duplicate argument")]
+pub struct DuplicateArgument {
+    pub(crate) name: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("The parameter `{name}` is given here")]
+    pub(crate) first_arg: miette::SourceSpan,
+    #[label("And it's given again here")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Stack overflow error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::functions::stack_overflow)"),
+    help("Try making the recursive call a tail call (`return f<...>;`), which doesn't deepen the calls.")
+)]
+#[error("Stack overflow")]
+pub struct StackOverflow {
+    pub(crate) max_call_depth: usize,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("This call exceeded the maximum call depth {max_call_depth}")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Stack unavailable error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::functions::stack_unavailable)"),
+    help("Try a smaller maximum call depth with `--max-call-depth=<depth>`.")
+)]
+#[error("Could not allocate a stack of {stack_size} bytes for the maximum call depth {max_call_depth}: {reason}")]
+pub struct StackUnavailable {
+    pub(crate) max_call_depth: usize,
+    pub(crate) stack_size: usize,
+    pub(crate) reason: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+}
+
+/// Missing argument error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::functions::missing_argument)"),
+    help("Pass an argument for `{name}`, or give it a default value.")
+)]
+#[error("Missing argument")]
+pub struct MissingArgument {
+    pub(crate) name: String,
+    pub(crate) func_name: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Function `{func_name}` is defined here")]
+    pub(crate) func_span: miette::SourceSpan,
+    #[label("Missing argument for parameter `{name}`")]
+    pub(crate) span: miette::SourceSpan,
+}
@@ -15,6 +15,41 @@ pub struct UnDeclaredIdent {
     pub(crate) span: miette::SourceSpan,
 }
 
+/// Use after move error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::idents::use_after_move)"),
+    help("Borrow `{name}` with `&{name}` or copy it with `clone<{name}>` before moving it, to keep using it.")
+)]
+#[error("Use of moved value `{name}`")]
+pub struct UseAfterMove {
+    pub(crate) name: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("value moved here")]
+    pub(crate) moved_span: miette::SourceSpan,
+    #[label("used here after move")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Use after a move in only some paths warning diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("runtime::idents::use_after_maybe_move"),
+    severity(Warning),
+    help("Borrow `{name}` with `&{name}` or copy it with `clone<{name}>` in the branch, or declare it again after the move.")
+)]
+#[error("Use of possibly moved value `{name}`")]
+pub struct UseAfterMaybeMove {
+    pub(crate) name: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("value may be moved here")]
+    pub(crate) moved_span: miette::SourceSpan,
+    #[label("used here after a possible move")]
+    pub(crate) span: miette::SourceSpan,
+}
+
 /// Not callable error diagnostic.
 #[derive(Debug, Diagnostic, thiserror::Error)]
 #[diagnostic(code("Error(runtime::idents::not_callable)"))]
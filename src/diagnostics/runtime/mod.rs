@@ -1,5 +1,7 @@
 pub mod functions;
 pub mod idents;
+pub mod patterns;
+pub mod records;
 pub mod types;
 use miette::{Diagnostic, NamedSource};
 
@@ -48,6 +50,35 @@ pub struct InvalidUnpackedArgument {
     pub(crate) span: miette::SourceSpan,
 }
 
+/// Division by zero error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::division_by_zero)"),
+    help("Make sure that the divisor is not zero before dividing.")
+)]
+#[error("Division by zero")]
+pub struct DivisionByZero {
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("The divisor is zero")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Thrown value error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::thrown)"),
+    help("Catch the thrown value with a `try`/`catch` statement.")
+)]
+#[error("Uncaught thrown value")]
+pub struct Thrown {
+    pub(crate) value: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("{value}")]
+    pub(crate) span: miette::SourceSpan,
+}
+
 /// Runtime error diagnostic.
 #[derive(Debug, Diagnostic, thiserror::Error)]
 #[diagnostic(code("Error(runtime::runtime)"))]
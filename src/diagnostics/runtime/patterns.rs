@@ -0,0 +1,16 @@
+use miette::{Diagnostic, NamedSource};
+
+/// No matching arm error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::patterns::no_matching_arm)"),
+    help("Add an arm that matches the value, or a wildcard `_` arm at the end of the match.")
+)]
+#[error("No matching arm")]
+pub struct NoMatchingArm {
+    pub(crate) value: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("No arm matches `{value}`")]
+    pub(crate) span: miette::SourceSpan,
+}
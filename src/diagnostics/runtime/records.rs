@@ -0,0 +1,34 @@
+use miette::{Diagnostic, NamedSource};
+
+/// Unknown field error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::records::unknown_field)"),
+    help("The fields of `{record}` are: {fields}")
+)]
+#[error("Unknown field")]
+pub struct UnknownField {
+    pub(crate) record: String,
+    pub(crate) field: String,
+    pub(crate) fields: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Record `{record}` has no field `{field}`")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Missing fields error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::records::missing_fields)"),
+    help("Add the missing fields to the record expression.")
+)]
+#[error("Missing fields")]
+pub struct MissingFields {
+    pub(crate) record: String,
+    pub(crate) fields: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Missing {fields} of record `{record}`")]
+    pub(crate) span: miette::SourceSpan,
+}
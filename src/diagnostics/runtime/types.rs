@@ -15,3 +15,54 @@ pub struct UnexpectedType {
     #[label("Unexpected type `{actual}`")]
     pub(crate) span: miette::SourceSpan,
 }
+
+/// Mismatched type error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::types::mismatched_type)"),
+    help("Try using a value of type `{expected}`.")
+)]
+#[error("Mismatched types")]
+pub struct MismatchedType {
+    pub(crate) expected: String,
+    pub(crate) found: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Expected because of this annotation")]
+    pub(crate) annotation: Option<miette::SourceSpan>,
+    #[label("Expected `{expected}`, found `{found}`")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Unknown type error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::types::unknown_type)"),
+    help("The types are `int`, `float`, `string`, `bool`, `array`, `map`, `function`, `nil` and the declared record and enum types.")
+)]
+#[error("Unknown type `{name}`")]
+pub struct UnknownType {
+    pub(crate) name: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Unknown type")]
+    pub(crate) span: miette::SourceSpan,
+}
+
+/// Invalid operands error diagnostic.
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[diagnostic(
+    code("Error(runtime::types::invalid_operands)"),
+    help("The operands of `{operator}` must be {expected}.")
+)]
+#[error("Invalid operands")]
+pub struct InvalidOperands {
+    pub(crate) operator: String,
+    pub(crate) expected: String,
+    pub(crate) left: String,
+    pub(crate) right: String,
+    #[source_code]
+    pub(crate) src: NamedSource,
+    #[label("Cannot apply `{operator}` to `{left}` and `{right}`")]
+    pub(crate) span: miette::SourceSpan,
+}
@@ -1,4 +1,4 @@
-use crate::{ast, diagnostics, parser::Rule};
+use crate::{ast, diagnostics, parser::Rule, runtime};
 use miette::{GraphicalReportHandler, JSONReportHandler};
 use pest::error::InputLocation;
 
@@ -22,9 +22,22 @@ pub enum ErrorKind {
     /// The ident is undeclared.
     /// - The first argument is the name of the ident.
     UnDeclaredIdent(String),
+    /// The ident is used after its value is moved.
+    /// - The first argument is the name of the ident.
+    /// - The second argument is the span where the value is moved.
+    ///
+    /// (The span of the error is the use of the ident.)
+    UseAfterMove(String, (usize, usize)),
+    /// The ident is used after its value is moved in only some paths, e.g. in one branch of an `if`. (This is a warning.)
+    /// - The first argument is the name of the ident.
+    /// - The second argument is the span where the value may be moved.
+    ///
+    /// (The span of the error is the use of the ident.)
+    UseAfterMaybeMove(String, (usize, usize)),
     /// Alreade decleared function.
     /// - The first argument is the name of the ident.
     /// - The second argument is the span of the old declaration.
+    ///
     /// (The span of the new declaration is stored in the error.)
     AlreadyDeclared(String, (usize, usize)),
     /// Missing main function.
@@ -33,35 +46,96 @@ pub enum ErrorKind {
     /// The exit code is invalid.
     /// - The first argument is the exit code.
     InvalidExitCode(bigdecimal::BigDecimal),
-    /// The ident is not callable.
+    /// The ident or the expression is not callable.
     /// - The first argument is the span of the call
-    /// (The span of the error is the not callable ident.)
+    ///
+    /// (The span of the error is the not callable ident or expression.)
     NotCallable((usize, usize)),
     /// Uncorrect arguments.
     /// - The first argument is the number of arguments.
     /// - The second argument is the span of the called function.
     /// - The third argument is the parametets of the called function.
     /// - The forth argument is the name of the called function.
+    ///
     /// (The span of the error is the function call expression.)
     UncorrectArguments(usize, (usize, usize), Vec<ast::Param>, String),
+    /// The named argument is not a parameter of the called function.
+    /// - The first argument is the name of the argument.
+    /// - The second argument is the span of the called function.
+    /// - The third argument is the name of the called function.
+    ///
+    /// (The span of the error is the name of the argument.)
+    UnknownArgument(String, (usize, usize), String),
+    /// The parameter is given more than one argument.
+    /// - The first argument is the name of the parameter.
+    /// - The second argument is the span of the first argument.
+    ///
+    /// (The span of the error is the named argument.)
+    DuplicateArgument(String, (usize, usize)),
+    /// The required parameter is not given an argument.
+    /// - The first argument is the name of the parameter.
+    /// - The second argument is the span of the called function.
+    /// - The third argument is the name of the called function.
+    ///
+    /// (The span of the error is the function call expression.)
+    MissingArgument(String, (usize, usize), String),
     /// Unexpected type.
     /// - The first argument is the expected type.
     /// - The second argument is the actual type.
+    ///
     /// (The span of the error is the expression.)
     UnexpectedType(String, String),
+    /// The value is not of the annotated type.
+    /// - The first argument is the type annotation.
+    /// - The second argument is the type of the value.
+    /// - The third argument is the span of the type annotation, the builtin functions have no annotation span.
+    ///
+    /// (The span of the error is the value.)
+    MismatchedType(String, String, Option<(usize, usize)>),
+    /// The type annotation has an unknown type.
+    /// - The first argument is the name of the type.
+    ///
+    /// (The span of the error is the type name.)
+    UnknownType(String),
+    /// The nested function calls exceeded the maximum call depth.
+    /// - The first argument is the maximum call depth.
+    ///
+    /// (The span of the error is the overflowing call, and the call chain is in the traceback of the error.)
+    StackOverflow(usize),
+    /// The stack of the program can't be allocated for the maximum call depth.
+    /// - The first argument is the maximum call depth.
+    /// - The second argument is the size of the stack in bytes.
+    /// - The third argument is the reason.
+    ///
+    /// (The span of the error will not be used.)
+    StackUnavailable(usize, usize, String),
     /// Multiple packed params.
     /// - The first argument is the name of the function.
+    ///
     /// (The span of the error is the parametets of the function.)
     MultiplePackedParams(String),
     /// Packed param is not last.
     /// - The first argument is the name of the packed param.
+    ///
     /// (The span of the error is the packed param.)
     PackedParamNotLast(String),
     /// Multiple params with the same name.
     /// - The first argument is the name of the param.
     /// - The second argument is the name of the function.
+    ///
     /// (The span of the error is the parametets of the function.)
     MultipleParamsWithTheSameName(String, String),
+    /// Packed param with a default value.
+    /// - The first argument is the name of the packed param.
+    ///
+    /// (The span of the error is the packed param.)
+    PackedParamWithDefault(String),
+    /// Required param after a param with a default value.
+    /// - The first argument is the name of the required param.
+    /// - The second argument is the name of the param with a default value.
+    ///
+    /// (The span of the error is the required param.)
+    RequiredParamAfterDefault(String, String),
     /// Invalid unpacked argument.
     /// - The first argument is the name of the argument type.
     InvalidUnpackArg(String),
@@ -69,6 +143,60 @@ pub enum ErrorKind {
     /// - The first argument is the reason.
     /// - The second argument is the help message.
     FormatError(String, String),
+    /// A loop control statement outside of a loop.
+    /// - The first argument is the statement keyword, `break` or `continue`.
+    ///
+    /// (The span of the error is the statement.)
+    OutsideLoop(String),
+    /// Invalid operands of a binary operator.
+    /// - The first argument is the operator.
+    /// - The second argument is the type of the left operand.
+    /// - The third argument is the type of the right operand.
+    ///
+    /// (The span of the error is the binary expression.)
+    InvalidOperands(String, String, String),
+    /// Division by zero.
+    /// (The span of the error is the divisor.)
+    DivisionByZero,
+    /// The imported file can't be imported.
+    /// - The first argument is the path of the imported file.
+    /// - The second argument is the reason.
+    ///
+    /// (The span of the error is the import statement.)
+    InvalidImport(String, String),
+    /// The imported file imports the importer file, directly or indirectly.
+    /// - The first argument is the import chain, from the importer file back to itself.
+    ///
+    /// (The span of the error is the import statement.)
+    CircularImport(Vec<String>),
+    /// No arm of the match expression matches the value.
+    /// - The first argument is the matched value.
+    ///
+    /// (The span of the error is the matched value.)
+    NoMatchingArm(String),
+    /// The match arm is unreachable, because a previous arm matches all its values. (This is a warning.)
+    /// - The first argument is the span of the previous arm.
+    ///
+    /// (The span of the error is the unreachable arm.)
+    UnreachableArm((usize, usize)),
+    /// The record has no field with the name.
+    /// - The first argument is the name of the record type.
+    /// - The second argument is the name of the field.
+    /// - The third argument is the fields of the record.
+    ///
+    /// (The span of the error is the field.)
+    UnknownField(String, String, Vec<String>),
+    /// The record expression doesn't have all the fields of the record.
+    /// - The first argument is the name of the record type.
+    /// - The second argument is the missing fields.
+    ///
+    /// (The span of the error is the record expression.)
+    MissingFields(String, Vec<String>),
+    /// A value thrown by the `throw` builtin function, and not caught.
+    /// - The first argument is the thrown value.
+    ///
+    /// (The span of the error is the `throw` call.)
+    Thrown(Box<runtime::value::Value>),
     /// Runtime error. (The error is not a bug in the interpreter.)
     /// - The first argument is the reason.
     Runtime(String),
@@ -76,11 +204,27 @@ pub enum ErrorKind {
     Parse(String),
 }
 
+/// A function call in the traceback of a runtime error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// The name of the called function, `None` for an anonymous function.
+    pub name: Option<String>,
+    /// The span of the called function, an anonymous function is labelled by the location where it's defined.
+    pub function_span: miette::SourceSpan,
+    /// The span of the call, `None` for the call of the main function.
+    pub span: Option<miette::SourceSpan>,
+}
+
 /// The error type.
 #[derive(Debug, Clone)]
 pub struct Error {
     pub kind: ErrorKind,
     pub span: miette::SourceSpan,
+    /// The calls that led to the runtime error, from the main function to the function where the error happened.
+    /// It's empty for the errors that happen before running the program.
+    // Boxed to keep the error small, it's returned in every result.
+    #[allow(clippy::box_collection)]
+    pub traceback: Box<Vec<CallSite>>,
 }
 
 pub trait SpanError {
@@ -126,34 +270,148 @@ impl Error {
         Self {
             kind,
             span: span.span().into(),
+            traceback: Box::default(),
+        }
+    }
+
+    /// Returns the error with the given traceback.
+    pub fn with_traceback(self, traceback: Vec<CallSite>) -> Self {
+        Self {
+            traceback: Box::new(traceback),
+            ..self
         }
     }
 
-    /// Returns a diagnostic for this error.
+    /// Returns the error with its spans relative to the source that starts at the given offset and has the given length.
+    /// The spans of the error that are not in the source are replaced with the span of the error.
+    pub fn relative_to(self, offset: usize, length: usize) -> Self {
+        let span = (self.span.offset() - offset, self.span.len());
+        let relative = |(start, len): (usize, usize)| {
+            if start >= offset && start + len <= offset + length {
+                (start - offset, len)
+            } else {
+                span
+            }
+        };
+        let kind = match self.kind {
+            ErrorKind::AlreadyDeclared(name, old_decl) => {
+                ErrorKind::AlreadyDeclared(name, relative(old_decl))
+            }
+            ErrorKind::UseAfterMove(name, moved_span) => {
+                ErrorKind::UseAfterMove(name, relative(moved_span))
+            }
+            ErrorKind::UseAfterMaybeMove(name, moved_span) => {
+                ErrorKind::UseAfterMaybeMove(name, relative(moved_span))
+            }
+            ErrorKind::NotCallable(call_span) => ErrorKind::NotCallable(relative(call_span)),
+            ErrorKind::MismatchedType(expected, found, annotation) => {
+                ErrorKind::MismatchedType(expected, found, annotation.map(relative))
+            }
+            ErrorKind::UnreachableArm(previous_arm) => {
+                ErrorKind::UnreachableArm(relative(previous_arm))
+            }
+            ErrorKind::UncorrectArguments(args_count, func_span, params, func_name) => {
+                ErrorKind::UncorrectArguments(args_count, relative(func_span), params, func_name)
+            }
+            ErrorKind::UnknownArgument(name, func_span, func_name) => {
+                ErrorKind::UnknownArgument(name, relative(func_span), func_name)
+            }
+            ErrorKind::DuplicateArgument(name, first_arg) => {
+                ErrorKind::DuplicateArgument(name, relative(first_arg))
+            }
+            ErrorKind::MissingArgument(name, func_span, func_name) => {
+                ErrorKind::MissingArgument(name, relative(func_span), func_name)
+            }
+            kind => kind,
+        };
+        Self {
+            kind,
+            span: span.into(),
+            traceback: self.traceback,
+        }
+    }
+
+    /// Returns whether the error is a warning, the warnings are reported but they don't stop the program.
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::UnreachableArm(_) | ErrorKind::UseAfterMaybeMove(..)
+        )
+    }
+
+    /// Returns the code of the error, which is the code of its diagnostic, e.g. `runtime::idents::undeclared_ident`.
+    pub fn code(&self) -> String {
+        let diagnostic = diagnostics::as_diagnostic::<GraphicalReportHandler>(
+            self.clone(),
+            String::new(),
+            String::new(),
+        );
+        let code = diagnostic
+            .diagnostic
+            .code()
+            .map(|code| code.to_string())
+            .unwrap_or_default();
+        code.strip_prefix("Error(")
+            .and_then(|code| code.strip_suffix(')'))
+            .map_or_else(|| code.clone(), ToOwned::to_owned)
+    }
+
+    /// Returns the message of the error, which is the label of its diagnostic at the error span,
+    /// or the diagnostic message if it has no such label.
+    pub fn message(&self) -> String {
+        let diagnostic = diagnostics::as_diagnostic::<GraphicalReportHandler>(
+            self.clone(),
+            String::new(),
+            String::new(),
+        );
+        diagnostic
+            .diagnostic
+            .labels()
+            .and_then(|labels| {
+                labels
+                    .filter(|label| label.inner() == &self.span)
+                    .filter_map(|label| label.label().map(ToOwned::to_owned))
+                    .last()
+            })
+            .unwrap_or_else(|| diagnostic.diagnostic.to_string())
+    }
+
+    /// Returns a diagnostic for this error, with its traceback if it's a runtime error.
     pub fn as_diagnostic(
         self,
         source: impl AsRef<str>,
         source_name: impl AsRef<str>,
     ) -> diagnostics::Diagnostic<GraphicalReportHandler> {
+        let traceback = self.traceback(source.as_ref(), source_name.as_ref());
         diagnostics::as_diagnostic::<GraphicalReportHandler>(
             self,
             source.as_ref().to_string(),
             source_name.as_ref().to_string(),
         )
         .rgb()
+        .with_traceback(traceback)
     }
 
-    /// Returns a json diagnostic for this error.
+    /// Returns a json diagnostic for this error, with its traceback if it's a runtime error.
     pub fn as_json_diagnostic(
         self,
         source: impl AsRef<str>,
         source_name: impl AsRef<str>,
     ) -> diagnostics::Diagnostic<JSONReportHandler> {
+        let traceback = self.traceback(source.as_ref(), source_name.as_ref());
         diagnostics::as_diagnostic::<JSONReportHandler>(
             self,
             source.as_ref().to_string(),
             source_name.as_ref().to_string(),
         )
+        .with_traceback(traceback)
+    }
+
+    /// Returns the traceback of the error, with the calls located in the given source.
+    fn traceback(&self, source: &str, source_name: &str) -> Option<String> {
+        diagnostics::traceback(&self.traceback, |offset| {
+            diagnostics::location(source, source_name, offset)
+        })
     }
 }
 
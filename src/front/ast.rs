@@ -10,7 +10,7 @@ pub trait ASTNodeSpan {
 }
 
 /// A span.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Span {
     /// The start of the span.
     pub start: usize,
@@ -51,6 +51,8 @@ pub struct Ident {
 pub struct Arg {
     /// The argument value.
     pub expr: ExpressionStatement,
+    /// The name of the param, if the argument is a named argument.
+    pub name: Option<Ident>,
     /// Whether the argument is unpacked.
     pub is_unpack: bool,
     /// The span of the argument.
@@ -64,6 +66,27 @@ pub struct Param {
     pub ident: Ident,
     /// Whether the param is a pack param.
     pub is_pack: bool,
+    /// The default value of the param, which is used when the param has no argument.
+    pub default: Option<ExpressionStatement>,
+    /// The type of the param, e.g. `<name: string>`. The type of a pack param is the type of each packed argument.
+    pub type_annotation: Option<TypeAnnotation>,
+}
+
+/// A type annotation, e.g. `int` or `int | float`.
+/// The annotated value must be of one of the types, which are the names of the objects types (see [`ObjectExpression::type_name`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAnnotation {
+    /// The names of the types.
+    pub types: Vec<Ident>,
+    /// The span of the type annotation, without the `:`.
+    pub span: Span,
+}
+
+impl TypeAnnotation {
+    /// Returns whether the type annotation accepts the values of the given type.
+    pub fn accepts(&self, type_name: &str) -> bool {
+        self.types.iter().any(|ty| ty.ident == type_name)
+    }
 }
 
 /// A visibility, which is public or private.
@@ -94,8 +117,24 @@ pub enum Statement {
     Assignment(AssignmentStatement),
     /// The return statement.
     Return(ReturnStatement),
+    /// The if statement.
+    If(IfStatement),
+    /// The break statement.
+    Break(BreakStatement),
+    /// The continue statement.
+    Continue(ContinueStatement),
     /// The expression statement.
     Expression(ExpressionStatement),
+    /// The import statement, which can only be at the top of a file.
+    Import(ImportStatement),
+    /// The try statement.
+    Try(TryStatement),
+    /// The record statement, which can only be at the top level of a file.
+    Record(RecordStatement),
+    /// The enum statement, which can only be at the top level of a file.
+    Enum(EnumStatement),
+    /// The field assignment statement.
+    FieldAssignment(FieldAssignmentStatement),
 }
 
 /// A Expression statement.
@@ -105,6 +144,20 @@ pub enum ExpressionStatement {
     FunctionCall(FunctionCallExpression),
     /// A value, which is a ident or a object.
     Value(ValueExpression),
+    /// A loop, which can only be a statement, an assigned value or a returned value.
+    Loop(LoopExpression),
+    /// A binary operation, e.g. `a + b`.
+    Binary(BinaryExpression),
+    /// A unary operation, e.g. `-a`.
+    Unary(UnaryExpression),
+    /// A match expression, e.g. `match value {< 1 => "one", _ => "other" >}`.
+    Match(MatchExpression),
+    /// A record expression, e.g. `Point {x: 1, y: 2}`.
+    Record(RecordExpression),
+    /// A field access, e.g. `point.x`.
+    FieldAccess(FieldAccessExpression),
+    /// A borrow of a variable, e.g. `&x`.
+    Borrow(BorrowExpression),
 }
 
 /// A function statement.
@@ -119,10 +172,73 @@ pub struct FunctionStatement {
     pub block: Option<Block>,
     /// The function visibility.
     pub visibility: Visibility,
+    /// The type of the returned value, e.g. `~add<a><b>: int {< ... >}`.
+    pub return_type: Option<TypeAnnotation>,
     /// The span of the function statement.
     pub span: Span,
 }
 
+/// A import statement, e.g. `import "math.oy";`.
+/// The public functions of the imported file are available in the importer file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportStatement {
+    /// The path of the imported file, relative to the importer file.
+    pub path: String,
+    /// The span of the import statement.
+    pub span: Span,
+}
+
+/// A record statement, e.g. `record Point<x><y>;`, which declares a record type with named fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordStatement {
+    /// The name of the record type.
+    pub ident: Ident,
+    /// The fields of the record, in the declaration order.
+    pub fields: Vec<Ident>,
+    /// The record visibility, the public records can be used in other files.
+    pub visibility: Visibility,
+    /// The span of the record statement.
+    pub span: Span,
+}
+
+/// A enum statement, e.g. `enum Option<Some<value>><None>;`, which declares a enum type with its variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumStatement {
+    /// The name of the enum type.
+    pub ident: Ident,
+    /// The variants of the enum.
+    pub variants: Vec<Variant>,
+    /// The enum visibility, the public enums can be used in other files.
+    pub visibility: Visibility,
+    /// The span of the enum statement.
+    pub span: Span,
+}
+
+/// A variant of a enum, e.g. `<Some<value>>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// The name of the variant.
+    pub ident: Ident,
+    /// The names of the payload values, a variant without fields has no payload.
+    pub fields: Vec<Ident>,
+    /// The span of the variant.
+    pub span: Span,
+}
+
+/// A field assignment statement, e.g. `point.x = 1;`.
+/// The value is moved to the field of the record variable, the other fields are not changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldAssignmentStatement {
+    /// The record variable.
+    pub ident: Ident,
+    /// The path of the assigned field, e.g. `[line, start]` for `shape.line.start = point;`.
+    pub fields: Vec<Ident>,
+    /// The value to assign.
+    pub expression: ExpressionStatement,
+    /// The span of the field assignment statement.
+    pub span: Span,
+}
+
 /// A assignment statement.
 /// This is a assignment of a value to a ident.
 /// The value is a expression.
@@ -132,6 +248,8 @@ pub struct AssignmentStatement {
     pub ident: Ident,
     /// The value to assign.
     pub expression: ExpressionStatement,
+    /// The type of the variable, e.g. `name: string = "Ocypode";`.
+    pub type_annotation: Option<TypeAnnotation>,
     /// The span of the assignment statement.
     pub span: Span,
 }
@@ -145,11 +263,233 @@ pub struct ReturnStatement {
     pub value: ExpressionStatement,
 }
 
+/// A if statement.
+/// The first block whose condition is truthy is executed, if there is no truthy condition the else block is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IfStatement {
+    /// The condition of the if statement.
+    pub condition: ExpressionStatement,
+    /// The block to execute if the condition is truthy.
+    pub block: Block,
+    /// The `else if` branches, checked in order.
+    pub else_ifs: Vec<ElseIf>,
+    /// The block to execute if there is no truthy condition.
+    pub else_block: Option<Block>,
+    /// The span of the if statement.
+    pub span: Span,
+}
+
+/// A `else if` branch of a if statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElseIf {
+    /// The condition of the branch.
+    pub condition: ExpressionStatement,
+    /// The block to execute if the condition is truthy.
+    pub block: Block,
+    /// The span of the branch.
+    pub span: Span,
+}
+
+/// A try statement, e.g. `try {< ... >} catch err {< ... >}`.
+/// If the try block fails, the error is moved to the ident and the catch block is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryStatement {
+    /// The block to try.
+    pub block: Block,
+    /// The ident of the caught error, which is available in the catch block.
+    pub ident: Ident,
+    /// The block to execute if the try block fails.
+    pub catch_block: Block,
+    /// The span of the try statement.
+    pub span: Span,
+}
+
+/// A break statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakStatement {
+    /// The value of the loop. If the value is None, the loop value is `nil`.
+    pub value: Option<ExpressionStatement>,
+    /// The span of the break statement.
+    pub span: Span,
+}
+
+/// A continue statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContinueStatement {
+    /// The span of the continue statement.
+    pub span: Span,
+}
+
+/// A loop expression.
+/// The value of the loop is the value of the `break` statement that stopped it, or `nil`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopExpression {
+    /// The kind of the loop.
+    pub kind: LoopKind,
+    /// The loop block, executed in a new scope in each iteration.
+    pub block: Block,
+    /// The span of the loop expression.
+    pub span: Span,
+}
+
+/// A loop kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoopKind {
+    /// A unconditional loop, `loop {< ... >}`.
+    Loop,
+    /// A while loop, `while condition {< ... >}`. The condition is checked before each iteration.
+    While(Box<ExpressionStatement>),
+    /// A for loop, `for item in iterable {< ... >}`.
+    /// Each element of the array (or character of the string) is moved to the ident in each iteration.
+    For(Ident, Box<ExpressionStatement>),
+}
+
+/// A match expression.
+/// The value of the match is the expression of the first arm whose pattern matches the value and whose guard is truthy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchExpression {
+    /// The matched value.
+    pub value: Box<ExpressionStatement>,
+    /// The arms, checked in order.
+    pub arms: Vec<MatchArm>,
+    /// The span of the match expression.
+    pub span: Span,
+}
+
+/// A arm of a match expression, e.g. `[first, *rest] if first > 0 => first`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchArm {
+    /// The pattern of the arm.
+    pub pattern: Pattern,
+    /// The guard of the arm, the arm is only taken if the guard is truthy.
+    pub guard: Option<ExpressionStatement>,
+    /// The expression of the arm, executed in a new scope with the bound idents of the pattern.
+    pub expression: ExpressionStatement,
+    /// The span of the arm.
+    pub span: Span,
+}
+
+/// A pattern of a match arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    /// A literal, e.g. `1`, `"a"`, `true` or `nil`. Matches the equal values.
+    Literal(ObjectExpression),
+    /// The wildcard `_`, matches any value.
+    Wildcard(Span),
+    /// A ident, matches any value and binds it to the ident.
+    Ident(Ident),
+    /// A array pattern, e.g. `[first, *rest]`. Matches the arrays whose elements match the patterns.
+    /// - The first argument is the patterns of the elements.
+    /// - The second argument is the rest pattern (a wildcard or an ident), which matches the remaining elements as an array.
+    Array(Vec<Pattern>, Option<Box<Pattern>>, Span),
+    /// A variant pattern, e.g. `Some<value>` or `None`. Matches the values of the variant whose payload values match the patterns.
+    /// - The first argument is the name of the variant.
+    /// - The second argument is the patterns of the payload values.
+    Variant(Ident, Vec<Pattern>, Span),
+}
+
+/// A record expression, which creates a record of a declared record type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordExpression {
+    /// The name of the record type.
+    pub ident: Ident,
+    /// The fields with their values, in the written order.
+    pub fields: Vec<(Ident, ExpressionStatement)>,
+    /// The span of the record expression.
+    pub span: Span,
+}
+
+/// A field access expression.
+/// The record is moved to get the field, like the other uses of a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldAccessExpression {
+    /// The record.
+    pub value: Box<ExpressionStatement>,
+    /// The accessed field.
+    pub field: Ident,
+    /// The span of the field access expression.
+    pub span: Span,
+}
+
+/// A borrow expression.
+/// The value of the variable is read without moving it, so the variable can be used again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorrowExpression {
+    /// The borrowed ident.
+    pub ident: Ident,
+    /// The span of the borrow expression.
+    pub span: Span,
+}
+
+/// A binary expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryExpression {
+    /// The left operand.
+    pub left: Box<ExpressionStatement>,
+    /// The operator.
+    pub operator: BinaryOperator,
+    /// The right operand.
+    pub right: Box<ExpressionStatement>,
+    /// The span of the binary expression.
+    pub span: Span,
+}
+
+/// A binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    /// The addition operator, `+`.
+    Add,
+    /// The subtraction operator, `-`.
+    Sub,
+    /// The multiplication operator, `*`.
+    Mul,
+    /// The division operator, `/`.
+    Div,
+    /// The modulo operator, `%`.
+    Mod,
+    /// The equality operator, `==`.
+    Eq,
+    /// The inequality operator, `!=`.
+    Ne,
+    /// The less than operator, `<`.
+    Lt,
+    /// The less than or equal operator, `<=`.
+    Le,
+    /// The greater than operator, `>`.
+    Gt,
+    /// The greater than or equal operator, `>=`.
+    Ge,
+    /// The logical and operator, `&&`.
+    And,
+    /// The logical or operator, `||`.
+    Or,
+}
+
+/// A unary expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnaryExpression {
+    /// The operator.
+    pub operator: UnaryOperator,
+    /// The operand.
+    pub operand: Box<ExpressionStatement>,
+    /// The span of the unary expression.
+    pub span: Span,
+}
+
+/// A unary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    /// The negation operator, `-`.
+    Neg,
+    /// The logical not operator, `!`.
+    Not,
+}
+
 /// A function call expression.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FunctionCallExpression {
-    /// The function name.
-    pub callable: ValueExpression,
+    /// The called expression, which is a function name, an anonymous function or an expression in parentheses.
+    pub callable: Box<ExpressionStatement>,
     /// The arguments to the function.
     pub args: Vec<Arg>,
     /// The span of the function call expression.
@@ -180,13 +520,15 @@ pub enum ObjectExpression {
     Bool(bool, Span),
     /// A array.
     Array(Vec<ExpressionStatement>, Span),
+    /// A map of keys to values, the entries are in the insertion order.
+    Map(Vec<(ExpressionStatement, ExpressionStatement)>, Span),
     /// A nil.
     Nil(Span),
 }
 
 impl ObjectExpression {
     /// Returns the name of the type of the object.
-    pub fn type_name(&self) -> &'static str {
+    pub fn type_name(&self) -> &str {
         match self {
             ObjectExpression::Function(_) => "function",
             ObjectExpression::String(_, _) => "string",
@@ -194,52 +536,51 @@ impl ObjectExpression {
             ObjectExpression::Float(_, _) => "float",
             ObjectExpression::Bool(_, _) => "bool",
             ObjectExpression::Array(_, _) => "array",
+            ObjectExpression::Map(_, _) => "map",
             ObjectExpression::Nil(_) => "nil",
         }
     }
 }
 
-impl ToString for ObjectExpression {
-    fn to_string(&self) -> String {
-        match self {
-            ObjectExpression::Function(func) => {
-                let func_type = if func.block.is_none() {
-                    "<builtin-function> "
-                } else {
-                    ""
-                };
-                format!(
-                    "{}{}{}",
-                    func_type,
-                    func.ident
-                        .as_ref()
-                        .map_or_else(|| "Anonymous Function".to_string(), |i| i.ident.clone()),
-                    func.params
-                        .iter()
-                        .map(|param| format!("<{}>", param.ident.ident))
-                        .collect::<String>()
-                )
-            }
-            ObjectExpression::String(string, _) => string.clone(),
-            ObjectExpression::Int(int, _) => int.to_string(),
-            ObjectExpression::Float(float, _) => float.to_string(),
-            ObjectExpression::Bool(boolean, _) => boolean.to_string(),
-            ObjectExpression::Array(arr, _) => {
-                format!(
-                    "[{}]",
-                    arr.iter()
-                        .map(|e| match e {
-                            ExpressionStatement::Value(ValueExpression::Object(obj)) => {
-                                obj.to_string()
-                            }
-                            _ => unreachable!("array can only contain objects"),
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                )
-            }
-            ObjectExpression::Nil(_) => "nil".to_string(),
-        }
+impl std::fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let types = self
+            .types
+            .iter()
+            .map(|ty| ty.ident.as_str())
+            .collect::<Vec<_>>();
+        write!(f, "{}", types.join(" | "))
+    }
+}
+
+impl std::fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Sub => "-",
+            BinaryOperator::Mul => "*",
+            BinaryOperator::Div => "/",
+            BinaryOperator::Mod => "%",
+            BinaryOperator::Eq => "==",
+            BinaryOperator::Ne => "!=",
+            BinaryOperator::Lt => "<",
+            BinaryOperator::Le => "<=",
+            BinaryOperator::Gt => ">",
+            BinaryOperator::Ge => ">=",
+            BinaryOperator::And => "&&",
+            BinaryOperator::Or => "||",
+        };
+        write!(f, "{}", operator)
+    }
+}
+
+impl std::fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = match self {
+            UnaryOperator::Neg => "-",
+            UnaryOperator::Not => "!",
+        };
+        write!(f, "{}", operator)
     }
 }
 
@@ -261,6 +602,15 @@ impl ASTNodeSpan for Param {
     }
 }
 
+impl ASTNodeSpan for TypeAnnotation {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
 impl ASTNodeSpan for Block {
     fn span(&self) -> Span {
         self.span
@@ -279,6 +629,42 @@ impl ASTNodeSpan for FunctionStatement {
     }
 }
 
+impl ASTNodeSpan for RecordStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for EnumStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for Variant {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for FieldAssignmentStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
 impl ASTNodeSpan for AssignmentStatement {
     fn span(&self) -> Span {
         self.span
@@ -297,6 +683,153 @@ impl ASTNodeSpan for ReturnStatement {
     }
 }
 
+impl ASTNodeSpan for IfStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for ElseIf {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for TryStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for BreakStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for ContinueStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for ImportStatement {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for LoopExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for MatchExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for MatchArm {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for Pattern {
+    fn span(&self) -> Span {
+        match self {
+            Pattern::Literal(literal) => literal.span(),
+            Pattern::Wildcard(span) | Pattern::Array(_, _, span) | Pattern::Variant(_, _, span) => {
+                *span
+            }
+            Pattern::Ident(ident) => ident.span(),
+        }
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Pattern::Literal(literal) => literal.span_mut(),
+            Pattern::Wildcard(span) | Pattern::Array(_, _, span) | Pattern::Variant(_, _, span) => {
+                span
+            }
+            Pattern::Ident(ident) => ident.span_mut(),
+        }
+    }
+}
+
+impl ASTNodeSpan for RecordExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for FieldAccessExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for BorrowExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for BinaryExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
+impl ASTNodeSpan for UnaryExpression {
+    fn span(&self) -> Span {
+        self.span
+    }
+    fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+}
+
 impl ASTNodeSpan for FunctionCallExpression {
     fn span(&self) -> Span {
         self.span
@@ -330,6 +863,7 @@ impl ASTNodeSpan for ObjectExpression {
             ObjectExpression::Float(_, span) => *span,
             ObjectExpression::Bool(_, span) => *span,
             ObjectExpression::Array(_, span) => *span,
+            ObjectExpression::Map(_, span) => *span,
             ObjectExpression::Nil(span) => *span,
         }
     }
@@ -341,6 +875,7 @@ impl ASTNodeSpan for ObjectExpression {
             ObjectExpression::Float(_, span) => span,
             ObjectExpression::Bool(_, span) => span,
             ObjectExpression::Array(_, span) => span,
+            ObjectExpression::Map(_, span) => span,
             ObjectExpression::Nil(span) => span,
         }
     }
@@ -351,12 +886,26 @@ impl ASTNodeSpan for ExpressionStatement {
         match self {
             ExpressionStatement::FunctionCall(function_call) => function_call.span(),
             ExpressionStatement::Value(value) => value.span(),
+            ExpressionStatement::Loop(loop_expr) => loop_expr.span(),
+            ExpressionStatement::Binary(binary) => binary.span(),
+            ExpressionStatement::Unary(unary) => unary.span(),
+            ExpressionStatement::Match(match_expr) => match_expr.span(),
+            ExpressionStatement::Record(record) => record.span(),
+            ExpressionStatement::FieldAccess(field_access) => field_access.span(),
+            ExpressionStatement::Borrow(borrow) => borrow.span(),
         }
     }
     fn span_mut(&mut self) -> &mut Span {
         match self {
             ExpressionStatement::FunctionCall(function_call) => function_call.span_mut(),
             ExpressionStatement::Value(value) => value.span_mut(),
+            ExpressionStatement::Loop(loop_expr) => loop_expr.span_mut(),
+            ExpressionStatement::Binary(binary) => binary.span_mut(),
+            ExpressionStatement::Unary(unary) => unary.span_mut(),
+            ExpressionStatement::Match(match_expr) => match_expr.span_mut(),
+            ExpressionStatement::Record(record) => record.span_mut(),
+            ExpressionStatement::FieldAccess(field_access) => field_access.span_mut(),
+            ExpressionStatement::Borrow(borrow) => borrow.span_mut(),
         }
     }
 }
@@ -367,6 +916,14 @@ impl ASTNodeSpan for Statement {
             Statement::Function(function) => function.span(),
             Statement::Assignment(assignment) => assignment.span(),
             Statement::Return(return_statement) => return_statement.span(),
+            Statement::If(if_statement) => if_statement.span(),
+            Statement::Break(break_statement) => break_statement.span(),
+            Statement::Continue(continue_statement) => continue_statement.span(),
+            Statement::Import(import_statement) => import_statement.span(),
+            Statement::Try(try_statement) => try_statement.span(),
+            Statement::Record(record_statement) => record_statement.span(),
+            Statement::Enum(enum_statement) => enum_statement.span(),
+            Statement::FieldAssignment(field_assignment) => field_assignment.span(),
             Statement::Expression(expression) => expression.span(),
         }
     }
@@ -375,6 +932,14 @@ impl ASTNodeSpan for Statement {
             Statement::Function(function) => function.span_mut(),
             Statement::Assignment(assignment) => assignment.span_mut(),
             Statement::Return(return_statement) => return_statement.span_mut(),
+            Statement::If(if_statement) => if_statement.span_mut(),
+            Statement::Break(break_statement) => break_statement.span_mut(),
+            Statement::Continue(continue_statement) => continue_statement.span_mut(),
+            Statement::Import(import_statement) => import_statement.span_mut(),
+            Statement::Try(try_statement) => try_statement.span_mut(),
+            Statement::Record(record_statement) => record_statement.span_mut(),
+            Statement::Enum(enum_statement) => enum_statement.span_mut(),
+            Statement::FieldAssignment(field_assignment) => field_assignment.span_mut(),
             Statement::Expression(expression) => expression.span_mut(),
         }
     }
@@ -1,3 +1,8 @@
 pub mod ast;
+pub mod modules;
+pub mod ownership;
 pub mod parser;
+pub mod resolver;
+pub mod types;
 pub(crate) mod utils;
+pub mod warnings;
@@ -0,0 +1,405 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use miette::GraphicalReportHandler;
+
+use crate::{
+    ast::*,
+    diagnostics::{self, Diagnostic},
+    errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult},
+    parser::OYParser,
+    warnings,
+};
+
+/// A source file of the program.
+#[derive(Debug, Clone)]
+pub struct Source {
+    /// The name of the source, which is its path.
+    pub name: String,
+    /// The source code.
+    pub code: String,
+    /// The offset of the source, the spans of the source start from it.
+    pub offset: usize,
+}
+
+/// The source files of the program.
+/// Each source has its own range of spans, so a span (or an error) points to exactly one source.
+#[derive(Debug, Clone, Default)]
+pub struct Sources {
+    sources: Vec<Source>,
+}
+
+impl Sources {
+    /// Adds the given source, and returns its offset.
+    pub fn add(&mut self, name: String, code: String) -> usize {
+        // The sources are separated by one position, so the end of a source is not the start of the next one.
+        let offset = self
+            .sources
+            .last()
+            .map_or(0, |source| source.offset + source.code.len() + 1);
+        self.sources.push(Source { name, code, offset });
+        offset
+    }
+
+    /// Returns the source that contains the given offset.
+    pub fn find(&self, offset: usize) -> &Source {
+        self.sources
+            .iter()
+            .rev()
+            .find(|source| source.offset <= offset)
+            .expect("There must be at least one source")
+    }
+
+    /// Returns a diagnostic for the given error, in the source that contains it.
+    /// The calls of its traceback are located in their sources, which can be other than the source of the error.
+    pub fn as_diagnostic(&self, mut err: OYError) -> Diagnostic<GraphicalReportHandler> {
+        let calls = std::mem::take(&mut err.traceback);
+        let traceback = diagnostics::traceback(&calls, |offset| {
+            let source = self.find(offset);
+            diagnostics::location(&source.code, &source.name, offset - source.offset)
+        });
+        let source = self.find(err.span.offset());
+        err.relative_to(source.offset, source.code.len())
+            .as_diagnostic(&source.code, &source.name)
+            .with_traceback(traceback)
+    }
+}
+
+/// A module of the program, which is a loaded source file.
+#[derive(Debug, Clone)]
+pub struct Module {
+    /// The offset of the module source, see [`Sources`].
+    pub offset: usize,
+    /// The functions of the module.
+    pub functions: Vec<FunctionStatement>,
+    /// The record types of the module.
+    pub records: Vec<RecordStatement>,
+    /// The enum types of the module.
+    pub enums: Vec<EnumStatement>,
+    /// The indexes of the imported modules, with the spans of their import statements.
+    pub imports: Vec<(usize, Span)>,
+    /// The warnings of the module, see [`warnings::check`].
+    pub warnings: Vec<OYError>,
+}
+
+impl Module {
+    /// Creates the main module of a program that is not loaded from a file, so it can't contain imports.
+    pub fn from_program(program: Program) -> OYResult<Self> {
+        let mut functions = Vec::new();
+        let mut records = Vec::new();
+        let mut enums = Vec::new();
+        for statement in program.0 {
+            match statement {
+                Statement::Function(function) => functions.push(function),
+                Statement::Record(record) => records.push(record),
+                Statement::Enum(enum_stmt) => enums.push(enum_stmt),
+                Statement::Import(import) => {
+                    return Err(OYError::new(
+                        OYErrorKind::InvalidImport(
+                            import.path,
+                            "The imports need the program to be loaded from a file".to_owned(),
+                        ),
+                        import.span,
+                    ))
+                }
+                _ => unreachable!("The program only contains imports, functions and types"),
+            }
+        }
+        Ok(Self {
+            offset: 0,
+            functions,
+            records,
+            enums,
+            imports: Vec::new(),
+            warnings: Vec::new(),
+        })
+    }
+}
+
+/// Loads the given main file and the files that it imports, the first module is the main module.
+/// The loaded files are added to the given sources, so the error can be shown even if the loading fails.
+pub fn load(sources: &mut Sources, path: impl AsRef<Path>, code: String) -> OYResult<Vec<Module>> {
+    let mut loader = Loader {
+        sources,
+        modules: Vec::new(),
+        paths: Vec::new(),
+        stack: Vec::new(),
+    };
+    loader.load(path.as_ref(), code)?;
+    Ok(loader
+        .modules
+        .into_iter()
+        .map(|module| module.expect("All the modules are loaded"))
+        .collect())
+}
+
+/// The loader of the modules.
+struct Loader<'a> {
+    /// The sources of the loaded modules.
+    sources: &'a mut Sources,
+    /// The loaded modules, the module is `None` while its imports are loading.
+    modules: Vec<Option<Module>>,
+    /// The canonical paths of the modules, to not load a file twice.
+    paths: Vec<PathBuf>,
+    /// The indexes of the modules that are loading, from the main module to the current module.
+    stack: Vec<usize>,
+}
+
+impl Loader<'_> {
+    /// Loads the given file and its imports, and returns the index of its module.
+    fn load(&mut self, path: &Path, code: String) -> OYResult<usize> {
+        let index = self.modules.len();
+        let offset = self.sources.add(path.display().to_string(), code.clone());
+        self.modules.push(None);
+        self.paths
+            .push(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+        self.stack.push(index);
+
+        // The parser errors only have the span of the error, so only it is moved to the source offset.
+        let (mut statements, _) = OYParser::parse_program(&code).map_err(|err| OYError {
+            span: (err.span.offset() + offset, err.span.len()).into(),
+            ..err
+        })?;
+        let mut functions = Vec::new();
+        let mut records = Vec::new();
+        let mut enums = Vec::new();
+        let mut imports = Vec::new();
+        statements.shift_spans(offset);
+        let warnings = warnings::check(&statements);
+        for statement in statements {
+            match statement {
+                Statement::Import(import) => {
+                    imports.push((self.load_import(&import, path)?, import.span));
+                }
+                Statement::Function(function) => functions.push(function),
+                Statement::Record(record) => records.push(record),
+                Statement::Enum(enum_stmt) => enums.push(enum_stmt),
+                _ => unreachable!("The program only contains imports, functions and types"),
+            }
+        }
+
+        self.stack.pop();
+        self.modules[index] = Some(Module {
+            offset,
+            functions,
+            records,
+            enums,
+            imports,
+            warnings,
+        });
+        Ok(index)
+    }
+
+    /// Loads the imported file, relative to the importer file, and returns the index of its module.
+    fn load_import(&mut self, import: &ImportStatement, importer: &Path) -> OYResult<usize> {
+        let invalid_import = |err: std::io::Error| {
+            OYError::new(
+                OYErrorKind::InvalidImport(import.path.clone(), err.to_string()),
+                import.span,
+            )
+        };
+        let path = importer
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&import.path);
+        let canonical_path = fs::canonicalize(&path).map_err(invalid_import)?;
+        if let Some(index) = self.paths.iter().position(|path| path == &canonical_path) {
+            if let Some(cycle_start) = self.stack.iter().position(|module| module == &index) {
+                let chain = self.stack[cycle_start..]
+                    .iter()
+                    .chain([&index])
+                    .map(|module| self.sources.sources[*module].name.clone())
+                    .collect();
+                return Err(OYError::new(
+                    OYErrorKind::CircularImport(chain),
+                    import.span,
+                ));
+            }
+            return Ok(index);
+        }
+        let code = fs::read_to_string(&path).map_err(invalid_import)?;
+        self.load(&path, code)
+    }
+}
+
+/// Shifts the spans of a node by the given offset, so they point to the source of the node in [`Sources`].
+trait ShiftSpans {
+    fn shift_spans(&mut self, offset: usize);
+}
+
+impl ShiftSpans for Span {
+    fn shift_spans(&mut self, offset: usize) {
+        self.start += offset;
+        self.end += offset;
+    }
+}
+
+impl<T: ShiftSpans> ShiftSpans for Box<T> {
+    fn shift_spans(&mut self, offset: usize) {
+        self.as_mut().shift_spans(offset);
+    }
+}
+
+impl<T: ShiftSpans> ShiftSpans for Option<T> {
+    fn shift_spans(&mut self, offset: usize) {
+        if let Some(node) = self {
+            node.shift_spans(offset);
+        }
+    }
+}
+
+impl<T: ShiftSpans> ShiftSpans for Vec<T> {
+    fn shift_spans(&mut self, offset: usize) {
+        self.iter_mut().for_each(|node| node.shift_spans(offset));
+    }
+}
+
+impl<A: ShiftSpans, B: ShiftSpans> ShiftSpans for (A, B) {
+    fn shift_spans(&mut self, offset: usize) {
+        self.0.shift_spans(offset);
+        self.1.shift_spans(offset);
+    }
+}
+
+/// Implements [`ShiftSpans`] for a struct, by shifting the spans of the given fields.
+macro_rules! impl_shift_spans {
+    ($($node:ty => $($field:ident),+;)+) => {
+        $(
+            impl ShiftSpans for $node {
+                fn shift_spans(&mut self, offset: usize) {
+                    $(self.$field.shift_spans(offset);)+
+                }
+            }
+        )+
+    };
+}
+
+impl_shift_spans! {
+    Ident => span;
+    Arg => expr, name, span;
+    Param => ident, default, type_annotation;
+    TypeAnnotation => types, span;
+    Block => statements, span;
+    FunctionStatement => ident, params, block, return_type, span;
+    AssignmentStatement => ident, expression, type_annotation, span;
+    ReturnStatement => value, span;
+    IfStatement => condition, block, else_ifs, else_block, span;
+    ElseIf => condition, block, span;
+    TryStatement => block, ident, catch_block, span;
+    RecordStatement => ident, fields, span;
+    EnumStatement => ident, variants, span;
+    Variant => ident, fields, span;
+    FieldAssignmentStatement => ident, fields, expression, span;
+    BreakStatement => value, span;
+    ContinueStatement => span;
+    ImportStatement => span;
+    LoopExpression => kind, block, span;
+    BinaryExpression => left, right, span;
+    UnaryExpression => operand, span;
+    FunctionCallExpression => callable, args, span;
+    MatchExpression => value, arms, span;
+    MatchArm => pattern, guard, expression, span;
+    RecordExpression => ident, fields, span;
+    FieldAccessExpression => value, field, span;
+    BorrowExpression => ident, span;
+}
+
+impl ShiftSpans for Statement {
+    fn shift_spans(&mut self, offset: usize) {
+        match self {
+            Statement::Function(function) => function.shift_spans(offset),
+            Statement::Assignment(assignment) => assignment.shift_spans(offset),
+            Statement::Return(return_stmt) => return_stmt.shift_spans(offset),
+            Statement::If(if_stmt) => if_stmt.shift_spans(offset),
+            Statement::Break(break_stmt) => break_stmt.shift_spans(offset),
+            Statement::Continue(continue_stmt) => continue_stmt.shift_spans(offset),
+            Statement::Expression(expression) => expression.shift_spans(offset),
+            Statement::Import(import) => import.shift_spans(offset),
+            Statement::Try(try_stmt) => try_stmt.shift_spans(offset),
+            Statement::Record(record) => record.shift_spans(offset),
+            Statement::Enum(enum_stmt) => enum_stmt.shift_spans(offset),
+            Statement::FieldAssignment(assignment) => assignment.shift_spans(offset),
+        }
+    }
+}
+
+impl ShiftSpans for ExpressionStatement {
+    fn shift_spans(&mut self, offset: usize) {
+        match self {
+            ExpressionStatement::FunctionCall(func_call) => func_call.shift_spans(offset),
+            ExpressionStatement::Value(value) => value.shift_spans(offset),
+            ExpressionStatement::Loop(loop_expr) => loop_expr.shift_spans(offset),
+            ExpressionStatement::Binary(binary) => binary.shift_spans(offset),
+            ExpressionStatement::Unary(unary) => unary.shift_spans(offset),
+            ExpressionStatement::Match(match_expr) => match_expr.shift_spans(offset),
+            ExpressionStatement::Record(record) => record.shift_spans(offset),
+            ExpressionStatement::FieldAccess(field_access) => field_access.shift_spans(offset),
+            ExpressionStatement::Borrow(borrow) => borrow.shift_spans(offset),
+        }
+    }
+}
+
+impl ShiftSpans for Pattern {
+    fn shift_spans(&mut self, offset: usize) {
+        match self {
+            Pattern::Literal(literal) => literal.shift_spans(offset),
+            Pattern::Wildcard(span) => span.shift_spans(offset),
+            Pattern::Ident(ident) => ident.shift_spans(offset),
+            Pattern::Array(patterns, rest, span) => {
+                patterns.shift_spans(offset);
+                rest.shift_spans(offset);
+                span.shift_spans(offset);
+            }
+            Pattern::Variant(ident, patterns, span) => {
+                ident.shift_spans(offset);
+                patterns.shift_spans(offset);
+                span.shift_spans(offset);
+            }
+        }
+    }
+}
+
+impl ShiftSpans for LoopKind {
+    fn shift_spans(&mut self, offset: usize) {
+        match self {
+            LoopKind::Loop => {}
+            LoopKind::While(condition) => condition.shift_spans(offset),
+            LoopKind::For(ident, iterable) => {
+                ident.shift_spans(offset);
+                iterable.shift_spans(offset);
+            }
+        }
+    }
+}
+
+impl ShiftSpans for ValueExpression {
+    fn shift_spans(&mut self, offset: usize) {
+        match self {
+            ValueExpression::Ident(ident) => ident.shift_spans(offset),
+            ValueExpression::Object(object) => object.shift_spans(offset),
+        }
+    }
+}
+
+impl ShiftSpans for ObjectExpression {
+    fn shift_spans(&mut self, offset: usize) {
+        match self {
+            ObjectExpression::Function(function) => function.shift_spans(offset),
+            ObjectExpression::Array(array, span) => {
+                array.shift_spans(offset);
+                span.shift_spans(offset);
+            }
+            ObjectExpression::Map(map, span) => {
+                map.shift_spans(offset);
+                span.shift_spans(offset);
+            }
+            ObjectExpression::String(_, span)
+            | ObjectExpression::Int(_, span)
+            | ObjectExpression::Float(_, span)
+            | ObjectExpression::Bool(_, span)
+            | ObjectExpression::Nil(span) => span.shift_spans(offset),
+        }
+    }
+}
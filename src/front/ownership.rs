@@ -0,0 +1,781 @@
+//! The ownership checker, it simulates the moves of the runtime (see [`Environment::take`]) before running the program,
+//! and reports all the uses of the moved variables and the undeclared idents at once.
+//!
+//! A variable is moved after an `if`, a `match` or a `try` only if it's moved in all their branches, so the checker
+//! doesn't reject a program that may run without errors. The uses of the variables that are moved in only some branches
+//! are reported as warnings (see [`OYError::is_warning`]), and they are checked by the runtime. The block of a loop is checked twice, because a variable that is moved in an iteration is moved
+//! in the next one.
+//!
+//! [`Environment::take`]: crate::runtime::environment::Environment::take
+
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, SpanError},
+    modules::Module,
+    runtime::{builtins::Builtins, closures, interpreter},
+};
+
+/// Returns the ownership errors and warnings of the given statements, the statements are the program of one file.
+pub fn check(statements: &[Statement]) -> Vec<OYError> {
+    let builtins = Builtins::new().functions;
+    let functions = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let enums = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Enum(enum_stmt) => Some(enum_stmt),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    Checker::new(Globals::new(&functions, &enums, &builtins)).check(&functions)
+}
+
+/// Returns the ownership errors and warnings of the given modules, the global idents of a module are its own, the public
+/// ones of its imports and the given builtin functions.
+pub fn check_modules(modules: &[Module], builtins: &[FunctionStatement]) -> Vec<OYError> {
+    let mut errors = Vec::new();
+    for module in modules {
+        let imports = module.imports.iter().map(|(import, _)| &modules[*import]);
+        let functions = module
+            .functions
+            .iter()
+            .chain(imports.clone().flat_map(|import| {
+                import
+                    .functions
+                    .iter()
+                    .filter(|function| function.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        let enums = module
+            .enums
+            .iter()
+            .chain(imports.flat_map(|import| {
+                import
+                    .enums
+                    .iter()
+                    .filter(|enum_stmt| enum_stmt.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        let checker = Checker::new(Globals::new(&functions, &enums, builtins));
+        errors.extend(checker.check(&module.functions.iter().collect::<Vec<_>>()));
+    }
+    errors
+}
+
+/// The global idents of a module, which are never moved.
+struct Globals<'a> {
+    /// The global functions, with the builtin functions.
+    functions: Vec<&'a FunctionStatement>,
+    /// The variants of the enum types.
+    variants: Vec<&'a Variant>,
+    /// The names of the global functions that never return, e.g. the functions that always throw.
+    never_return: Vec<String>,
+}
+
+impl<'a> Globals<'a> {
+    fn new(
+        functions: &[&'a FunctionStatement],
+        enums: &[&'a EnumStatement],
+        builtins: &'a [FunctionStatement],
+    ) -> Self {
+        Self {
+            functions: functions.iter().copied().chain(builtins).collect(),
+            variants: enums
+                .iter()
+                .flat_map(|enum_stmt| &enum_stmt.variants)
+                .collect(),
+            never_return: vec!["throw".to_owned()],
+        }
+    }
+
+    /// Returns the global function by name.
+    fn function(&self, name: &str) -> Option<&'a FunctionStatement> {
+        self.functions
+            .iter()
+            // Global functions must have an identifier.
+            .find(|function| function.ident.as_ref().unwrap().ident == name)
+            .copied()
+    }
+
+    /// Returns the variant by name.
+    fn variant(&self, name: &str) -> Option<&'a Variant> {
+        self.variants
+            .iter()
+            .find(|variant| variant.ident.ident == name)
+            .copied()
+    }
+}
+
+/// The state of a variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ownership {
+    /// The variable owns its value.
+    Owned,
+    /// The value of the variable is moved, with the span where it's moved.
+    Moved((usize, usize)),
+    /// The value of the variable is moved in only some paths, with the span where it may be moved.
+    MaybeMoved((usize, usize)),
+}
+
+/// The idents that are declared in a scope.
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    /// The variables, with their state.
+    variables: Vec<(String, Ownership)>,
+    /// The local functions, with whether they never return.
+    /// They are copied when they are used, so they are never moved.
+    functions: Vec<(String, bool)>,
+}
+
+/// How an ident is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Use {
+    /// The value is moved, e.g. `x`.
+    Move,
+    /// The value is copied, e.g. `&x`.
+    Borrow,
+}
+
+/// What an ident refers to.
+enum Resolved<'a> {
+    /// A global function (or a builtin function).
+    Global(&'a FunctionStatement),
+    /// A variant, a local function or a variable.
+    Local,
+    /// A moved variable or an undeclared ident, the error is reported.
+    Error,
+}
+
+/// The states of a loop, they are collected from its `break` and `continue` statements.
+#[derive(Debug, Default)]
+struct Loop {
+    /// The number of scopes outside the loop.
+    depth: usize,
+    /// The state after the loop, from the `break` statements.
+    breaks: Option<Vec<Scope>>,
+    /// The state of the next iteration, from the `continue` statements.
+    continues: Option<Vec<Scope>>,
+}
+
+/// The ownership checker of a module.
+struct Checker<'a> {
+    globals: Globals<'a>,
+    /// The scopes of the checked function, from the outermost to the innermost.
+    scopes: Vec<Scope>,
+    /// The loops of the checked function, from the outermost to the innermost.
+    loops: Vec<Loop>,
+    /// Whether the checked function has a reachable `return` statement.
+    returns: bool,
+    errors: Vec<OYError>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(globals: Globals<'a>) -> Self {
+        Self {
+            globals,
+            scopes: Vec::new(),
+            loops: Vec::new(),
+            returns: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Checks the given global functions, and returns the errors.
+    /// The functions that never return are found first, so their calls end the paths of their callers.
+    fn check(mut self, functions: &[&FunctionStatement]) -> Vec<OYError> {
+        for function in functions {
+            if self.function(function, Scope::default()) {
+                // Global functions must have an identifier.
+                let name = function.ident.as_ref().unwrap().ident.clone();
+                self.globals.never_return.push(name);
+            }
+        }
+        self.errors.clear();
+        for function in functions {
+            self.function(function, Scope::default());
+        }
+        self.errors
+    }
+
+    /// Reports an error of the given kind with the span, see [`Checker::report`].
+    fn error(&mut self, kind: OYErrorKind, span: impl SpanError) {
+        self.report(OYError::new(kind, span));
+    }
+
+    /// Reports the error, if it's not already reported. (The loops are checked twice.)
+    /// An error replaces a warning of the same span.
+    fn report(&mut self, error: OYError) {
+        match self.errors.iter().position(|old| old.span == error.span) {
+            Some(old) if self.errors[old].is_warning() && !error.is_warning() => {
+                self.errors[old] = error
+            }
+            Some(_) => {}
+            None => self.errors.push(error),
+        }
+    }
+
+    /// Checks the function in its own scopes, the params and the captures are declared in the function scope.
+    /// The default values of the params are checked as they are executed, after the params.
+    /// Returns whether the function never returns.
+    fn function(&mut self, function: &FunctionStatement, mut captures: Scope) -> bool {
+        let Some(block) = &function.block else {
+            return false;
+        };
+        for param in function.params.iter().rev() {
+            captures
+                .variables
+                .insert(0, (param.ident.ident.clone(), Ownership::Owned));
+        }
+        let scopes = std::mem::replace(&mut self.scopes, vec![captures]);
+        let loops = std::mem::take(&mut self.loops);
+        let returns = std::mem::replace(&mut self.returns, false);
+        for default in function
+            .params
+            .iter()
+            .filter_map(|param| param.default.as_ref())
+        {
+            self.expression(default);
+        }
+        let never_returns = !self.block(block) && !self.returns;
+        self.scopes = scopes;
+        self.loops = loops;
+        self.returns = returns;
+        never_returns
+    }
+
+    /// Checks the statements of the block in a new scope.
+    /// Returns whether the end of the block is reachable.
+    fn block(&mut self, block: &Block) -> bool {
+        self.scopes.push(Scope::default());
+        let is_reachable = block
+            .statements
+            .iter()
+            .all(|statement| self.statement(statement));
+        self.scopes.pop();
+        is_reachable
+    }
+
+    /// Checks the statement, and returns whether the next statement is reachable.
+    fn statement(&mut self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Function(function) => {
+                let never_returns = self.function(function, Scope::default());
+                // Local functions must have an identifier.
+                let name = function.ident.as_ref().unwrap().ident.clone();
+                self.scope().functions.push((name, never_returns));
+            }
+            Statement::Assignment(assign) => {
+                self.expression(&assign.expression);
+                self.assign(&assign.ident);
+            }
+            Statement::FieldAssignment(assign) => {
+                self.expression(&assign.expression);
+                self.ident(&assign.ident.ident, assign.ident.span, Use::Borrow);
+            }
+            Statement::Return(return_stmt) => {
+                self.expression(&return_stmt.value);
+                self.returns = true;
+                return false;
+            }
+            Statement::If(if_stmt) => return self.if_statement(if_stmt),
+            Statement::Try(try_stmt) => return self.try_statement(try_stmt),
+            Statement::Break(break_stmt) => {
+                if let Some(value) = &break_stmt.value {
+                    self.expression(value);
+                }
+                let state = self.loop_state();
+                let loop_ = self.loops.last_mut().expect("The parser checks the loops");
+                join(&mut loop_.breaks, state);
+                return false;
+            }
+            Statement::Continue(_) => {
+                let state = self.loop_state();
+                let loop_ = self.loops.last_mut().expect("The parser checks the loops");
+                join(&mut loop_.continues, state);
+                return false;
+            }
+            Statement::Expression(expression) => {
+                let never_returns = matches!(expression, ExpressionStatement::FunctionCall(func_call)
+                    if self.never_returns(func_call));
+                self.expression(expression);
+                return !never_returns;
+            }
+            Statement::Import(_) | Statement::Record(_) | Statement::Enum(_) => {}
+        }
+        true
+    }
+
+    /// Checks the if statement, the state after it is the joined state of its reachable branches.
+    fn if_statement(&mut self, if_stmt: &IfStatement) -> bool {
+        let mut end = None;
+        self.expression(&if_stmt.condition);
+        let mut condition_state = self.scopes.clone();
+        if self.block(&if_stmt.block) {
+            join(&mut end, self.scopes.clone());
+        }
+        for else_if in &if_stmt.else_ifs {
+            self.scopes = condition_state;
+            self.expression(&else_if.condition);
+            condition_state = self.scopes.clone();
+            if self.block(&else_if.block) {
+                join(&mut end, self.scopes.clone());
+            }
+        }
+        self.scopes = condition_state;
+        match &if_stmt.else_block {
+            Some(else_block) => {
+                if self.block(else_block) {
+                    join(&mut end, self.scopes.clone());
+                }
+            }
+            None => join(&mut end, self.scopes.clone()),
+        }
+        self.restore(end)
+    }
+
+    /// Checks the try statement, the catch block can start before any move of the try block.
+    fn try_statement(&mut self, try_stmt: &TryStatement) -> bool {
+        let mut end = None;
+        let start = self.scopes.clone();
+        if self.block(&try_stmt.block) {
+            join(&mut end, self.scopes.clone());
+        }
+        self.scopes = start;
+        self.scopes.push(Scope::default());
+        self.declare(&try_stmt.ident);
+        if self.block(&try_stmt.catch_block) {
+            self.scopes.pop();
+            join(&mut end, self.scopes.clone());
+        }
+        self.restore(end)
+    }
+
+    /// Sets the state to the given state, or returns `false` if there is no state (it's unreachable).
+    fn restore(&mut self, state: Option<Vec<Scope>>) -> bool {
+        match state {
+            Some(state) => {
+                self.scopes = state;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the state of the scopes outside the current loop, for its `break` and `continue` statements.
+    fn loop_state(&self) -> Vec<Scope> {
+        let depth = self
+            .loops
+            .last()
+            .expect("The parser checks the loops")
+            .depth;
+        self.scopes[..depth].to_vec()
+    }
+
+    /// Checks the loop, its block is checked twice, the second time with the state of the next iteration.
+    /// The state after the loop is the joined state of its exits, the failed conditions and the `break` statements.
+    fn loop_expression(&mut self, loop_expr: &LoopExpression) {
+        if let LoopKind::For(_, iterable) = &loop_expr.kind {
+            self.expression(iterable);
+        }
+        let start = self.scopes.clone();
+        let mut exits = None;
+        self.loops.push(Loop {
+            depth: self.scopes.len(),
+            ..Default::default()
+        });
+        let mut iteration = Some(start.clone());
+        for _ in 0..2 {
+            let Some(state) = iteration.take() else {
+                break;
+            };
+            self.scopes = state;
+            match &loop_expr.kind {
+                LoopKind::Loop => {}
+                LoopKind::While(condition) => {
+                    self.expression(condition);
+                    join(&mut exits, self.scopes.clone());
+                }
+                LoopKind::For(ident, _) => {
+                    join(&mut exits, self.scopes.clone());
+                    self.scopes.push(Scope::default());
+                    self.declare(ident);
+                }
+            }
+            if self.block(&loop_expr.block) {
+                if matches!(loop_expr.kind, LoopKind::For(..)) {
+                    self.scopes.pop();
+                }
+                join(&mut iteration, self.scopes.clone());
+            }
+            let loop_ = self.loops.last_mut().expect("The loop is pushed");
+            if let Some(continues) = loop_.continues.take() {
+                join(&mut iteration, continues);
+            }
+        }
+        let loop_ = self.loops.pop().expect("The loop is pushed");
+        if let Some(breaks) = loop_.breaks {
+            join(&mut exits, breaks);
+        }
+        // A loop without exits ends the function, so the state after it is not used.
+        self.scopes = exits.unwrap_or(start);
+    }
+
+    /// Checks the match expression, each arm starts after the matched value.
+    fn match_expression(&mut self, match_expr: &MatchExpression) {
+        self.expression(&match_expr.value);
+        let start = self.scopes.clone();
+        let mut end = None;
+        for arm in &match_expr.arms {
+            self.scopes = start.clone();
+            self.scopes.push(Scope::default());
+            self.pattern(&arm.pattern);
+            if let Some(guard) = &arm.guard {
+                self.expression(guard);
+            }
+            self.expression(&arm.expression);
+            self.scopes.pop();
+            join(&mut end, self.scopes.clone());
+        }
+        self.scopes = end.unwrap_or(start);
+    }
+
+    /// Declares the idents that the pattern binds. Reports the variant patterns of undeclared variants, and the ones
+    /// whose number of patterns isn't the number of the variant fields, because they never match.
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Ident(ident) => self.declare(ident),
+            Pattern::Array(patterns, rest, _) => {
+                patterns.iter().for_each(|pattern| self.pattern(pattern));
+                if let Some(rest) = rest {
+                    self.pattern(rest);
+                }
+            }
+            Pattern::Variant(ident, patterns, span) => {
+                match self.globals.variant(&ident.ident) {
+                    Some(variant) if variant.fields.len() != patterns.len() => self.report(
+                        interpreter::variant_arguments_error(variant, patterns.len(), *span),
+                    ),
+                    Some(_) => {}
+                    None => self.error(
+                        OYErrorKind::UnDeclaredIdent(ident.ident.clone()),
+                        ident.span,
+                    ),
+                }
+                patterns.iter().for_each(|pattern| self.pattern(pattern))
+            }
+            Pattern::Literal(_) | Pattern::Wildcard(_) => {}
+        }
+    }
+
+    fn expression(&mut self, expression: &ExpressionStatement) {
+        match expression {
+            ExpressionStatement::FunctionCall(func_call) => self.function_call(func_call),
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                self.ident(&ident.ident, ident.span, Use::Move);
+            }
+            ExpressionStatement::Value(ValueExpression::Object(object)) => match object {
+                ObjectExpression::Function(function) => self.closure(function),
+                ObjectExpression::Array(array, _) => {
+                    array.iter().for_each(|expr| self.expression(expr))
+                }
+                ObjectExpression::Map(map, _) => {
+                    for (key, value) in map {
+                        self.expression(key);
+                        self.expression(value);
+                    }
+                }
+                _ => {}
+            },
+            ExpressionStatement::Borrow(borrow) => {
+                self.ident(&borrow.ident.ident, borrow.span, Use::Borrow);
+            }
+            ExpressionStatement::Loop(loop_expr) => self.loop_expression(loop_expr),
+            ExpressionStatement::Binary(binary) => {
+                self.expression(&binary.left);
+                if matches!(binary.operator, BinaryOperator::And | BinaryOperator::Or) {
+                    // The right operand may not be executed.
+                    let state = self.scopes.clone();
+                    self.expression(&binary.right);
+                    merge(&mut self.scopes, &state);
+                } else {
+                    self.expression(&binary.right);
+                }
+            }
+            ExpressionStatement::Unary(unary) => self.expression(&unary.operand),
+            ExpressionStatement::Record(record) => record
+                .fields
+                .iter()
+                .for_each(|(_, value)| self.expression(value)),
+            ExpressionStatement::FieldAccess(field_access) => self.expression(&field_access.value),
+            ExpressionStatement::Match(match_expr) => self.match_expression(match_expr),
+        }
+    }
+
+    /// Checks the function call in the order of the runtime, the unpacked arguments are executed first,
+    /// then the packed arguments, then the other arguments. The idents are moved last, to the parameters.
+    fn function_call(&mut self, func_call: &FunctionCallExpression) {
+        let (function, is_builtin) = match func_call.callable.as_ref() {
+            ExpressionStatement::Value(ValueExpression::Ident(ident))
+                if self.globals.variant(&ident.ident).is_some() =>
+            {
+                func_call
+                    .args
+                    .iter()
+                    .for_each(|arg| self.expression(&arg.expr));
+                return;
+            }
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                match self.ident(&ident.ident, func_call.span, Use::Move) {
+                    Resolved::Global(function) => (Some(function), function.block.is_none()),
+                    Resolved::Local | Resolved::Error => (None, false),
+                }
+            }
+            ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Function(
+                function,
+            ))) => {
+                self.closure(function);
+                (Some(function), false)
+            }
+            callable => {
+                self.expression(callable);
+                (None, false)
+            }
+        };
+        let (unpacked, args): (Vec<_>, Vec<_>) =
+            func_call.args.iter().partition(|arg| arg.is_unpack);
+        unpacked.iter().for_each(|arg| self.expression(&arg.expr));
+        let packed_count = function
+            .filter(|_| unpacked.is_empty())
+            .and_then(|function| {
+                let pack_index = function.params.iter().position(|param| param.is_pack)?;
+                let positional_count = args.iter().filter(|arg| arg.name.is_none()).count();
+                Some(positional_count.saturating_sub(pack_index))
+            })
+            .unwrap_or_default();
+        let positional_count = args.iter().filter(|arg| arg.name.is_none()).count();
+        let (packed, args): (Vec<_>, Vec<_>) = args
+            .into_iter()
+            .enumerate()
+            .partition(|(idx, arg)| arg.name.is_none() && *idx >= positional_count - packed_count);
+        packed
+            .iter()
+            .for_each(|(_, arg)| self.expression(&arg.expr));
+        if is_builtin {
+            // The `clone` builtin borrows its argument.
+            let is_clone = matches!(function, Some(function) if function.ident.as_ref().unwrap().ident == "clone");
+            for (_, arg) in args {
+                match &arg.expr {
+                    ExpressionStatement::Value(ValueExpression::Ident(ident)) if is_clone => {
+                        self.ident(&ident.ident, ident.span, Use::Borrow);
+                    }
+                    expr => self.expression(expr),
+                }
+            }
+        } else {
+            let (idents, others): (Vec<_>, Vec<_>) = args.into_iter().partition(|(_, arg)| {
+                matches!(&arg.expr, ExpressionStatement::Value(ValueExpression::Ident(ident))
+                    if self.globals.variant(&ident.ident).is_none())
+            });
+            others
+                .iter()
+                .for_each(|(_, arg)| self.expression(&arg.expr));
+            for (_, arg) in idents {
+                if let ExpressionStatement::Value(ValueExpression::Ident(ident)) = &arg.expr {
+                    self.ident(&ident.ident, arg.span, Use::Move);
+                }
+            }
+        }
+    }
+
+    /// Returns whether the called function never returns, e.g. the `throw` builtin function.
+    fn never_returns(&self, func_call: &FunctionCallExpression) -> bool {
+        let ExpressionStatement::Value(ValueExpression::Ident(ident)) = func_call.callable.as_ref()
+        else {
+            return false;
+        };
+        match self.find(&ident.ident) {
+            Some((scope, None)) => self.scopes[scope]
+                .functions
+                .iter()
+                .any(|(function, never_returns)| function == &ident.ident && *never_returns),
+            Some((_, Some(_))) => false,
+            None => self.globals.never_return.contains(&ident.ident),
+        }
+    }
+
+    /// Checks the anonymous function, it captures its free idents from the current scopes.
+    /// The used idents are moved to it, and the borrowed idents are copied. The moved idents are captured
+    /// as moved, so their uses in the function are reported.
+    fn closure(&mut self, function: &FunctionStatement) {
+        let (idents, borrowed) = closures::free_idents(function);
+        let mut captures = Scope::default();
+        for (name, use_) in idents
+            .iter()
+            .map(|name| (name, Use::Move))
+            .chain(borrowed.iter().map(|name| (name, Use::Borrow)))
+        {
+            if self.globals.variant(name).is_some() {
+                continue;
+            }
+            match self.find(name) {
+                Some((scope, None)) => {
+                    let function = self.scopes[scope]
+                        .functions
+                        .iter()
+                        .find(|(function, _)| function == name)
+                        .expect("The local function is found");
+                    captures.functions.push(function.clone());
+                }
+                Some((scope, Some(variable))) => {
+                    let ownership = &mut self.scopes[scope].variables[variable].1;
+                    let captured = *ownership;
+                    match captured {
+                        // The global function is used instead of the moved variable.
+                        Ownership::Moved(_) if self.globals.function(name).is_some() => continue,
+                        Ownership::Moved(_) => {}
+                        _ if use_ == Use::Move => {
+                            *ownership = Ownership::Moved(function.span.span())
+                        }
+                        _ => {}
+                    }
+                    captures.variables.push((name.clone(), captured));
+                }
+                None => {}
+            }
+        }
+        self.function(function, captures);
+    }
+
+    /// Uses the ident, it's looked up like the runtime (see [`crate::resolver`]), the innermost local ident, then the
+    /// global one. Reports the use of a moved variable or an undeclared ident, and warns about the use of a possibly
+    /// moved variable.
+    fn ident(&mut self, name: &str, span: Span, use_: Use) -> Resolved<'a> {
+        if self.globals.variant(name).is_some() {
+            return Resolved::Local;
+        }
+        let mut moved = None;
+        match self.find(name) {
+            Some((scope, Some(variable))) => {
+                let ownership = &mut self.scopes[scope].variables[variable].1;
+                match *ownership {
+                    Ownership::Moved(moved_span) => moved = Some(moved_span),
+                    maybe_moved => {
+                        if use_ == Use::Move {
+                            *ownership = Ownership::Moved(span.span());
+                        }
+                        if let Ownership::MaybeMoved(moved_span) = maybe_moved {
+                            self.error(
+                                OYErrorKind::UseAfterMaybeMove(name.to_owned(), moved_span),
+                                span,
+                            );
+                        }
+                        return Resolved::Local;
+                    }
+                }
+            }
+            Some((_, None)) => return Resolved::Local,
+            None => {}
+        }
+        if let Some(function) = self.globals.function(name) {
+            return Resolved::Global(function);
+        }
+        match moved {
+            Some(moved_span) => {
+                self.error(OYErrorKind::UseAfterMove(name.to_owned(), moved_span), span)
+            }
+            None => self.error(OYErrorKind::UnDeclaredIdent(name.to_owned()), span),
+        }
+        Resolved::Error
+    }
+
+    /// Returns the scope of the variable or the local function with the name, from the innermost scope,
+    /// with the index of the variable, or `None` if it's a local function.
+    fn find(&self, name: &str) -> Option<(usize, Option<usize>)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(idx, scope)| {
+                scope
+                    .variables
+                    .iter()
+                    .position(|(variable, _)| variable == name)
+                    .map(|variable| (idx, Some(variable)))
+                    .or_else(|| {
+                        scope
+                            .functions
+                            .iter()
+                            .any(|(function, _)| function == name)
+                            .then_some((idx, None))
+                    })
+            })
+    }
+
+    /// Declares the variable in the current scope, a moved variable owns the new value.
+    fn declare(&mut self, ident: &Ident) {
+        let scope = self.scope();
+        match scope
+            .variables
+            .iter_mut()
+            .find(|(variable, _)| variable == &ident.ident)
+        {
+            Some((_, ownership)) => *ownership = Ownership::Owned,
+            None => scope
+                .variables
+                .push((ident.ident.clone(), Ownership::Owned)),
+        }
+    }
+
+    /// Assigns the variable, like the runtime (see [`crate::resolver`]) the variable of an enclosing scope with the same
+    /// name is updated, so it owns the new value. Otherwise the variable is declared in the current scope.
+    fn assign(&mut self, ident: &Ident) {
+        match self.find(&ident.ident) {
+            Some((scope, Some(variable))) => {
+                self.scopes[scope].variables[variable].1 = Ownership::Owned
+            }
+            _ => self.declare(ident),
+        }
+    }
+
+    /// Returns the current scope.
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("There must be at least one scope.")
+    }
+}
+
+/// Keeps the moves of the state that are in the other state too, the states have the same scopes.
+/// The variables that are moved in only one of them may be owned, so they are possibly moved.
+fn merge(state: &mut [Scope], other: &[Scope]) {
+    for (scope, other) in state.iter_mut().zip(other) {
+        for (name, ownership) in &mut scope.variables {
+            let other_ownership = other
+                .variables
+                .iter()
+                .find_map(|(variable, ownership)| (variable == name).then_some(*ownership))
+                .unwrap_or(Ownership::Owned);
+            *ownership = match (*ownership, other_ownership) {
+                (Ownership::Owned, Ownership::Owned) => Ownership::Owned,
+                (Ownership::Moved(moved_span), Ownership::Moved(_)) => Ownership::Moved(moved_span),
+                (Ownership::Moved(moved_span) | Ownership::MaybeMoved(moved_span), _)
+                | (_, Ownership::Moved(moved_span) | Ownership::MaybeMoved(moved_span)) => {
+                    Ownership::MaybeMoved(moved_span)
+                }
+            };
+        }
+    }
+}
+
+/// Joins the state with the state of another path, a missing state is an unreachable path.
+fn join(state: &mut Option<Vec<Scope>>, other: Vec<Scope>) {
+    match state {
+        Some(state) => merge(state, &other),
+        None => *state = Some(other),
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::OnceLock};
 
 use crate::{
     ast::*,
@@ -8,7 +8,11 @@ use crate::{
 
 use super::ast::{Block, ExpressionStatement, Program, Statement};
 use bigdecimal::BigDecimal;
-use pest::{iterators::Pair, Parser};
+use pest::{
+    iterators::Pair,
+    pratt_parser::{Assoc, Op, PrattParser},
+    Parser,
+};
 use pest_derive::Parser;
 
 /// The parser for the Ocypode Language. This is generated by the pest_derive crate from the grammar in `grammar.pest`.
@@ -16,9 +20,30 @@ use pest_derive::Parser;
 #[grammar = "grammar.pest"]
 pub struct OYParser;
 
+/// Returns the pratt parser of the operators, the operators are ordered from the lowest to the highest precedence.
+fn pratt_parser() -> &'static PrattParser<Rule> {
+    static PRATT_PARSER: OnceLock<PrattParser<Rule>> = OnceLock::new();
+    PRATT_PARSER.get_or_init(|| {
+        PrattParser::new()
+            .op(Op::infix(Rule::or, Assoc::Left))
+            .op(Op::infix(Rule::and, Assoc::Left))
+            .op(Op::infix(Rule::eq, Assoc::Left) | Op::infix(Rule::ne, Assoc::Left))
+            .op(Op::infix(Rule::lt, Assoc::Left)
+                | Op::infix(Rule::le, Assoc::Left)
+                | Op::infix(Rule::gt, Assoc::Left)
+                | Op::infix(Rule::ge, Assoc::Left))
+            .op(Op::infix(Rule::add, Assoc::Left) | Op::infix(Rule::sub, Assoc::Left))
+            .op(Op::infix(Rule::mul, Assoc::Left)
+                | Op::infix(Rule::div, Assoc::Left)
+                | Op::infix(Rule::modulo, Assoc::Left))
+            .op(Op::prefix(Rule::neg) | Op::prefix(Rule::not))
+            .op(Op::postfix(Rule::field_access))
+    })
+}
+
 impl<'a> OYParser {
     /// Parse the given source code to a program.
-    pub fn parse_program(input: &'a str) -> OYResult<Program> {
+    pub fn parse_program(input: &'a str) -> OYResult<Program<'a>> {
         let program_pair = OYParser::parse(Rule::program, input)
             .map_err(OYError::from)?
             .next()
@@ -41,8 +66,21 @@ impl<'a> OYParser {
     pub fn parse_statement(statement: Pair<'a, Rule>) -> OYResult<Option<Statement>> {
         match statement.as_rule() {
             Rule::func_def => Ok(Some(Self::parse_function(statement)?)),
+            Rule::record_def => Ok(Some(Self::parse_record(statement)?)),
+            Rule::enum_def => Ok(Some(Self::parse_enum(statement)?)),
+            Rule::import => Ok(Some(Self::parse_import(statement)?)),
             Rule::assignment => Ok(Some(Self::parse_assignment(statement)?)),
+            Rule::field_assign => Ok(Some(Self::parse_field_assignment(statement)?)),
             Rule::return_stmt => Ok(Some(Self::parse_return(statement)?)),
+            Rule::if_stmt => Ok(Some(Self::parse_if(statement)?)),
+            Rule::try_stmt => Ok(Some(Self::parse_try(statement)?)),
+            Rule::break_stmt => Ok(Some(Self::parse_break(statement)?)),
+            Rule::continue_stmt => Ok(Some(Statement::Continue(ContinueStatement {
+                span: statement.as_span().into(),
+            }))),
+            Rule::loop_expr => Ok(Some(Statement::Expression(Self::parse_expression(
+                statement,
+            )?))),
             Rule::expression => Ok(Some(Statement::Expression(Self::parse_expression(
                 statement,
             )?))),
@@ -102,7 +140,19 @@ impl<'a> OYParser {
                 Self::parse_function_call(expr)?,
             )),
             Rule::value => Ok(ExpressionStatement::Value(Self::parse_value(expr)?)),
-            Rule::expression => Self::parse_expression(expr.into_inner().next().unwrap()),
+            Rule::loop_expr => Ok(ExpressionStatement::Loop(Self::parse_loop(expr)?)),
+            Rule::match_expr => Ok(ExpressionStatement::Match(Self::parse_match(expr)?)),
+            Rule::record_expr => Ok(ExpressionStatement::Record(Self::parse_record_expression(
+                expr,
+            )?)),
+            Rule::expression | Rule::arg_expression => Self::parse_operation(expr),
+            Rule::borrow => {
+                let span = expr.as_span();
+                Ok(ExpressionStatement::Borrow(BorrowExpression {
+                    ident: Self::parse_ident(expr.into_inner().next().unwrap()),
+                    span: span.into(),
+                }))
+            }
             Rule::anonymous_function => {
                 let anonymous_function = Self::parse_anonymous_function(expr)?;
                 Ok(ExpressionStatement::Value(ValueExpression::Object(
@@ -116,6 +166,74 @@ impl<'a> OYParser {
         }
     }
 
+    /// Parse the operators of the given expression, by their precedence.
+    /// Make sure that the given pair is a expression, otherwise this will panic.
+    pub fn parse_operation(expr: Pair<'a, Rule>) -> OYResult<ExpressionStatement> {
+        pratt_parser()
+            .map_primary(Self::parse_expression)
+            .map_prefix(|operator, operand| {
+                let operand = operand?;
+                let span = Span::new(operator.as_span().start(), operand.span().end);
+                let operator = match operator.as_rule() {
+                    Rule::neg => UnaryOperator::Neg,
+                    Rule::not => UnaryOperator::Not,
+                    _ => {
+                        dbg!(operator.as_rule());
+                        unreachable!("This function only parse the prefix operators")
+                    }
+                };
+                Ok(ExpressionStatement::Unary(UnaryExpression {
+                    operator,
+                    operand: Box::new(operand),
+                    span,
+                }))
+            })
+            .map_postfix(|operand, operator| {
+                let operand = operand?;
+                let field = match operator.as_rule() {
+                    Rule::field_access => Self::parse_ident(operator.into_inner().next().unwrap()),
+                    _ => {
+                        dbg!(operator.as_rule());
+                        unreachable!("This function only parse the postfix operators")
+                    }
+                };
+                Ok(ExpressionStatement::FieldAccess(FieldAccessExpression {
+                    span: Span::new(operand.span().start, field.span.end),
+                    value: Box::new(operand),
+                    field,
+                }))
+            })
+            .map_infix(|left, operator, right| {
+                let (left, right) = (left?, right?);
+                let operator = match operator.as_rule() {
+                    Rule::add => BinaryOperator::Add,
+                    Rule::sub => BinaryOperator::Sub,
+                    Rule::mul => BinaryOperator::Mul,
+                    Rule::div => BinaryOperator::Div,
+                    Rule::modulo => BinaryOperator::Mod,
+                    Rule::eq => BinaryOperator::Eq,
+                    Rule::ne => BinaryOperator::Ne,
+                    Rule::lt => BinaryOperator::Lt,
+                    Rule::le => BinaryOperator::Le,
+                    Rule::gt => BinaryOperator::Gt,
+                    Rule::ge => BinaryOperator::Ge,
+                    Rule::and => BinaryOperator::And,
+                    Rule::or => BinaryOperator::Or,
+                    _ => {
+                        dbg!(operator.as_rule());
+                        unreachable!("This function only parse the infix operators")
+                    }
+                };
+                Ok(ExpressionStatement::Binary(BinaryExpression {
+                    span: Span::new(left.span().start, right.span().end),
+                    left: Box::new(left),
+                    operator,
+                    right: Box::new(right),
+                }))
+            })
+            .parse(expr.into_inner())
+    }
+
     /// Parse the given source code to a function call expression.
     /// Make sure that the given pair is a function call expression, otherwise this will panic.
     pub fn parse_function_call(func: Pair<'a, Rule>) -> OYResult<FunctionCallExpression> {
@@ -128,28 +246,47 @@ impl<'a> OYParser {
             .into_inner()
             .map(Self::parse_arg)
             .collect::<OYResult<Vec<_>>>()?;
-        match callable.as_rule() {
-            Rule::IDENT => Ok(FunctionCallExpression {
-                callable: ValueExpression::Ident(Self::parse_ident(callable)),
-                args,
-                span: span.into(),
-            }),
-            Rule::anonymous_function => {
-                let anonymous_function = Self::parse_anonymous_function(callable)?;
-
-                Ok(FunctionCallExpression {
-                    callable: ValueExpression::Object(ObjectExpression::Function(
-                        anonymous_function,
-                    )),
-                    args,
-                    span: span.into(),
-                })
+        let callable = match callable.as_rule() {
+            Rule::IDENT => {
+                ExpressionStatement::Value(ValueExpression::Ident(Self::parse_ident(callable)))
             }
+            Rule::anonymous_function => ExpressionStatement::Value(ValueExpression::Object(
+                ObjectExpression::Function(Self::parse_anonymous_function(callable)?),
+            )),
+            Rule::expression => Self::parse_expression(callable)?,
             _ => {
                 dbg!(callable.as_rule());
                 unreachable!("This function only parse the function call expressions")
             }
-        }
+        };
+        Ok(FunctionCallExpression {
+            callable: Box::new(callable),
+            args,
+            span: span.into(),
+        })
+    }
+
+    /// Parse the given source code to a record expression.
+    /// Make sure that the given pair is a record expression, otherwise this will panic.
+    pub fn parse_record_expression(record: Pair<'a, Rule>) -> OYResult<RecordExpression> {
+        let span = record.as_span();
+        let mut inner = record.into_inner();
+        let ident = Self::parse_ident(inner.next().unwrap());
+        let fields = inner
+            .map(|field| {
+                let mut field = field.into_inner();
+                Ok((
+                    Self::parse_ident(field.next().unwrap()),
+                    Self::parse_expression(field.next().unwrap())?,
+                ))
+            })
+            .collect::<OYResult<Vec<_>>>()?;
+        utils::check_unique_names(fields.iter().map(|(field, _)| field))?;
+        Ok(RecordExpression {
+            ident,
+            fields,
+            span: span.into(),
+        })
     }
 
     /// Parse the given source code to a return statement.
@@ -163,6 +300,231 @@ impl<'a> OYParser {
         }))
     }
 
+    /// Parse the given source code to a if statement.
+    /// Make sure that the given pair is a if statement, otherwise this will panic.
+    pub fn parse_if(if_stmt: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = if_stmt.as_span();
+        let mut inner = if_stmt.into_inner();
+        let condition = Self::parse_expression(inner.next().unwrap())?;
+        let block = Self::parse_block(inner.next().unwrap())?;
+        let mut else_ifs = Vec::new();
+        let mut else_block = None;
+        for branch in inner {
+            match branch.as_rule() {
+                Rule::else_if => {
+                    let span = branch.as_span();
+                    let mut branch_inner = branch.into_inner();
+                    else_ifs.push(ElseIf {
+                        condition: Self::parse_expression(branch_inner.next().unwrap())?,
+                        block: Self::parse_block(branch_inner.next().unwrap())?,
+                        span: span.into(),
+                    });
+                }
+                Rule::else_block => {
+                    else_block = Some(Self::parse_block(branch.into_inner().next().unwrap())?);
+                }
+                _ => {
+                    dbg!(branch.as_rule());
+                    unreachable!("This function only parse the if statements")
+                }
+            }
+        }
+        Ok(Statement::If(IfStatement {
+            condition,
+            block,
+            else_ifs,
+            else_block,
+            span: span.into(),
+        }))
+    }
+
+    /// Parse the given source code to a try statement.
+    /// Make sure that the given pair is a try statement, otherwise this will panic.
+    pub fn parse_try(try_stmt: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = try_stmt.as_span();
+        let mut inner = try_stmt.into_inner();
+        let block = Self::parse_block(inner.next().unwrap())?;
+        let ident = utils::check_ident_case(
+            Self::parse_ident(inner.next().unwrap()),
+            "variable",
+            "the variable name must be snake_case",
+            utils::Case::Snake,
+        )?;
+        Ok(Statement::Try(TryStatement {
+            block,
+            ident,
+            catch_block: Self::parse_block(inner.next().unwrap())?,
+            span: span.into(),
+        }))
+    }
+
+    /// Parse the given source code to a loop expression.
+    /// Make sure that the given pair is a loop expression, otherwise this will panic.
+    pub fn parse_loop(loop_expr: Pair<'a, Rule>) -> OYResult<LoopExpression> {
+        let loop_expr = loop_expr.into_inner().next().unwrap();
+        let span = loop_expr.as_span();
+        let rule = loop_expr.as_rule();
+        let mut inner = loop_expr.into_inner();
+        let kind = match rule {
+            Rule::while_loop => {
+                LoopKind::While(Box::new(Self::parse_expression(inner.next().unwrap())?))
+            }
+            Rule::infinite_loop => LoopKind::Loop,
+            Rule::for_loop => {
+                let ident = utils::check_ident_case(
+                    Self::parse_ident(inner.next().unwrap()),
+                    "variable",
+                    "the variable name must be snake_case",
+                    utils::Case::Snake,
+                )?;
+                LoopKind::For(
+                    ident,
+                    Box::new(Self::parse_expression(inner.next().unwrap())?),
+                )
+            }
+            _ => {
+                dbg!(rule);
+                unreachable!("This function only parse the loop expressions")
+            }
+        };
+        Ok(LoopExpression {
+            kind,
+            block: Self::parse_block(inner.next().unwrap())?,
+            span: span.into(),
+        })
+    }
+
+    /// Parse the given source code to a match expression.
+    /// Make sure that the given pair is a match expression, otherwise this will panic.
+    pub fn parse_match(match_expr: Pair<'a, Rule>) -> OYResult<MatchExpression> {
+        let span = match_expr.as_span();
+        let mut inner = match_expr.into_inner();
+        let value = Self::parse_expression(inner.next().unwrap())?;
+        let arms = inner
+            .map(|arm| {
+                let span = arm.as_span();
+                let mut inner = arm.into_inner();
+                let pattern = Self::parse_pattern(inner.next().unwrap())?;
+                let mut next = inner.next().unwrap();
+                let guard = if next.as_rule() == Rule::match_guard {
+                    let guard = Self::parse_expression(next.into_inner().next().unwrap())?;
+                    next = inner.next().unwrap();
+                    Some(guard)
+                } else {
+                    None
+                };
+                let expression = Self::parse_expression(next)?;
+                // The span of the arm pair contains the whitespaces after the expression.
+                let span = Span::new(span.start(), expression.span().end);
+                Ok(MatchArm {
+                    pattern,
+                    guard,
+                    expression,
+                    span,
+                })
+            })
+            .collect::<OYResult<_>>()?;
+        Ok(MatchExpression {
+            value: Box::new(value),
+            arms,
+            span: span.into(),
+        })
+    }
+
+    /// Parse the given source code to a pattern.
+    /// Make sure that the given pair is a pattern, otherwise this will panic.
+    pub fn parse_pattern(pattern: Pair<'a, Rule>) -> OYResult<Pattern> {
+        let span = pattern.as_span();
+        Ok(match pattern.as_rule() {
+            Rule::wildcard => Pattern::Wildcard(span.into()),
+            // The variables are snake_case, so a identifier that starts with a uppercase letter is a variant without payload.
+            Rule::IDENT if pattern.as_str().starts_with(|c: char| c.is_uppercase()) => {
+                Pattern::Variant(Self::parse_variant_ident(pattern)?, Vec::new(), span.into())
+            }
+            Rule::IDENT => Pattern::Ident(utils::check_ident_case(
+                Self::parse_ident(pattern),
+                "variable",
+                "the variable name must be snake_case",
+                utils::Case::Snake,
+            )?),
+            Rule::variant_pattern => {
+                // The span of the repeated payloads can end with the whitespace after the last one.
+                let span = Span::new(span.start(), span.start() + span.as_str().trim_end().len());
+                let mut inner = pattern.into_inner();
+                let ident = Self::parse_variant_ident(inner.next().unwrap())?;
+                Pattern::Variant(
+                    ident,
+                    inner.map(Self::parse_pattern).collect::<OYResult<_>>()?,
+                    span,
+                )
+            }
+            Rule::literal_pattern => {
+                let mut inner = pattern.into_inner();
+                let first = inner.next().unwrap();
+                let literal = if first.as_rule() == Rule::neg {
+                    match Self::parse_value(inner.next().unwrap())? {
+                        ValueExpression::Object(ObjectExpression::Int(int, _)) => {
+                            ObjectExpression::Int(-int, span.into())
+                        }
+                        ValueExpression::Object(ObjectExpression::Float(float, _)) => {
+                            ObjectExpression::Float(-float, span.into())
+                        }
+                        _ => unreachable!("The negative literal pattern is a number"),
+                    }
+                } else {
+                    match Self::parse_value(first)? {
+                        ValueExpression::Object(literal) => literal,
+                        _ => unreachable!("The literal pattern is not an ident"),
+                    }
+                };
+                Pattern::Literal(literal)
+            }
+            Rule::array_pattern => {
+                let mut patterns = Vec::new();
+                let mut rest = None;
+                for element in pattern.into_inner() {
+                    if element.as_rule() == Rule::rest_pattern {
+                        rest = Some(Box::new(Self::parse_pattern(
+                            element.into_inner().next().unwrap(),
+                        )?));
+                    } else {
+                        patterns.push(Self::parse_pattern(element)?);
+                    }
+                }
+                Pattern::Array(patterns, rest, span.into())
+            }
+            Rule::pattern => Self::parse_pattern(pattern.into_inner().next().unwrap())?,
+            _ => {
+                dbg!(pattern.as_rule());
+                unreachable!("This function only parse the patterns")
+            }
+        })
+    }
+
+    /// Parse the name of a variant in a pattern, which must be PascalCase.
+    fn parse_variant_ident(ident: Pair<'a, Rule>) -> OYResult<Ident> {
+        utils::check_ident_case(
+            Self::parse_ident(ident),
+            "variant",
+            "the variant name must be PascalCase",
+            utils::Case::Pascal,
+        )
+    }
+
+    /// Parse the given source code to a break statement.
+    /// Make sure that the given pair is a break statement, otherwise this will panic.
+    pub fn parse_break(break_stmt: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = break_stmt.as_span();
+        Ok(Statement::Break(BreakStatement {
+            value: break_stmt
+                .into_inner()
+                .next()
+                .map(Self::parse_expression)
+                .transpose()?,
+            span: span.into(),
+        }))
+    }
+
     /// Parse the given source code to a value expression.
     /// Make sure that the given pair is a value expression, otherwise this will panic.
     pub fn parse_value(value: Pair<'a, Rule>) -> OYResult<ValueExpression> {
@@ -197,6 +559,19 @@ impl<'a> OYParser {
                     .collect::<OYResult<_>>()?,
                 span.into(),
             )),
+            Rule::map => ValueExpression::Object(ObjectExpression::Map(
+                value
+                    .into_inner()
+                    .map(|entry| {
+                        let mut entry = entry.into_inner();
+                        Ok((
+                            Self::parse_expression(entry.next().unwrap())?,
+                            Self::parse_expression(entry.next().unwrap())?,
+                        ))
+                    })
+                    .collect::<OYResult<_>>()?,
+                span.into(),
+            )),
             Rule::nil => ValueExpression::Object(ObjectExpression::Nil(span.into())),
             Rule::value => Self::parse_value(value.into_inner().next().unwrap())?,
             _ => {
@@ -210,20 +585,25 @@ impl<'a> OYParser {
     /// Make sure that the given pair is an anonymous function, otherwise this will panic.
     pub fn parse_anonymous_function(anonymous_func: Pair<'a, Rule>) -> OYResult<FunctionStatement> {
         let span = anonymous_func.as_span();
-        let mut anonymous_inner = anonymous_func.into_inner();
+        let mut anonymous_inner = anonymous_func.into_inner().peekable();
         let params = anonymous_inner
             .next()
             .unwrap()
             .into_inner()
             .map(Self::parse_param)
             .collect::<OYResult<_>>()?;
+        let return_type = anonymous_inner
+            .next_if(|pair| pair.as_rule() == Rule::type_annotation)
+            .map(Self::parse_type_annotation);
         let block = Self::parse_block(anonymous_inner.next().unwrap())?;
+        utils::check_loop_control(&block)?;
         Ok(FunctionStatement {
             ident: None,
             params,
             block: Some(block),
             span: span.into(),
             visibility: Visibility::Private,
+            return_type,
         })
     }
 
@@ -231,7 +611,7 @@ impl<'a> OYParser {
     /// Make sure that the given pair is a function statement, otherwise this will panic.
     pub fn parse_function(func: Pair<'a, Rule>) -> OYResult<Statement> {
         let span = func.as_span();
-        let mut inner = func.into_inner();
+        let mut inner = func.into_inner().peekable();
         let visibility = Self::parse_visibility(inner.next().unwrap());
         let ident = utils::check_ident_case(
             Self::parse_ident(inner.next().unwrap()),
@@ -246,26 +626,145 @@ impl<'a> OYParser {
             .map(Self::parse_param)
             .collect::<OYResult<Vec<_>>>()?;
         let params = utils::cheeck_params(params, &ident)?;
-        let block = Some(Self::parse_block(inner.next().unwrap())?);
+        let return_type = inner
+            .next_if(|pair| pair.as_rule() == Rule::type_annotation)
+            .map(Self::parse_type_annotation);
+        let block = Self::parse_block(inner.next().unwrap())?;
+        utils::check_loop_control(&block)?;
+        let block = Some(block);
         utils::check_main_function(&ident, &params, &visibility)?;
         Ok(Statement::Function(FunctionStatement {
             ident: Some(ident),
             params,
             block,
             visibility,
+            return_type,
+            span: span.into(),
+        }))
+    }
+
+    /// Parse the given source code to a record statement.
+    /// Make sure that the given pair is a record statement, otherwise this will panic.
+    pub fn parse_record(record: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = record.as_span();
+        let mut inner = record.into_inner();
+        let visibility = Self::parse_visibility(inner.next().unwrap());
+        let ident = utils::check_ident_case(
+            Self::parse_ident(inner.next().unwrap()),
+            "record",
+            "the record name must be PascalCase",
+            utils::Case::Pascal,
+        )?;
+        let fields = inner
+            .map(|field| {
+                utils::check_ident_case(
+                    Self::parse_ident(field.into_inner().next().unwrap()),
+                    "field",
+                    "the field name must be snake_case",
+                    utils::Case::Snake,
+                )
+            })
+            .collect::<OYResult<Vec<_>>>()?;
+        utils::check_unique_names(&fields)?;
+        Ok(Statement::Record(RecordStatement {
+            ident,
+            fields,
+            visibility,
+            span: span.into(),
+        }))
+    }
+
+    /// Parse the given source code to a enum statement.
+    /// Make sure that the given pair is a enum statement, otherwise this will panic.
+    pub fn parse_enum(enum_def: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = enum_def.as_span();
+        let mut inner = enum_def.into_inner();
+        let visibility = Self::parse_visibility(inner.next().unwrap());
+        let ident = utils::check_ident_case(
+            Self::parse_ident(inner.next().unwrap()),
+            "enum",
+            "the enum name must be PascalCase",
+            utils::Case::Pascal,
+        )?;
+        let variants = inner
+            .map(|variant| {
+                let span = variant.as_span();
+                let mut inner = variant.into_inner();
+                let ident = utils::check_ident_case(
+                    Self::parse_ident(inner.next().unwrap()),
+                    "variant",
+                    "the variant name must be PascalCase",
+                    utils::Case::Pascal,
+                )?;
+                let fields = inner
+                    .map(|field| {
+                        utils::check_ident_case(
+                            Self::parse_ident(field.into_inner().next().unwrap()),
+                            "field",
+                            "the field name must be snake_case",
+                            utils::Case::Snake,
+                        )
+                    })
+                    .collect::<OYResult<Vec<_>>>()?;
+                utils::check_unique_names(&fields)?;
+                Ok(Variant {
+                    ident,
+                    fields,
+                    span: span.into(),
+                })
+            })
+            .collect::<OYResult<Vec<_>>>()?;
+        utils::check_unique_names(variants.iter().map(|variant| &variant.ident))?;
+        Ok(Statement::Enum(EnumStatement {
+            ident,
+            variants,
+            visibility,
             span: span.into(),
         }))
     }
 
-    /// Parse the argument of a function call.
+    /// Parse the given import statement.
+    /// Make sure that the given pair is a import statement, otherwise this will panic.
+    pub fn parse_import(import: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = import.as_span();
+        match Self::parse_value(import.into_inner().next().unwrap())? {
+            ValueExpression::Object(ObjectExpression::String(path, _)) => {
+                Ok(Statement::Import(ImportStatement {
+                    path,
+                    span: span.into(),
+                }))
+            }
+            _ => unreachable!("The import path is a string"),
+        }
+    }
+
+    /// Parse the argument of a function call, the span of a named argument is from its name to its value.
     /// Make sure that the given pair is a function argument, otherwise this will panic.
     pub fn parse_arg(arg: Pair<'a, Rule>) -> OYResult<Arg> {
+        let is_named = arg.as_rule() == Rule::named_arg;
         let mut inner = arg.into_inner();
+        if is_named {
+            let name = utils::check_ident_case(
+                Self::parse_ident(inner.next().unwrap()),
+                "parameter",
+                "the parameter name must be snake_case",
+                utils::Case::Snake,
+            )?;
+            let expr = Self::parse_expression(inner.next().unwrap())?;
+            let span = Span::new(name.span.start, expr.span().end);
+            return Ok(Arg {
+                expr,
+                name: Some(name),
+                is_unpack: false,
+                span,
+            });
+        }
         let is_unpack = inner.next().unwrap().as_str() == "...";
         let expr = Self::parse_expression(inner.next().unwrap())?;
         let span = expr.span();
         Ok(Arg {
             expr,
+            name: None,
             is_unpack,
             span,
         })
@@ -274,7 +773,7 @@ impl<'a> OYParser {
     /// Parse the parameter of a function.
     /// Make sure that the given pair is a function parameter, otherwise this will panic.
     pub fn parse_param(param: Pair<'a, Rule>) -> OYResult<Param> {
-        let mut inner = param.into_inner();
+        let mut inner = param.into_inner().peekable();
         let is_pack = inner.next().unwrap().as_str() == "*";
         let ident = utils::check_ident_case(
             Self::parse_ident(inner.next().unwrap()),
@@ -282,24 +781,70 @@ impl<'a> OYParser {
             "the parameter name must be snake_case",
             utils::Case::Snake,
         )?;
-        Ok(Param { ident, is_pack })
+        let type_annotation = inner
+            .next_if(|pair| pair.as_rule() == Rule::type_annotation)
+            .map(Self::parse_type_annotation);
+        let default = inner
+            .next()
+            .map(|default| Self::parse_expression(default.into_inner().next().unwrap()))
+            .transpose()?;
+        Ok(Param {
+            ident,
+            is_pack,
+            default,
+            type_annotation,
+        })
+    }
+
+    /// Parse the given type annotation, the `nil` type is parsed as a type name.
+    /// Make sure that the given pair is a type annotation, otherwise this will panic.
+    pub fn parse_type_annotation(type_annotation: Pair<'a, Rule>) -> TypeAnnotation {
+        let types = type_annotation
+            .into_inner()
+            .map(|type_name| Ident {
+                ident: type_name.as_str().to_owned(),
+                span: type_name.as_span().into(),
+            })
+            .collect::<Vec<_>>();
+        let span = Span::new(types[0].span.start, types[types.len() - 1].span.end);
+        TypeAnnotation { types, span }
     }
 
     /// Parse the given source code to a assignment statement.
     /// Make sure that the given pair is a assignment statement, otherwise this will panic.
     pub fn parse_assignment(assignment: Pair<'a, Rule>) -> OYResult<Statement> {
         let span = assignment.as_span();
-        let mut inner = assignment.into_inner();
+        let mut inner = assignment.into_inner().peekable();
         let ident = utils::check_ident_case(
             Self::parse_ident(inner.next().unwrap()),
             "variable",
             "the variable name must be snake_case",
             utils::Case::Snake,
         )?;
+        let type_annotation = inner
+            .next_if(|pair| pair.as_rule() == Rule::type_annotation)
+            .map(Self::parse_type_annotation);
         let expression = Self::parse_expression(inner.next().unwrap())?;
         Ok(Statement::Assignment(AssignmentStatement {
             ident,
             expression,
+            type_annotation,
+            span: span.into(),
+        }))
+    }
+
+    /// Parse the given source code to a field assignment statement.
+    /// Make sure that the given pair is a field assignment statement, otherwise this will panic.
+    pub fn parse_field_assignment(assignment: Pair<'a, Rule>) -> OYResult<Statement> {
+        let span = assignment.as_span();
+        let mut inner = assignment.into_inner().collect::<Vec<_>>();
+        let expression = Self::parse_expression(inner.pop().unwrap())?;
+        let mut path = inner.into_iter().map(Self::parse_ident);
+        let ident = path.next().unwrap();
+        Ok(Statement::FieldAssignment(FieldAssignmentStatement {
+            ident,
+            fields: path.collect(),
+            expression,
             span: span.into(),
         }))
     }
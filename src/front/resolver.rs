@@ -0,0 +1,650 @@
+//! The resolver, it binds each used ident to its declaration before running the program, so the interpreter
+//! finds the variables and the local functions by their slots instead of their names.
+//!
+//! Each function has a slot for each of its params, its captures and the variables and the local functions
+//! that it declares. The slots of a scope (e.g. a block) are cleared when the scope is exited. An ident is bound to
+//! the slot of its innermost visible declaration, and the global function with the same name is used when the slot
+//! is empty or moved. An assignment to a variable of an enclosing scope updates it in its slot.
+//!
+//! The idents that have no visible declaration and are not global functions or variants are reported at once.
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind},
+    modules::Module,
+    runtime::{
+        builtins::Builtins,
+        closures, interpreter,
+        symbols::{SpanMap, Symbol, Symbols},
+    },
+};
+
+/// What a used ident is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binding {
+    /// A variable or a local function of the function, with the slot of its innermost visible declaration.
+    /// The global function with the same name (if any) is used when the slot is empty or moved.
+    Local { slot: usize, global: Option<Symbol> },
+    /// A global function of the module.
+    Global(Symbol),
+    /// A builtin function.
+    Builtin(Symbol),
+    /// A variant of an enum type.
+    Variant(Symbol),
+}
+
+/// The slots of a function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionLayout {
+    /// The number of slots, the first slots are the params in their order.
+    pub slots: usize,
+    /// The captures of an anonymous function, in the order that they are captured.
+    pub captures: Vec<Capture>,
+}
+
+/// An ident that an anonymous function captures from the function where it's created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    /// The slot of the ident in the function where the anonymous function is created.
+    pub from: usize,
+    /// The slot of the ident in the anonymous function.
+    pub slot: usize,
+    /// Whether the anonymous function only borrows the ident, so it's copied instead of moved.
+    pub is_borrowed: bool,
+}
+
+/// A table of the idents, indexed by the starts of their spans. The spans of the idents don't overlap (the spans
+/// of a module start from its offset), so the start of the span is the id of the ident, and finding an ident is
+/// an index instead of a hash.
+#[derive(Debug, Clone)]
+struct IdentTable<V> {
+    entries: Vec<Option<V>>,
+}
+
+impl<V> Default for IdentTable<V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<V> IdentTable<V> {
+    /// Returns the value of the ident.
+    fn get(&self, ident: &Ident) -> Option<&V> {
+        self.entries.get(ident.span.start)?.as_ref()
+    }
+
+    /// Sets the value of the ident, and returns it.
+    fn insert(&mut self, ident: &Ident, value: V) -> &mut V {
+        let id = ident.span.start;
+        if id >= self.entries.len() {
+            self.entries.resize_with(id + 1, || None);
+        }
+        self.entries[id].insert(value)
+    }
+}
+
+/// The resolved idents of a program, the idents are found by their spans.
+#[derive(Debug, Clone, Default)]
+pub struct Resolutions {
+    /// The bindings of the used idents.
+    bindings: IdentTable<Binding>,
+    /// The slots of the declared idents, which are the params, the variables, the local functions,
+    /// the idents of the patterns, the items of the for loops and the caught errors.
+    declarations: IdentTable<usize>,
+    /// The assigned idents that update a variable of an enclosing scope, instead of declaring a new variable.
+    updates: IdentTable<()>,
+    /// The slots of the functions that have blocks.
+    functions: SpanMap<FunctionLayout>,
+    /// The slots that are declared in the scopes (the blocks, the match arms, the for loops and the catch blocks).
+    /// The scope of a for loop is by the span of the loop, and the scope of a catch block is by the span of the try statement.
+    scopes: SpanMap<Range<usize>>,
+}
+
+impl Resolutions {
+    /// Returns the binding of the used ident.
+    pub fn binding(&self, ident: &Ident) -> Option<&Binding> {
+        self.bindings.get(ident)
+    }
+
+    /// Returns the slot of the declared ident.
+    pub fn declaration(&self, ident: &Ident) -> usize {
+        *self
+            .declarations
+            .get(ident)
+            .expect("The declarations are resolved before running")
+    }
+
+    /// Returns whether the assigned ident updates a variable of an enclosing scope, its slot is the slot of the variable.
+    pub fn is_update(&self, ident: &Ident) -> bool {
+        self.updates.get(ident).is_some()
+    }
+
+    /// Returns the slots of the function.
+    pub fn function(&self, function: &FunctionStatement) -> &FunctionLayout {
+        self.functions
+            .get(&function.span)
+            .expect("The functions are resolved before running")
+    }
+
+    /// Returns the slots that are declared in the scope with the given span.
+    pub fn scope(&self, span: Span) -> Range<usize> {
+        self.scopes
+            .get(&span)
+            .cloned()
+            .expect("The scopes are resolved before running")
+    }
+}
+
+/// Resolves the idents of the given statements, the statements are the program of one file.
+/// Returns the undeclared idents errors if there are any.
+pub fn resolve(
+    statements: &[Statement],
+    symbols: &mut Symbols,
+) -> Result<Resolutions, Vec<OYError>> {
+    let builtins = Builtins::new().functions;
+    let functions = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let enums = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Enum(enum_stmt) => Some(enum_stmt),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut resolver = Resolver::new(symbols);
+    resolver.globals = Globals::new(resolver.symbols, &functions, &enums, &builtins);
+    functions
+        .iter()
+        .for_each(|function| resolver.function(function, Vec::new()));
+    resolver.finish()
+}
+
+/// Resolves the idents of the given modules, the global idents of a module are its own, the public ones of its imports
+/// and the given builtin functions. Returns the undeclared idents errors if there are any.
+pub fn resolve_modules(
+    modules: &[Module],
+    builtins: &[FunctionStatement],
+    symbols: &mut Symbols,
+) -> Result<Resolutions, Vec<OYError>> {
+    let mut resolver = Resolver::new(symbols);
+    for module in modules {
+        let imports = module.imports.iter().map(|(import, _)| &modules[*import]);
+        let functions = module
+            .functions
+            .iter()
+            .chain(imports.clone().flat_map(|import| {
+                import
+                    .functions
+                    .iter()
+                    .filter(|function| function.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        let enums = module
+            .enums
+            .iter()
+            .chain(imports.flat_map(|import| {
+                import
+                    .enums
+                    .iter()
+                    .filter(|enum_stmt| enum_stmt.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        resolver.globals = Globals::new(resolver.symbols, &functions, &enums, builtins);
+        module
+            .functions
+            .iter()
+            .for_each(|function| resolver.function(function, Vec::new()));
+    }
+    resolver.finish()
+}
+
+/// The global idents of a module.
+#[derive(Debug, Default)]
+struct Globals {
+    /// The global functions (with the builtin functions), with whether they are builtin functions.
+    functions: HashMap<String, (Symbol, bool)>,
+    /// The variants of the enum types, with their declarations.
+    variants: HashMap<String, (Symbol, Variant)>,
+}
+
+impl Globals {
+    fn new(
+        symbols: &mut Symbols,
+        functions: &[&FunctionStatement],
+        enums: &[&EnumStatement],
+        builtins: &[FunctionStatement],
+    ) -> Self {
+        let mut globals = Self::default();
+        for function in functions.iter().copied().chain(builtins) {
+            // Global functions must have an identifier.
+            let name = &function.ident.as_ref().unwrap().ident;
+            globals
+                .functions
+                .entry(name.clone())
+                .or_insert_with(|| (symbols.intern(name), function.block.is_none()));
+        }
+        for variant in enums.iter().flat_map(|enum_stmt| &enum_stmt.variants) {
+            let name = &variant.ident.ident;
+            globals
+                .variants
+                .entry(name.clone())
+                .or_insert_with(|| (symbols.intern(name), variant.clone()));
+        }
+        globals
+    }
+}
+
+/// The idents that are declared in a scope, with their slots.
+#[derive(Debug, Default)]
+struct Scope {
+    /// The variables, with the params and the captures.
+    variables: HashMap<String, usize>,
+    /// The local functions.
+    functions: HashMap<String, usize>,
+}
+
+/// The resolver of the functions of the modules.
+struct Resolver<'s> {
+    symbols: &'s mut Symbols,
+    /// The global idents of the resolved module.
+    globals: Globals,
+    /// The scopes of the resolved function, from the outermost to the innermost.
+    scopes: Vec<Scope>,
+    /// The number of slots of the resolved function.
+    slots: usize,
+    resolutions: Resolutions,
+    errors: Vec<OYError>,
+}
+
+impl<'s> Resolver<'s> {
+    fn new(symbols: &'s mut Symbols) -> Self {
+        Self {
+            symbols,
+            globals: Globals::default(),
+            scopes: Vec::new(),
+            slots: 0,
+            resolutions: Resolutions::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Returns the resolutions, or the errors if there are any.
+    fn finish(self) -> Result<Resolutions, Vec<OYError>> {
+        if self.errors.is_empty() {
+            Ok(self.resolutions)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// Resolves the function in its own slots, the params and the captures are declared in the function scope
+    /// with the statements of its block. The default values of the params are resolved after the params.
+    /// The captures are the names of the captured idents, with their slots in the current function.
+    fn function(&mut self, function: &FunctionStatement, captures: Vec<(String, Capture)>) {
+        let Some(block) = &function.block else {
+            return;
+        };
+        let scopes = std::mem::replace(&mut self.scopes, vec![Scope::default()]);
+        let slots = std::mem::take(&mut self.slots);
+        for param in &function.params {
+            let slot = self.new_slot();
+            self.resolutions.declarations.insert(&param.ident, slot);
+            self.scope()
+                .variables
+                .entry(param.ident.ident.clone())
+                .or_insert(slot);
+        }
+        let captures = captures
+            .into_iter()
+            .map(|(name, capture)| {
+                let slot = self.new_slot();
+                self.scope().variables.entry(name).or_insert(slot);
+                Capture { slot, ..capture }
+            })
+            .collect();
+        for default in function
+            .params
+            .iter()
+            .filter_map(|param| param.default.as_ref())
+        {
+            self.expression(default);
+        }
+        self.statements(&block.statements);
+        self.resolutions.functions.insert(
+            function.span,
+            FunctionLayout {
+                slots: self.slots,
+                captures,
+            },
+        );
+        self.scopes = scopes;
+        self.slots = slots;
+    }
+
+    /// Resolves the anonymous function, its free idents that are declared in the current function are captured.
+    fn closure(&mut self, function: &FunctionStatement) {
+        let (idents, borrowed) = closures::free_idents(function);
+        let captures = idents
+            .into_iter()
+            .map(|name| (name, false))
+            .chain(borrowed.into_iter().map(|name| (name, true)))
+            .filter(|(name, _)| !self.globals.variants.contains_key(name))
+            .filter_map(|(name, is_borrowed)| {
+                let from = self.slot_of(&name)?;
+                Some((
+                    name,
+                    Capture {
+                        from,
+                        slot: 0,
+                        is_borrowed,
+                    },
+                ))
+            })
+            .collect();
+        self.function(function, captures);
+    }
+
+    /// Returns a new slot of the current function.
+    fn new_slot(&mut self) -> usize {
+        self.slots += 1;
+        self.slots - 1
+    }
+
+    /// Returns the current scope.
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("There must be at least one scope.")
+    }
+
+    /// Resolves the given closure in a new scope, the slots that are declared in it are the slots of the scope.
+    fn in_scope(&mut self, span: Span, resolve: impl FnOnce(&mut Self)) {
+        let start = self.slots;
+        self.scopes.push(Scope::default());
+        resolve(self);
+        self.scopes.pop();
+        self.resolutions.scopes.insert(span, start..self.slots);
+    }
+
+    /// Resolves the statements of the block in a new scope.
+    fn block(&mut self, block: &Block) {
+        self.in_scope(block.span, |resolver| {
+            resolver.statements(&block.statements)
+        });
+    }
+
+    /// Declares the variable in the current scope, a variable that is declared again in the same scope has the same slot.
+    fn declare(&mut self, ident: &Ident) {
+        let slot = match self.scope().variables.get(&ident.ident) {
+            Some(slot) => *slot,
+            None => {
+                let slot = self.new_slot();
+                self.scope().variables.insert(ident.ident.clone(), slot);
+                slot
+            }
+        };
+        self.resolutions.declarations.insert(ident, slot);
+    }
+
+    /// Declares the assigned variable in the current scope, or updates the variable of an enclosing scope
+    /// with the same name, so a block (e.g. the block of a loop) can update the variables of its function.
+    fn assign(&mut self, ident: &Ident) {
+        let innermost = self
+            .scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, scope)| match scope.variables.get(&ident.ident) {
+                Some(slot) => Some((index, Some(*slot))),
+                None => scope
+                    .functions
+                    .contains_key(&ident.ident)
+                    .then_some((index, None)),
+            });
+        match innermost {
+            Some((index, Some(slot))) if index + 1 < self.scopes.len() => {
+                self.resolutions.declarations.insert(ident, slot);
+                self.resolutions.updates.insert(ident, ());
+            }
+            _ => self.declare(ident),
+        }
+    }
+
+    /// Declares the local function in the current scope.
+    fn declare_function(&mut self, ident: &Ident) {
+        let slot = match self.scope().functions.get(&ident.ident) {
+            Some(slot) => *slot,
+            None => {
+                let slot = self.new_slot();
+                self.scope().functions.insert(ident.ident.clone(), slot);
+                slot
+            }
+        };
+        self.resolutions.declarations.insert(ident, slot);
+    }
+
+    /// Returns the slot of the innermost visible declaration of the name.
+    /// The variable of a scope is before its local function.
+    fn slot_of(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.variables.get(name).or(scope.functions.get(name)))
+            .copied()
+    }
+
+    /// Binds the used ident, the variants are before the local idents, and the local idents are before the global ones.
+    /// Reports the ident if it's undeclared, with the given span.
+    fn use_ident(&mut self, ident: &Ident, span: Span) -> Option<&Binding> {
+        let global = self.globals.functions.get(&ident.ident).copied();
+        let binding = if let Some((symbol, _)) = self.globals.variants.get(&ident.ident) {
+            Binding::Variant(*symbol)
+        } else {
+            match (self.slot_of(&ident.ident), global) {
+                (Some(slot), _) => Binding::Local {
+                    slot,
+                    global: global.map(|(symbol, _)| symbol),
+                },
+                (None, Some((symbol, true))) => Binding::Builtin(symbol),
+                (None, Some((symbol, false))) => Binding::Global(symbol),
+                (None, None) => {
+                    self.errors.push(OYError::new(
+                        OYErrorKind::UnDeclaredIdent(ident.ident.clone()),
+                        span,
+                    ));
+                    return None;
+                }
+            }
+        };
+        Some(self.resolutions.bindings.insert(ident, binding))
+    }
+
+    fn statements(&mut self, statements: &[Statement]) {
+        statements
+            .iter()
+            .for_each(|statement| self.statement(statement));
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            // The named functions don't capture, so their blocks are resolved without the scopes of this function.
+            Statement::Function(function) => {
+                self.function(function, Vec::new());
+                // Local functions must have an identifier.
+                self.declare_function(function.ident.as_ref().unwrap());
+            }
+            Statement::Assignment(assignment) => {
+                self.expression(&assignment.expression);
+                self.assign(&assignment.ident);
+            }
+            Statement::FieldAssignment(assignment) => {
+                self.expression(&assignment.expression);
+                self.use_ident(&assignment.ident, assignment.ident.span);
+            }
+            Statement::Return(return_stmt) => self.expression(&return_stmt.value),
+            Statement::If(if_stmt) => {
+                self.expression(&if_stmt.condition);
+                self.block(&if_stmt.block);
+                for else_if in &if_stmt.else_ifs {
+                    self.expression(&else_if.condition);
+                    self.block(&else_if.block);
+                }
+                if let Some(else_block) = &if_stmt.else_block {
+                    self.block(else_block);
+                }
+            }
+            Statement::Try(try_stmt) => {
+                self.block(&try_stmt.block);
+                // The caught error is in its own scope, so the catch block can shadow it.
+                self.in_scope(try_stmt.span, |resolver| {
+                    resolver.declare(&try_stmt.ident);
+                    resolver.block(&try_stmt.catch_block);
+                });
+            }
+            Statement::Break(break_stmt) => {
+                if let Some(value) = &break_stmt.value {
+                    self.expression(value);
+                }
+            }
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Continue(_)
+            | Statement::Import(_)
+            | Statement::Record(_)
+            | Statement::Enum(_) => {}
+        }
+    }
+
+    fn expression(&mut self, expression: &ExpressionStatement) {
+        match expression {
+            ExpressionStatement::FunctionCall(func_call) => self.function_call(func_call),
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                self.use_ident(ident, ident.span);
+            }
+            ExpressionStatement::Value(ValueExpression::Object(object)) => match object {
+                ObjectExpression::Function(function) => self.closure(function),
+                ObjectExpression::Array(array, _) => {
+                    array.iter().for_each(|expr| self.expression(expr))
+                }
+                ObjectExpression::Map(map, _) => {
+                    for (key, value) in map {
+                        self.expression(key);
+                        self.expression(value);
+                    }
+                }
+                _ => {}
+            },
+            ExpressionStatement::Borrow(borrow) => {
+                self.use_ident(&borrow.ident, borrow.span);
+            }
+            ExpressionStatement::Loop(loop_expr) => match &loop_expr.kind {
+                LoopKind::Loop => self.block(&loop_expr.block),
+                LoopKind::While(condition) => {
+                    self.expression(condition);
+                    self.block(&loop_expr.block);
+                }
+                LoopKind::For(ident, iterable) => {
+                    self.expression(iterable);
+                    // The item is in its own scope, so the block can shadow it.
+                    self.in_scope(loop_expr.span, |resolver| {
+                        resolver.declare(ident);
+                        resolver.block(&loop_expr.block);
+                    });
+                }
+            },
+            ExpressionStatement::Binary(binary) => {
+                self.expression(&binary.left);
+                self.expression(&binary.right);
+            }
+            ExpressionStatement::Unary(unary) => self.expression(&unary.operand),
+            // The name of the record type is not a variable, so only the values of the fields are resolved.
+            ExpressionStatement::Record(record) => record
+                .fields
+                .iter()
+                .for_each(|(_, value)| self.expression(value)),
+            ExpressionStatement::FieldAccess(field_access) => self.expression(&field_access.value),
+            ExpressionStatement::Match(match_expr) => {
+                self.expression(&match_expr.value);
+                for arm in &match_expr.arms {
+                    // The arm is executed in its own scope, with the bound idents of its pattern.
+                    self.in_scope(arm.span, |resolver| {
+                        resolver.pattern(&arm.pattern);
+                        if let Some(guard) = &arm.guard {
+                            resolver.expression(guard);
+                        }
+                        resolver.expression(&arm.expression);
+                    });
+                }
+            }
+        }
+    }
+
+    /// Resolves the function call, the called ident is reported with the span of the call,
+    /// and the idents that are moved to the params are reported with the spans of their arguments.
+    fn function_call(&mut self, func_call: &FunctionCallExpression) {
+        let is_builtin = match func_call.callable.as_ref() {
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => matches!(
+                self.use_ident(ident, func_call.span),
+                Some(Binding::Builtin(_))
+            ),
+            ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Function(
+                function,
+            ))) => {
+                self.closure(function);
+                false
+            }
+            callable => {
+                self.expression(callable);
+                false
+            }
+        };
+        for arg in &func_call.args {
+            match &arg.expr {
+                ExpressionStatement::Value(ValueExpression::Ident(ident)) if !is_builtin => {
+                    self.use_ident(ident, arg.span);
+                }
+                expr => self.expression(expr),
+            }
+        }
+    }
+
+    /// Declares the idents that the pattern binds. Reports the variant patterns of undeclared variants, and the ones
+    /// whose number of patterns isn't the number of the variant fields.
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Ident(ident) => self.declare(ident),
+            Pattern::Array(patterns, rest, _) => {
+                patterns.iter().for_each(|pattern| self.pattern(pattern));
+                if let Some(rest) = rest {
+                    self.pattern(rest);
+                }
+            }
+            Pattern::Variant(ident, patterns, span) => {
+                match self.globals.variants.get(&ident.ident) {
+                    Some((_, variant)) if variant.fields.len() != patterns.len() => {
+                        self.errors.push(interpreter::variant_arguments_error(
+                            variant,
+                            patterns.len(),
+                            *span,
+                        ))
+                    }
+                    Some(_) => {}
+                    None => self.errors.push(OYError::new(
+                        OYErrorKind::UnDeclaredIdent(ident.ident.clone()),
+                        ident.span,
+                    )),
+                }
+                patterns.iter().for_each(|pattern| self.pattern(pattern))
+            }
+            Pattern::Literal(_) | Pattern::Wildcard(_) => {}
+        }
+    }
+}
@@ -0,0 +1,673 @@
+//! The type checker of the type annotations, it reports the values whose types are known before running and are not
+//! of their annotated types, and the annotations with unknown types.
+//!
+//! The annotations are optional, the values without annotations can be of any type. The type of a value is known if
+//! it's a literal, an annotated variable or param, a param of the main function, or the result of a function with a
+//! return type, otherwise it's checked by the runtime when it's passed to an annotated param, returned or assigned
+//! (see [`runtime::types`]).
+//!
+//! [`runtime::types`]: crate::runtime::types
+
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, SpanError},
+    modules::Module,
+    runtime::builtins::Builtins,
+};
+
+/// The names of the builtin types, the other types are the record and enum types.
+const BUILTIN_TYPES: [&str; 8] = [
+    "int", "float", "string", "bool", "array", "map", "function", "nil",
+];
+
+/// The types of the arguments of the main function, `argc` and `argv`, which are given by the runtime.
+const MAIN_ARGUMENT_TYPES: [&str; 2] = ["int", "array"];
+
+/// Returns the type errors of the given statements, the statements are the program of one file.
+pub fn check(statements: &[Statement]) -> Vec<OYError> {
+    let builtins = Builtins::new().functions;
+    let mut functions = Vec::new();
+    let mut records = Vec::new();
+    let mut enums = Vec::new();
+    for statement in statements {
+        match statement {
+            Statement::Function(function) => functions.push(function),
+            Statement::Record(record) => records.push(record),
+            Statement::Enum(enum_stmt) => enums.push(enum_stmt),
+            _ => {}
+        }
+    }
+    Checker::new(Globals::new(&functions, &records, &enums, &builtins)).check(&functions, true)
+}
+
+/// Returns the type errors of the given modules, the global functions and types of a module are its own, the
+/// public ones of its imports and the given builtin functions.
+pub fn check_modules(modules: &[Module], builtins: &[FunctionStatement]) -> Vec<OYError> {
+    let mut errors = Vec::new();
+    for (index, module) in modules.iter().enumerate() {
+        let imports = module.imports.iter().map(|(import, _)| &modules[*import]);
+        let functions = module
+            .functions
+            .iter()
+            .chain(imports.clone().flat_map(|import| {
+                import
+                    .functions
+                    .iter()
+                    .filter(|function| function.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        let records = module
+            .records
+            .iter()
+            .chain(imports.clone().flat_map(|import| {
+                import
+                    .records
+                    .iter()
+                    .filter(|record| record.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        let enums = module
+            .enums
+            .iter()
+            .chain(imports.flat_map(|import| {
+                import
+                    .enums
+                    .iter()
+                    .filter(|enum_stmt| enum_stmt.visibility == Visibility::Public)
+            }))
+            .collect::<Vec<_>>();
+        let checker = Checker::new(Globals::new(&functions, &records, &enums, builtins));
+        // The first module is the main module, its main function is the one that is run.
+        errors.extend(checker.check(&module.functions.iter().collect::<Vec<_>>(), index == 0));
+    }
+    errors
+}
+
+/// The global functions and types of a module.
+struct Globals<'a> {
+    /// The global functions, with the builtin functions.
+    functions: Vec<&'a FunctionStatement>,
+    /// The names of the record types.
+    records: Vec<&'a str>,
+    /// The enum types.
+    enums: Vec<&'a EnumStatement>,
+}
+
+impl<'a> Globals<'a> {
+    fn new(
+        functions: &[&'a FunctionStatement],
+        records: &[&'a RecordStatement],
+        enums: &[&'a EnumStatement],
+        builtins: &'a [FunctionStatement],
+    ) -> Self {
+        Self {
+            functions: functions.iter().copied().chain(builtins).collect(),
+            records: records
+                .iter()
+                .map(|record| record.ident.ident.as_str())
+                .collect(),
+            enums: enums.to_vec(),
+        }
+    }
+
+    /// Returns the global function by name.
+    fn function(&self, name: &str) -> Option<&'a FunctionStatement> {
+        self.functions
+            .iter()
+            // Global functions must have an identifier.
+            .find(|function| function.ident.as_ref().unwrap().ident == name)
+            .copied()
+    }
+
+    /// Returns the name of the enum type of the variant, or `None` if there is no variant with the name.
+    fn variant_type(&self, name: &str) -> Option<&'a str> {
+        self.enums
+            .iter()
+            .find(|enum_stmt| {
+                enum_stmt
+                    .variants
+                    .iter()
+                    .any(|variant| variant.ident.ident == name)
+            })
+            .map(|enum_stmt| enum_stmt.ident.ident.as_str())
+    }
+
+    /// Returns whether the type is a builtin type or a record or enum type.
+    fn is_type(&self, name: &str) -> bool {
+        BUILTIN_TYPES.contains(&name)
+            || self.records.contains(&name)
+            || self
+                .enums
+                .iter()
+                .any(|enum_stmt| enum_stmt.ident.ident == name)
+    }
+}
+
+/// A local ident.
+enum Local<'a> {
+    /// A variable, with the type of its value if it's known.
+    Variable(Option<String>),
+    /// A local function.
+    Function(&'a FunctionStatement),
+}
+
+/// The idents that are declared in a scope.
+struct Scope<'a> {
+    /// The idents, in the declaration order.
+    locals: Vec<(String, Local<'a>)>,
+    /// Whether the scope is the scope of a named function, the idents of the parent scopes are not visible in it.
+    /// The anonymous functions capture the idents of their parent scopes, so they can see them.
+    is_function: bool,
+}
+
+/// The type checker of a module.
+struct Checker<'a> {
+    /// The global functions and types.
+    globals: Globals<'a>,
+    /// The scopes, from the outermost to the innermost.
+    scopes: Vec<Scope<'a>>,
+    /// The return types of the checked functions, from the outermost to the innermost.
+    return_types: Vec<Option<&'a TypeAnnotation>>,
+    /// The reported errors.
+    errors: Vec<OYError>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(globals: Globals<'a>) -> Self {
+        Self {
+            globals,
+            scopes: Vec::new(),
+            return_types: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Checks the given global functions, and returns the errors.
+    /// The params of the main function of the main module have the types of the arguments that are given by the runtime.
+    fn check(mut self, functions: &[&'a FunctionStatement], is_main_module: bool) -> Vec<OYError> {
+        for function in functions {
+            // Global functions must have an identifier.
+            let is_main = is_main_module && function.ident.as_ref().unwrap().ident == "main";
+            let argument_types: &[&str] = if is_main { &MAIN_ARGUMENT_TYPES } else { &[] };
+            self.function(function, false, argument_types);
+        }
+        self.errors
+    }
+
+    /// Checks the given function, the params are declared in a new scope with their types.
+    /// The known types of the arguments of the params are given in `argument_types`, they are checked against the
+    /// annotations of the params.
+    fn function(
+        &mut self,
+        function: &'a FunctionStatement,
+        is_anonymous: bool,
+        argument_types: &[&str],
+    ) {
+        self.scopes.push(Scope {
+            locals: Vec::new(),
+            is_function: !is_anonymous,
+        });
+        for (index, param) in function.params.iter().enumerate() {
+            self.annotation(param.type_annotation.as_ref());
+            if let Some(default) = &param.default {
+                let default_type = self.expression(default);
+                self.mismatch(
+                    param.type_annotation.as_ref(),
+                    default_type,
+                    default.span(),
+                    true,
+                );
+            }
+            let argument_type = argument_types.get(index).map(|ty| ty.to_string());
+            if !param.is_pack {
+                self.mismatch(
+                    param.type_annotation.as_ref(),
+                    argument_type.clone(),
+                    param.ident.span,
+                    true,
+                );
+            }
+            let param_type = if param.is_pack {
+                Some("array".to_owned())
+            } else {
+                annotated_type(param.type_annotation.as_ref(), argument_type)
+            };
+            self.declare(&param.ident.ident, Local::Variable(param_type));
+        }
+        self.annotation(function.return_type.as_ref());
+        self.return_types.push(function.return_type.as_ref());
+        if let Some(block) = &function.block {
+            self.statements(&block.statements);
+        }
+        self.return_types.pop();
+        self.scopes.pop();
+    }
+
+    /// Reports the unknown types of the given type annotation.
+    fn annotation(&mut self, type_annotation: Option<&TypeAnnotation>) {
+        for ty in type_annotation.into_iter().flat_map(|ty| &ty.types) {
+            if !self.globals.is_type(&ty.ident) {
+                self.errors.push(OYError::new(
+                    OYErrorKind::UnknownType(ty.ident.clone()),
+                    ty.span,
+                ));
+            }
+        }
+    }
+
+    /// Reports the value if its type is known and is not one of the annotated types.
+    /// The annotations with unknown types are already reported, so their values are not checked.
+    /// The annotations of the builtin functions have no span, so they are not shown in the error.
+    fn mismatch(
+        &mut self,
+        type_annotation: Option<&TypeAnnotation>,
+        value_type: Option<String>,
+        span: Span,
+        has_span: bool,
+    ) {
+        if let (Some(type_annotation), Some(value_type)) = (type_annotation, value_type) {
+            let is_known = type_annotation
+                .types
+                .iter()
+                .all(|ty| self.globals.is_type(&ty.ident));
+            if is_known && !type_annotation.accepts(&value_type) {
+                self.errors.push(OYError::new(
+                    OYErrorKind::MismatchedType(
+                        type_annotation.to_string(),
+                        value_type,
+                        has_span.then(|| type_annotation.span.span()),
+                    ),
+                    span,
+                ));
+            }
+        }
+    }
+
+    /// Declares the ident in the innermost scope.
+    fn declare(&mut self, name: &str, local: Local<'a>) {
+        self.scopes
+            .last_mut()
+            .expect("The idents are declared in a function")
+            .locals
+            .push((name.to_owned(), local));
+    }
+
+    /// Assigns the variable, the variable of an enclosing scope with the same name is updated like the runtime does,
+    /// its type is unknown if the new value is of another type, because the update may not run. Otherwise the variable
+    /// is declared in the innermost scope.
+    fn assign(&mut self, name: &str, value_type: Option<String>) {
+        let (current, enclosing) = self
+            .scopes
+            .split_last_mut()
+            .expect("The idents are declared in a function");
+        let is_declared = current.locals.iter().any(|(local, _)| local == name);
+        if !is_declared && !current.is_function {
+            for scope in enclosing.iter_mut().rev() {
+                if let Some((_, local)) = scope
+                    .locals
+                    .iter_mut()
+                    .rev()
+                    .find(|(local, _)| local == name)
+                {
+                    if let Local::Variable(variable_type) = local {
+                        if *variable_type != value_type {
+                            *variable_type = None;
+                        }
+                        return;
+                    }
+                    break;
+                }
+                if scope.is_function {
+                    break;
+                }
+            }
+        }
+        self.declare(name, Local::Variable(value_type));
+    }
+
+    /// Returns the innermost visible local ident by name.
+    fn local(&self, name: &str) -> Option<&Local<'a>> {
+        for scope in self.scopes.iter().rev() {
+            if let Some((_, local)) = scope.locals.iter().rev().find(|(local, _)| local == name) {
+                return Some(local);
+            }
+            if scope.is_function {
+                break;
+            }
+        }
+        None
+    }
+
+    /// Checks the given statements in a new scope.
+    fn block(&mut self, block: &'a Block) {
+        self.scopes.push(Scope {
+            locals: Vec::new(),
+            is_function: false,
+        });
+        self.statements(&block.statements);
+        self.scopes.pop();
+    }
+
+    fn statements(&mut self, statements: &'a [Statement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::Function(function) => {
+                // Local functions must have an identifier.
+                let name = &function.ident.as_ref().unwrap().ident;
+                self.declare(name, Local::Function(function));
+                self.function(function, false, &[]);
+            }
+            Statement::Assignment(assignment) => {
+                let type_annotation = assignment.type_annotation.as_ref();
+                self.annotation(type_annotation);
+                let value_type = self.expression(&assignment.expression);
+                self.mismatch(
+                    type_annotation,
+                    value_type.clone(),
+                    assignment.expression.span(),
+                    true,
+                );
+                self.assign(
+                    &assignment.ident.ident,
+                    annotated_type(type_annotation, value_type),
+                );
+            }
+            Statement::Return(return_stmt) => {
+                let value_type = self.expression(&return_stmt.value);
+                let return_type = self.return_types.last().copied().flatten();
+                self.mismatch(return_type, value_type, return_stmt.value.span(), true);
+            }
+            Statement::If(if_stmt) => {
+                self.expression(&if_stmt.condition);
+                self.block(&if_stmt.block);
+                for else_if in &if_stmt.else_ifs {
+                    self.expression(&else_if.condition);
+                    self.block(&else_if.block);
+                }
+                if let Some(else_block) = &if_stmt.else_block {
+                    self.block(else_block);
+                }
+            }
+            Statement::Try(try_stmt) => {
+                self.block(&try_stmt.block);
+                // The caught error is a map, in its own scope.
+                self.scopes.push(Scope {
+                    locals: Vec::new(),
+                    is_function: false,
+                });
+                self.declare(
+                    &try_stmt.ident.ident,
+                    Local::Variable(Some("map".to_owned())),
+                );
+                self.block(&try_stmt.catch_block);
+                self.scopes.pop();
+            }
+            Statement::Break(break_stmt) => {
+                if let Some(value) = &break_stmt.value {
+                    self.expression(value);
+                }
+            }
+            Statement::Expression(expression) => {
+                self.expression(expression);
+            }
+            Statement::FieldAssignment(assignment) => {
+                self.expression(&assignment.expression);
+            }
+            Statement::Continue(_)
+            | Statement::Import(_)
+            | Statement::Record(_)
+            | Statement::Enum(_) => {}
+        }
+    }
+
+    /// Checks the given expression, and returns its type if it's known.
+    fn expression(&mut self, expression: &'a ExpressionStatement) -> Option<String> {
+        match expression {
+            ExpressionStatement::FunctionCall(func_call) => self.function_call(func_call),
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                self.ident_type(&ident.ident)
+            }
+            ExpressionStatement::Value(ValueExpression::Object(object)) => self.object(object),
+            ExpressionStatement::Loop(loop_expr) => {
+                self.scopes.push(Scope {
+                    locals: Vec::new(),
+                    is_function: false,
+                });
+                match &loop_expr.kind {
+                    LoopKind::Loop => {}
+                    LoopKind::While(condition) => {
+                        self.expression(condition);
+                    }
+                    LoopKind::For(ident, iterable) => {
+                        self.expression(iterable);
+                        self.declare(&ident.ident, Local::Variable(None));
+                    }
+                }
+                self.block(&loop_expr.block);
+                self.scopes.pop();
+                None
+            }
+            ExpressionStatement::Binary(binary) => {
+                let left = self.expression(&binary.left);
+                let right = self.expression(&binary.right);
+                binary_type(binary.operator, left.as_deref(), right.as_deref())
+            }
+            ExpressionStatement::Unary(unary) => {
+                let operand = self.expression(&unary.operand);
+                match unary.operator {
+                    UnaryOperator::Not => Some("bool".to_owned()),
+                    UnaryOperator::Neg => {
+                        operand.filter(|operand| operand == "int" || operand == "float")
+                    }
+                }
+            }
+            ExpressionStatement::Match(match_expr) => {
+                self.expression(&match_expr.value);
+                let mut arm_types = Vec::new();
+                for arm in &match_expr.arms {
+                    self.scopes.push(Scope {
+                        locals: Vec::new(),
+                        is_function: false,
+                    });
+                    for binding in pattern_bindings(&arm.pattern) {
+                        self.declare(&binding.ident, Local::Variable(None));
+                    }
+                    if let Some(guard) = &arm.guard {
+                        self.expression(guard);
+                    }
+                    arm_types.push(self.expression(&arm.expression));
+                    self.scopes.pop();
+                }
+                // The type of the match is known if all its arms have the same known type.
+                let first = arm_types.first().cloned().flatten();
+                first.filter(|first| arm_types.iter().all(|ty| ty.as_ref() == Some(first)))
+            }
+            ExpressionStatement::Record(record) => {
+                for (_, value) in &record.fields {
+                    self.expression(value);
+                }
+                Some(record.ident.ident.clone())
+            }
+            ExpressionStatement::FieldAccess(field_access) => {
+                self.expression(&field_access.value);
+                None
+            }
+            ExpressionStatement::Borrow(borrow) => self.ident_type(&borrow.ident.ident),
+        }
+    }
+
+    /// Checks the given object, and returns its type.
+    fn object(&mut self, object: &'a ObjectExpression) -> Option<String> {
+        match object {
+            ObjectExpression::Function(function) => self.function(function, true, &[]),
+            ObjectExpression::Array(values, _) => {
+                for value in values {
+                    self.expression(value);
+                }
+            }
+            ObjectExpression::Map(entries, _) => {
+                for (key, value) in entries {
+                    self.expression(key);
+                    self.expression(value);
+                }
+            }
+            _ => {}
+        }
+        Some(object.type_name().to_owned())
+    }
+
+    /// Returns the type of the ident if it's known.
+    fn ident_type(&self, name: &str) -> Option<String> {
+        if let Some(enum_type) = self.globals.variant_type(name) {
+            return Some(enum_type.to_owned());
+        }
+        match self.local(name) {
+            Some(Local::Variable(ty)) => ty.clone(),
+            Some(Local::Function(_)) => Some("function".to_owned()),
+            None => self.globals.function(name).map(|_| "function".to_owned()),
+        }
+    }
+
+    /// Returns the called function if it's known, which is a named function or an anonymous function.
+    fn callee(&self, callable: &'a ExpressionStatement) -> Option<&'a FunctionStatement> {
+        match callable {
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                match self.local(&ident.ident) {
+                    Some(Local::Function(function)) => Some(function),
+                    Some(Local::Variable(_)) => None,
+                    None => self.globals.function(&ident.ident),
+                }
+            }
+            ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Function(
+                function,
+            ))) => Some(function),
+            _ => None,
+        }
+    }
+
+    /// Checks the given function call, the arguments of a known function are checked against its params types.
+    /// Returns the return type of the function if it's known.
+    fn function_call(&mut self, func_call: &'a FunctionCallExpression) -> Option<String> {
+        if let ExpressionStatement::Value(ValueExpression::Ident(ident)) =
+            func_call.callable.as_ref()
+        {
+            if let Some(enum_type) = self.globals.variant_type(&ident.ident) {
+                for arg in &func_call.args {
+                    self.expression(&arg.expr);
+                }
+                return Some(enum_type.to_owned());
+            }
+        } else {
+            self.expression(&func_call.callable);
+        }
+        let arg_types = func_call
+            .args
+            .iter()
+            .map(|arg| self.expression(&arg.expr))
+            .collect::<Vec<_>>();
+        let function = self.callee(&func_call.callable)?;
+        self.args(function, &func_call.args, &arg_types);
+        match function.ident.as_ref() {
+            // The `clone` builtin function returns a copy of its argument.
+            Some(ident) if function.block.is_none() && ident.ident == "clone" => {
+                arg_types.into_iter().next().flatten()
+            }
+            _ => annotated_type(function.return_type.as_ref(), None),
+        }
+    }
+
+    /// Checks the types of the arguments of the given function.
+    /// The positional arguments after an unpacked argument are not checked, because their params are not known.
+    fn args(
+        &mut self,
+        function: &'a FunctionStatement,
+        args: &[Arg],
+        arg_types: &[Option<String>],
+    ) {
+        let pack_index = function.params.iter().position(|param| param.is_pack);
+        let positional_count = pack_index.unwrap_or(function.params.len());
+        let mut position = Some(0);
+        for (arg, arg_type) in args.iter().zip(arg_types) {
+            let param = if let Some(name) = &arg.name {
+                function
+                    .params
+                    .iter()
+                    .find(|param| param.ident.ident == name.ident)
+            } else if arg.is_unpack {
+                position = None;
+                None
+            } else if let Some(index) = position.as_mut() {
+                *index += 1;
+                if *index <= positional_count {
+                    function.params.get(*index - 1)
+                } else {
+                    pack_index.map(|pack_index| &function.params[pack_index])
+                }
+            } else {
+                None
+            };
+            if let Some(param) = param {
+                self.mismatch(
+                    param.type_annotation.as_ref(),
+                    arg_type.clone(),
+                    arg.span,
+                    function.block.is_some(),
+                );
+            }
+        }
+    }
+}
+
+/// Returns the type of the annotated value, which is the annotated type if it's only one type,
+/// or the type of the value if it's known.
+fn annotated_type(
+    type_annotation: Option<&TypeAnnotation>,
+    value_type: Option<String>,
+) -> Option<String> {
+    match type_annotation.map(|ty| ty.types.as_slice()) {
+        Some([ty]) => Some(ty.ident.clone()),
+        _ => value_type,
+    }
+}
+
+/// Returns the type of the binary expression with the given operands types, if it's known.
+fn binary_type(
+    operator: BinaryOperator,
+    left: Option<&str>,
+    right: Option<&str>,
+) -> Option<String> {
+    match operator {
+        BinaryOperator::Eq
+        | BinaryOperator::Ne
+        | BinaryOperator::Lt
+        | BinaryOperator::Le
+        | BinaryOperator::Gt
+        | BinaryOperator::Ge
+        | BinaryOperator::And
+        | BinaryOperator::Or => Some("bool".to_owned()),
+        _ => match (left?, right?) {
+            ("int", "int") => Some("int".to_owned()),
+            ("int" | "float", "int" | "float") => Some("float".to_owned()),
+            _ => None,
+        },
+    }
+}
+
+/// Returns the idents that are bound by the given pattern.
+fn pattern_bindings(pattern: &Pattern) -> Vec<&Ident> {
+    match pattern {
+        Pattern::Ident(ident) => vec![ident],
+        Pattern::Array(patterns, rest, _) => patterns
+            .iter()
+            .chain(rest.as_deref())
+            .flat_map(pattern_bindings)
+            .collect(),
+        Pattern::Variant(_, patterns, _) => patterns.iter().flat_map(pattern_bindings).collect(),
+        Pattern::Literal(_) | Pattern::Wildcard(_) => Vec::new(),
+    }
+}
@@ -1,15 +1,19 @@
+use std::sync::Arc;
+
 use crate::ast::*;
-use crate::errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult};
-use crate::runtime::interpreter::Interpreter;
+use crate::errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult, SpanError};
+use crate::runtime::value::Value;
 
 pub enum Case {
     Snake,
+    Pascal,
 }
 
 impl Case {
     pub fn apply(&self, input: &str) -> String {
         match self {
             Case::Snake => heck::AsSnakeCase(input).to_string(),
+            Case::Pascal => heck::AsPascalCase(input).to_string(),
         }
     }
 }
@@ -80,10 +84,83 @@ pub fn cheeck_params(params: Vec<Param>, func_ident: &Ident) -> OYResult<Vec<Par
             packed_param.ident.span,
         ));
     }
+    // Check if the packed parameter has a default value, the packed parameter is an empty array by default.
+    if let Some(packed_param) = params.iter().find(|p| p.is_pack && p.default.is_some()) {
+        return Err(OYError::new(
+            OYErrorKind::PackedParamWithDefault(packed_param.ident.ident.clone()),
+            packed_param.ident.span,
+        ));
+    }
+    // Check if there is a required parameter after a parameter with a default value.
+    if let Some(default_index) = params.iter().position(|p| p.default.is_some()) {
+        if let Some(required_param) = params[default_index..]
+            .iter()
+            .find(|p| !p.is_pack && p.default.is_none())
+        {
+            return Err(OYError::new(
+                OYErrorKind::RequiredParamAfterDefault(
+                    required_param.ident.ident.clone(),
+                    params[default_index].ident.ident.clone(),
+                ),
+                required_param.ident.span,
+            ));
+        }
+    }
 
     Ok(params)
 }
 
+/// Check that the given identifiers have different names, e.g. the fields of a record or the variants of a enum.
+pub fn check_unique_names<'a>(idents: impl IntoIterator<Item = &'a Ident>) -> OYResult<()> {
+    let mut checked: Vec<&Ident> = Vec::new();
+    for ident in idents {
+        if let Some(old_ident) = checked.iter().find(|old| old.ident == ident.ident) {
+            return Err(OYError::new(
+                OYErrorKind::AlreadyDeclared(ident.ident.clone(), old_ident.span.span()),
+                ident.span,
+            ));
+        }
+        checked.push(ident);
+    }
+    Ok(())
+}
+
+/// Check that the `break` and `continue` statements of the given function block are inside a loop.
+/// The blocks of the nested functions are not checked, because they are checked when they are parsed.
+pub fn check_loop_control(block: &Block) -> OYResult<()> {
+    for statement in &block.statements {
+        match statement {
+            Statement::Break(BreakStatement { span, .. }) => {
+                return Err(OYError::new(
+                    OYErrorKind::OutsideLoop("break".to_owned()),
+                    *span,
+                ))
+            }
+            Statement::Continue(ContinueStatement { span }) => {
+                return Err(OYError::new(
+                    OYErrorKind::OutsideLoop("continue".to_owned()),
+                    *span,
+                ))
+            }
+            Statement::If(if_stmt) => {
+                check_loop_control(&if_stmt.block)?;
+                for else_if in &if_stmt.else_ifs {
+                    check_loop_control(&else_if.block)?;
+                }
+                if let Some(else_block) = &if_stmt.else_block {
+                    check_loop_control(else_block)?;
+                }
+            }
+            Statement::Try(try_stmt) => {
+                check_loop_control(&try_stmt.block)?;
+                check_loop_control(&try_stmt.catch_block)?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 /// Returns the span of the given spans.
 pub fn span_of_spans(spans: &[Span], default_span: Span) -> (usize, usize) {
     let last_param = spans
@@ -92,85 +169,179 @@ pub fn span_of_spans(spans: &[Span], default_span: Span) -> (usize, usize) {
         .unwrap_or(default_span.start);
     let first_param = spans
         .first()
-        .map(|s| s.start - 1)
+        .map(|s| s.start.saturating_sub(1))
         .unwrap_or(default_span.end);
     (first_param, last_param)
 }
 
-/// Unpack the given arguments.
-pub fn unpack_args(interpreter: &mut Interpreter, call_args: Vec<Arg>) -> OYResult<Vec<Arg>> {
+/// The executor of the argument expressions of the function calls, which is the interpreter or the virtual machine.
+pub trait ArgExecutor {
+    /// Executes the expression of the given argument.
+    fn execute_arg(&mut self, arg: &Arg) -> OYResult<Value>;
+}
+
+/// An argument of a function call, which is an argument expression of the call or an executed value
+/// (an element of an unpacked array or the packed arguments).
+#[derive(Debug, Clone)]
+pub enum CallArg<'a> {
+    /// An argument expression, it's executed by the executor after the arguments are bound.
+    Arg(&'a Arg),
+    /// An executed value with the span of its argument.
+    Value(Value, Span),
+}
+
+impl CallArg<'_> {
+    /// Returns the name of the argument, if it's a named argument.
+    fn name(&self) -> Option<&Ident> {
+        match self {
+            CallArg::Arg(arg) => arg.name.as_ref(),
+            CallArg::Value(_, _) => None,
+        }
+    }
+
+    /// Returns the span of the argument.
+    pub fn span(&self) -> Span {
+        match self {
+            CallArg::Arg(arg) => arg.span,
+            CallArg::Value(_, span) => *span,
+        }
+    }
+}
+
+/// Unpack the given arguments, the unpacked arrays are executed and their elements are the arguments.
+pub fn unpack_args<'a>(
+    executor: &mut impl ArgExecutor,
+    call_args: &'a [Arg],
+) -> OYResult<Vec<CallArg<'a>>> {
     let mut args = Vec::new();
     for arg in call_args {
         if arg.is_unpack {
-            let obj = interpreter.execute_expression(arg.expr)?;
-            let unpacked_args = match obj {
-                ObjectExpression::Array(array, _) => array
-                    .into_iter()
-                    .map(|expr| {
-                        let span = expr.span();
-                        Ok(Arg {
-                            expr,
-                            is_unpack: false,
-                            span,
-                        })
-                    })
-                    .collect::<OYResult<Vec<Arg>>>()?,
-                _ => {
+            match executor.execute_arg(arg)? {
+                Value::Array(array, _) => args.extend(
+                    array
+                        .iter()
+                        .map(|value| CallArg::Value(value.clone(), value.span())),
+                ),
+                value => {
                     return Err(OYError::new(
-                        OYErrorKind::InvalidUnpackArg(obj.type_name().to_owned()),
+                        OYErrorKind::InvalidUnpackArg(value.type_name().to_owned()),
                         arg.span,
                     ))
                 }
-            };
-            args.extend(unpacked_args);
+            }
         } else {
-            args.push(arg);
+            args.push(CallArg::Arg(arg));
         }
     }
     Ok(args)
 }
 
-/// Pack the rest of the given arguments.
-pub fn pack_args(
-    interpreter: &mut Interpreter,
+/// Binds the given arguments to the parameters of the function, and returns the argument of each parameter in order,
+/// or `None` for the parameters that will use their default value.
+/// The positional arguments are bound in order and the rest of them are packed into the packed parameter,
+/// then the named arguments are bound to the parameters with their names.
+pub fn bind_args<'a>(
+    executor: &mut impl ArgExecutor,
     function: &FunctionStatement,
-    call_args: Vec<Arg>,
-) -> OYResult<Vec<Arg>> {
-    // Collect the arguments.
-    let mut args = call_args
-        .iter()
-        .enumerate()
-        .take_while(|(idx, _)| *idx < function.params.len() - 1)
-        .map(|(_, arg)| arg.clone())
-        .collect::<Vec<_>>();
-    // Collect the rest of the arguments.
-    let rest_args = call_args
-        .iter()
-        .skip(function.params.len() - 1)
-        .cloned()
+    call_args: Vec<CallArg<'a>>,
+    call_span: Span,
+) -> OYResult<Vec<Option<CallArg<'a>>>> {
+    let func_name = function.ident.as_ref().map_or_else(
+        || "Anonymous function".to_owned(),
+        |ident| ident.ident.clone(),
+    );
+    let func_span = function
+        .ident
+        .as_ref()
+        .map_or_else(|| function.span.span(), |ident| ident.span.span());
+    let (positional_args, named_args): (Vec<_>, Vec<_>) =
+        call_args.into_iter().partition(|arg| arg.name().is_none());
+    let pack_index = function.params.iter().position(|p| p.is_pack);
+    let positional_count = pack_index.unwrap_or(function.params.len());
+    if pack_index.is_none() && positional_args.len() > positional_count {
+        return Err(OYError::new(
+            OYErrorKind::UncorrectArguments(
+                positional_args.len() + named_args.len(),
+                func_span,
+                function.params.clone(),
+                func_name,
+            ),
+            call_span,
+        ));
+    }
+
+    let mut positional_args = positional_args.into_iter();
+    let mut args = positional_args
+        .by_ref()
+        .take(positional_count)
+        .map(Some)
         .collect::<Vec<_>>();
-    // The default span will not be used, because the rest arguments are not empty.
+    args.resize_with(function.params.len(), || None);
+    if let Some(pack_index) = pack_index {
+        let rest_args = positional_args.collect::<Vec<_>>();
+        if !rest_args.is_empty() {
+            args[pack_index] = Some(pack_args(executor, rest_args)?);
+        }
+    }
+    for arg in named_args {
+        // The parser makes sure that the named arguments have a name.
+        let name = arg.name().unwrap();
+        let Some(index) = function
+            .params
+            .iter()
+            .position(|p| p.ident.ident == name.ident)
+        else {
+            return Err(OYError::new(
+                OYErrorKind::UnknownArgument(name.ident.clone(), func_span, func_name),
+                name.span,
+            ));
+        };
+        if let Some(old_arg) = &args[index] {
+            return Err(OYError::new(
+                OYErrorKind::DuplicateArgument(name.ident.clone(), old_arg.span().span()),
+                arg.span(),
+            ));
+        }
+        args[index] = Some(arg);
+    }
+
+    for (param, arg) in function.params.iter().zip(args.iter_mut()) {
+        if arg.is_some() || param.default.is_some() {
+            continue;
+        }
+        if param.is_pack {
+            *arg = Some(pack_args(executor, Vec::new())?);
+        } else {
+            return Err(OYError::new(
+                OYErrorKind::MissingArgument(param.ident.ident.clone(), func_span, func_name),
+                call_span,
+            ));
+        }
+    }
+    Ok(args)
+}
+
+/// Pack the given arguments into an array argument.
+fn pack_args<'a>(
+    executor: &mut impl ArgExecutor,
+    rest_args: Vec<CallArg<'a>>,
+) -> OYResult<CallArg<'a>> {
+    // The span of an empty pack is the default span, because it has no arguments.
     let rests_span = span_of_spans(
-        &rest_args.iter().map(|a| a.span).collect::<Vec<_>>(),
+        &rest_args.iter().map(CallArg::span).collect::<Vec<_>>(),
         Span::new(0, 0),
     );
-    // Pack the rest of the arguments.
-    args.push(Arg {
-        expr: ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Array(
-            rest_args
-                .into_iter()
-                .map(|arg| {
-                    Ok(ExpressionStatement::Value(ValueExpression::Object(
-                        interpreter.execute_expression(arg.expr)?,
-                    )))
-                })
-                .collect::<OYResult<_>>()?,
-            Span::new(rests_span.0, rests_span.1),
-        ))),
-        is_unpack: false,
-        span: Span::new(0, 0),
-    });
-    Ok(args)
+    let values = rest_args
+        .into_iter()
+        .map(|arg| match arg {
+            CallArg::Arg(arg) => executor.execute_arg(arg),
+            CallArg::Value(value, _) => Ok(value),
+        })
+        .collect::<OYResult<_>>()?;
+    Ok(CallArg::Value(
+        Value::Array(Arc::new(values), Span::new(rests_span.0, rests_span.1)),
+        Span::new(0, 0),
+    ))
 }
 
 /// Check the main function name.
@@ -180,7 +351,7 @@ pub fn pack_args(
 /// - The maximum number of parameters is 2, wich are `argc` and `argv`.
 pub fn check_main_function(
     ident: &Ident,
-    params: &Vec<Param>,
+    params: &[Param],
     visibility: &Visibility,
 ) -> OYResult<()> {
     if ident.ident != "main" {
@@ -0,0 +1,161 @@
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, SpanError},
+    runtime::{operators, value::Value},
+};
+
+/// Returns the warnings of the given statements.
+/// The warnings are errors that don't stop the program, like the unreachable match arms.
+pub fn check(statements: &[Statement]) -> Vec<OYError> {
+    let mut warnings = Vec::new();
+    statements
+        .iter()
+        .for_each(|statement| check_statement(statement, &mut warnings));
+    warnings
+}
+
+fn check_statement(statement: &Statement, warnings: &mut Vec<OYError>) {
+    match statement {
+        Statement::Function(function) => check_function(function, warnings),
+        Statement::Assignment(assignment) => check_expression(&assignment.expression, warnings),
+        Statement::FieldAssignment(assignment) => {
+            check_expression(&assignment.expression, warnings)
+        }
+        Statement::Return(return_stmt) => check_expression(&return_stmt.value, warnings),
+        Statement::If(if_stmt) => {
+            check_expression(&if_stmt.condition, warnings);
+            warnings.extend(check(&if_stmt.block.statements));
+            for else_if in &if_stmt.else_ifs {
+                check_expression(&else_if.condition, warnings);
+                warnings.extend(check(&else_if.block.statements));
+            }
+            if let Some(else_block) = &if_stmt.else_block {
+                warnings.extend(check(&else_block.statements));
+            }
+        }
+        Statement::Try(try_stmt) => {
+            warnings.extend(check(&try_stmt.block.statements));
+            warnings.extend(check(&try_stmt.catch_block.statements));
+        }
+        Statement::Break(break_stmt) => {
+            if let Some(value) = &break_stmt.value {
+                check_expression(value, warnings);
+            }
+        }
+        Statement::Expression(expression) => check_expression(expression, warnings),
+        Statement::Continue(_)
+        | Statement::Import(_)
+        | Statement::Record(_)
+        | Statement::Enum(_) => {}
+    }
+}
+
+fn check_function(function: &FunctionStatement, warnings: &mut Vec<OYError>) {
+    for default in function
+        .params
+        .iter()
+        .filter_map(|param| param.default.as_ref())
+    {
+        check_expression(default, warnings);
+    }
+    if let Some(block) = &function.block {
+        warnings.extend(check(&block.statements));
+    }
+}
+
+fn check_expression(expression: &ExpressionStatement, warnings: &mut Vec<OYError>) {
+    match expression {
+        ExpressionStatement::FunctionCall(func_call) => {
+            check_expression(&func_call.callable, warnings);
+            for arg in &func_call.args {
+                check_expression(&arg.expr, warnings);
+            }
+        }
+        ExpressionStatement::Value(ValueExpression::Object(object)) => match object {
+            ObjectExpression::Function(function) => check_function(function, warnings),
+            ObjectExpression::Array(array, _) => array
+                .iter()
+                .for_each(|expr| check_expression(expr, warnings)),
+            ObjectExpression::Map(map, _) => {
+                for (key, value) in map {
+                    check_expression(key, warnings);
+                    check_expression(value, warnings);
+                }
+            }
+            _ => {}
+        },
+        ExpressionStatement::Value(ValueExpression::Ident(_)) | ExpressionStatement::Borrow(_) => {}
+        ExpressionStatement::Loop(loop_expr) => {
+            match &loop_expr.kind {
+                LoopKind::Loop => {}
+                LoopKind::While(condition) => check_expression(condition, warnings),
+                LoopKind::For(_, iterable) => check_expression(iterable, warnings),
+            }
+            warnings.extend(check(&loop_expr.block.statements));
+        }
+        ExpressionStatement::Binary(binary) => {
+            check_expression(&binary.left, warnings);
+            check_expression(&binary.right, warnings);
+        }
+        ExpressionStatement::Unary(unary) => check_expression(&unary.operand, warnings),
+        ExpressionStatement::Record(record) => record
+            .fields
+            .iter()
+            .for_each(|(_, value)| check_expression(value, warnings)),
+        ExpressionStatement::FieldAccess(field_access) => {
+            check_expression(&field_access.value, warnings)
+        }
+        ExpressionStatement::Match(match_expr) => {
+            check_expression(&match_expr.value, warnings);
+            for (index, arm) in match_expr.arms.iter().enumerate() {
+                // The arms with a guard may not be taken, so they don't make the next arms unreachable.
+                if let Some(previous_arm) = match_expr.arms[..index].iter().find(|previous| {
+                    previous.guard.is_none() && covers(&previous.pattern, &arm.pattern)
+                }) {
+                    warnings.push(OYError::new(
+                        OYErrorKind::UnreachableArm(previous_arm.span.span()),
+                        arm.span,
+                    ));
+                }
+                if let Some(guard) = &arm.guard {
+                    check_expression(guard, warnings);
+                }
+                check_expression(&arm.expression, warnings);
+            }
+        }
+    }
+}
+
+/// Returns whether the pattern matches all the values that the other pattern matches.
+fn covers(pattern: &Pattern, other: &Pattern) -> bool {
+    match (pattern, other) {
+        (Pattern::Wildcard(_) | Pattern::Ident(_), _) => true,
+        (Pattern::Literal(literal), Pattern::Literal(other)) => {
+            operators::equals(&Value::literal(literal), &Value::literal(other))
+        }
+        (Pattern::Array(patterns, rest, _), Pattern::Array(other_patterns, other_rest, _)) => {
+            let is_length_covered = if rest.is_some() {
+                other_patterns.len() >= patterns.len()
+            } else {
+                other_rest.is_none() && other_patterns.len() == patterns.len()
+            };
+            is_length_covered
+                && patterns
+                    .iter()
+                    .zip(other_patterns)
+                    .all(|(pattern, other)| covers(pattern, other))
+        }
+        (
+            Pattern::Variant(ident, patterns, _),
+            Pattern::Variant(other_ident, other_patterns, _),
+        ) => {
+            ident.ident == other_ident.ident
+                && patterns.len() == other_patterns.len()
+                && patterns
+                    .iter()
+                    .zip(other_patterns)
+                    .all(|(pattern, other)| covers(pattern, other))
+        }
+        _ => false,
+    }
+}
@@ -1,33 +1,83 @@
 use std::{fs, process};
 
-use ocypode_lang::{parser::OYParser, runtime::interpreter::Interpreter};
+use ocypode_lang::{
+    modules::{self, Sources},
+    ownership,
+    runtime::{
+        builtins::Builtins,
+        interpreter::{Interpreter, DEFAULT_MAX_CALL_DEPTH},
+        vm::Vm,
+    },
+    types,
+};
+
+/// The engine that runs the program.
+enum Engine {
+    /// The tree-walking interpreter, the default engine.
+    Interpreter,
+    /// The bytecode virtual machine.
+    Vm,
+}
 
 fn main() {
     // FIXME: Use clap, structopt or something else to parse arguments
 
-    let mut args = std::env::args().skip(1);
-    let file = args.next().unwrap_or_else(|| {
-        eprintln!("Usage: ocypode <file>");
+    let usage = || -> ! {
+        eprintln!(
+            "Usage: ocypode [--max-call-depth=<depth>] [--engine=<interpreter|vm>] <file> [args...]"
+        );
         process::exit(1);
-    });
+    };
+    let mut args = std::env::args().skip(1).peekable();
+    let mut max_call_depth = DEFAULT_MAX_CALL_DEPTH;
+    let mut engine = Engine::Interpreter;
+    while let Some(option) = args.next_if(|arg| arg.starts_with("--")) {
+        match option.split_once('=') {
+            Some(("--max-call-depth", depth)) => {
+                max_call_depth = depth.parse().unwrap_or_else(|_| usage())
+            }
+            Some(("--engine", "interpreter")) => engine = Engine::Interpreter,
+            Some(("--engine", "vm")) => engine = Engine::Vm,
+            _ => usage(),
+        }
+    }
+    let file = args.next().unwrap_or_else(|| usage());
     let source = fs::read_to_string(&file).unwrap_or_else(|_| {
         eprintln!("Could not read file '{}'", file);
         process::exit(1);
     });
 
-    let program =
-        match OYParser::parse_program(&source).map_err(|e| e.as_diagnostic(&source, &file)) {
-            Ok(parser) => parser,
-            Err(e) => {
-                eprintln!("{}", e);
-                process::exit(1);
-            }
-        };
-    let exit_code = Interpreter::new()
-        .interpret(program, args.len(), args.collect())
-        .unwrap_or_else(|e| {
-            eprintln!("{}", e.as_diagnostic(source, file));
-            process::exit(1);
-        });
+    let mut sources = Sources::default();
+    let modules = modules::load(&mut sources, &file, source).unwrap_or_else(|e| {
+        eprintln!("{}", sources.as_diagnostic(e));
+        process::exit(1);
+    });
+    for warning in modules.iter().flat_map(|module| module.warnings.clone()) {
+        eprintln!("{}", sources.as_diagnostic(warning));
+    }
+    // The ownership and type errors are reported all at once, before running the program.
+    let builtins = Builtins::new().functions;
+    let mut errors = ownership::check_modules(&modules, &builtins);
+    errors.extend(types::check_modules(&modules, &builtins));
+    let has_errors = errors.iter().any(|error| !error.is_warning());
+    for error in errors {
+        eprintln!("{}", sources.as_diagnostic(error));
+    }
+    if has_errors {
+        process::exit(1);
+    }
+    let (argc, argv) = (args.len(), args.collect());
+    let result = match engine {
+        Engine::Interpreter => Interpreter::new()
+            .with_max_call_depth(max_call_depth)
+            .interpret_modules(modules, argc, argv),
+        Engine::Vm => Vm::new()
+            .with_max_call_depth(max_call_depth)
+            .interpret_modules(modules, argc, argv),
+    };
+    let exit_code = result.unwrap_or_else(|e| {
+        eprintln!("{}", sources.as_diagnostic(e));
+        process::exit(1);
+    });
     process::exit(exit_code as i32);
 }
@@ -1,3 +1,11 @@
+use std::sync::Arc;
+
+use bigdecimal::ToPrimitive;
+
+use crate::runtime::{
+    maps::{self, MapEntries},
+    value::Value,
+};
 use crate::{
     ast::*,
     errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult},
@@ -12,12 +20,12 @@ use crate::{
 /// println<format<"Hello {}"><["Awiteb"]>>;
 /// // Hello Awiteb
 /// ```
-pub fn format(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
+pub fn format(args: Vec<Value>, call_span: Span) -> OYResult<Value> {
     // Safety: The number of arguments is checked in the interpreter before calling this function.
     let format = &args[0];
     let args = &args[1];
     let format = match format {
-        ObjectExpression::String(format, _) => format,
+        Value::String(format, _) => format,
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType("String".to_owned(), format.type_name().to_owned()),
@@ -25,16 +33,8 @@ pub fn format(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectEx
             ))
         }
     };
-    let args: Vec<_> = match args {
-        ObjectExpression::Array(args, _) => args
-            .iter()
-            .map(|arg| match arg {
-                ExpressionStatement::Value(ValueExpression::Object(obj)) => obj,
-                _ => unreachable!(
-                    "The interpreter should have checked that the arguments are objects"
-                ),
-            })
-            .collect(),
+    let args = match args {
+        Value::Array(args, _) => args,
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType("Array".to_owned(), args.type_name().to_owned()),
@@ -151,28 +151,28 @@ pub fn format(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectEx
             result.push(c);
         }
     }
-    Ok(ObjectExpression::String(result, call_span))
+    Ok(Value::String(result.into(), call_span))
 }
 
 /// Print builtin function. It takes a array of objects as argument and print them.
 ///
 /// Note: The function will not print a newline at the end of the value. To print a newline, use `println`.
-pub fn print(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
+pub fn print(args: Vec<Value>, call_span: Span) -> OYResult<Value> {
     print!("{}", print_result(args)?);
-    Ok(ObjectExpression::Nil(call_span))
+    Ok(Value::Nil(call_span))
 }
 
 /// Print builtin function. It takes a array of objects as argument and print them with a newline.
-pub fn println(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
+pub fn println(args: Vec<Value>, call_span: Span) -> OYResult<Value> {
     println!("{}", print_result(args)?);
-    Ok(ObjectExpression::Nil(call_span))
+    Ok(Value::Nil(call_span))
 }
 
 /// Input builtin function. It takes a prompt as argument and read a line from stdin.
 /// The function returns a string.
-pub fn input(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
+pub fn input(args: Vec<Value>, call_span: Span) -> OYResult<Value> {
     let prompt = match &args[0] {
-        ObjectExpression::String(prompt, _) => prompt,
+        Value::String(prompt, _) => prompt,
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType("String".to_owned(), args[0].type_name().to_owned()),
@@ -194,25 +194,26 @@ pub fn input(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExp
                 call_span,
             )
         })?;
-    Ok(ObjectExpression::String(input, call_span))
+    Ok(Value::String(input.into(), call_span))
 }
 
-/// Len builtin function. It takes a array/string as argument and returns the length of the array/string.
-pub fn len(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
+/// Len builtin function. It takes a array/string/map as argument and returns the length of the array/string/map.
+pub fn len(args: Vec<Value>, call_span: Span) -> OYResult<Value> {
     let len = match &args[0] {
-        ObjectExpression::Array(array, _) => array.len().to_string(),
-        ObjectExpression::String(string, _) => string.chars().count().to_string(),
+        Value::Array(array, _) => array.len().to_string(),
+        Value::String(string, _) => string.chars().count().to_string(),
+        Value::Map(map, _) => map.len().to_string(),
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType(
-                    "Array or String".to_owned(),
+                    "Array, String or Map".to_owned(),
                     args[0].type_name().to_owned(),
                 ),
                 args[0].span(),
             ))
         }
     };
-    Ok(ObjectExpression::Int(
+    Ok(Value::Int(
         len.parse().map_err(|err| {
             OYError::new(
                 OYErrorKind::Runtime(format!(
@@ -226,12 +227,13 @@ pub fn len(args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpre
     ))
 }
 
-/// Push builtin function. It takes a array and an object as argument and returns the array with the object pushed.
-pub fn push(mut args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
-    let object = args.pop().unwrap();
+/// Push builtin function. It takes a array and a value as argument and returns the array with the value pushed.
+/// The array is copied if it's shared with other values.
+pub fn push(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let value = args.pop().unwrap();
     let array = args.pop().unwrap();
-    let mut array: Vec<_> = match array {
-        ObjectExpression::Array(array, _) => array,
+    let mut array = match array {
+        Value::Array(array, _) => array,
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType("Array".to_owned(), args[0].type_name().to_owned()),
@@ -239,15 +241,16 @@ pub fn push(mut args: Vec<ObjectExpression>, call_span: Span) -> OYResult<Object
             ))
         }
     };
-    array.push(ExpressionStatement::Value(ValueExpression::Object(object)));
-    Ok(ObjectExpression::Array(array, call_span))
+    Arc::make_mut(&mut array).push(value);
+    Ok(Value::Array(array, call_span))
 }
 
 /// Pop builtin function. It takes a array as argument and returns the array with the last element popped
-pub fn pop(mut args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectExpression> {
+/// The array is copied if it's shared with other values.
+pub fn pop(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
     let array = args.pop().unwrap();
-    let mut array: Vec<_> = match array {
-        ObjectExpression::Array(array, _) => array,
+    let mut array = match array {
+        Value::Array(array, _) => array,
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType("Array".to_owned(), args[0].type_name().to_owned()),
@@ -255,25 +258,118 @@ pub fn pop(mut args: Vec<ObjectExpression>, call_span: Span) -> OYResult<ObjectE
             ))
         }
     };
-    array.pop();
-    Ok(ObjectExpression::Array(array, call_span))
+    Arc::make_mut(&mut array).pop();
+    Ok(Value::Array(array, call_span))
+}
+
+/// Get builtin function. It takes a map and a key, or an array and an index, as argument and returns the value of the key (or the index),
+/// or `nil` if the map doesn't contain the key (or the index is out of the array).
+pub fn get(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let key = args.pop().unwrap();
+    let value = match args.pop().unwrap() {
+        Value::Array(array, _) => match &key {
+            Value::Int(index, _) => index.to_usize().and_then(|index| array.get(index).cloned()),
+            _ => {
+                return Err(OYError::new(
+                    OYErrorKind::UnexpectedType("Int".to_owned(), key.type_name().to_owned()),
+                    key.span(),
+                ))
+            }
+        },
+        Value::Map(map, _) => {
+            maps::check_key(&key)?;
+            maps::position(&map, &key).map(|index| map[index].1.clone())
+        }
+        collection => {
+            return Err(OYError::new(
+                OYErrorKind::UnexpectedType(
+                    "Array or Map".to_owned(),
+                    collection.type_name().to_owned(),
+                ),
+                collection.span(),
+            ))
+        }
+    };
+    Ok(match value {
+        Some(mut value) => {
+            *value.span_mut() = call_span;
+            value
+        }
+        None => Value::Nil(call_span),
+    })
+}
+
+/// Set builtin function. It takes a map, a key and a value as argument and returns the map with the value set to the key.
+/// If the map already contains the key, its value will be replaced.
+pub fn set(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let value = args.pop().unwrap();
+    let key = args.pop().unwrap();
+    let mut map = map_entries(args.pop().unwrap())?;
+    maps::insert(Arc::make_mut(&mut map), key, value)?;
+    Ok(Value::Map(map, call_span))
 }
 
-fn print_result(mut args: Vec<ObjectExpression>) -> OYResult<String> {
+/// Remove builtin function. It takes a map and a key as argument and returns the map without the key.
+pub fn remove(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let key = args.pop().unwrap();
+    let mut map = map_entries(args.pop().unwrap())?;
+    maps::check_key(&key)?;
+    if let Some(index) = maps::position(&map, &key) {
+        Arc::make_mut(&mut map).remove(index);
+    }
+    Ok(Value::Map(map, call_span))
+}
+
+/// Keys builtin function. It takes a map as argument and returns an array of its keys, in the insertion order.
+pub fn keys(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let map = map_entries(args.pop().unwrap())?;
+    Ok(Value::Array(
+        Arc::new(map.iter().map(|(key, _)| key.clone()).collect()),
+        call_span,
+    ))
+}
+
+/// Has builtin function. It takes a map and a key as argument and returns whether the map contains the key.
+pub fn has(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let key = args.pop().unwrap();
+    let map = map_entries(args.pop().unwrap())?;
+    maps::check_key(&key)?;
+    Ok(Value::Bool(maps::position(&map, &key).is_some(), call_span))
+}
+
+/// Throw builtin function. It takes a value and throws it as an error, the error can be caught with a `try`/`catch` statement.
+pub fn throw(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    Err(OYError::new(
+        OYErrorKind::Thrown(Box::new(args.pop().unwrap())),
+        call_span,
+    ))
+}
+
+/// Clone builtin function. It takes a value and returns a copy of it, the copy shares the content of the value until one of them is updated.
+/// The argument is borrowed when it's an ident, so the variable can be used after the clone.
+pub fn clone(mut args: Vec<Value>, call_span: Span) -> OYResult<Value> {
+    let mut value = args.pop().unwrap();
+    *value.span_mut() = call_span;
+    Ok(value)
+}
+
+/// Returns the entries of the given map, or an error if it's not a map.
+fn map_entries(map: Value) -> OYResult<Arc<MapEntries>> {
+    match map {
+        Value::Map(map, _) => Ok(map),
+        _ => Err(OYError::new(
+            OYErrorKind::UnexpectedType("Map".to_owned(), map.type_name().to_owned()),
+            map.span(),
+        )),
+    }
+}
+
+fn print_result(mut args: Vec<Value>) -> OYResult<String> {
     let args = args
         .pop()
         .expect("The interpreter should have checked that the arguments are not empty");
     Ok(match args {
-        ObjectExpression::Array(args, _) => args
-            .into_iter()
-            .map(|arg| match arg {
-                ExpressionStatement::Value(ValueExpression::Object(obj)) => obj.to_string(),
-                _ => unreachable!(
-                    "The interpreter should have checked that the arguments are objects, {:?}",
-                    arg
-                ),
-            })
-            .collect(),
+        Value::Array(args, _) => args.iter().map(ToString::to_string).collect(),
         _ => {
             return Err(OYError::new(
                 OYErrorKind::UnexpectedType("Array".to_owned(), args.type_name().to_owned()),
@@ -1,11 +1,37 @@
-use super::environment::Environment;
-use crate::{ast::*, errors::Result as OYResult};
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use super::{
+    environment::Environment,
+    value::{FunctionValue, Value},
+};
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult, SpanError},
+    parser::{OYParser, Rule},
+    utils::{self, Case},
+};
+use pest::Parser;
 pub mod functions;
 
+/// A native function of the host application, it's called with the arguments of its params and the span of the call.
+/// The arguments of a pack param are packed into an array, like the arguments of the builtin functions.
+pub type NativeFunction = Arc<dyn Fn(Vec<Value>, Span) -> OYResult<Value> + Send + Sync>;
+
 /// The builtins functions that are available in the environment.
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct Builtins {
     pub functions: Vec<FunctionStatement>,
+    /// The native functions that are registered by the host application, keyed by their names.
+    pub natives: HashMap<String, NativeFunction>,
+}
+
+impl fmt::Debug for Builtins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builtins")
+            .field("functions", &self.functions)
+            .field("natives", &self.natives.keys().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 /// Macro to create match expression for built in functions.
@@ -25,39 +51,150 @@ impl Builtins {
     pub fn new() -> Self {
         Self {
             functions: vec![
-                create_builtin("format", &[("format", false), ("args", true)]),
-                create_builtin("print", &[("values", true)]),
-                create_builtin("println", &[("values", true)]),
-                create_builtin("input", &[("prompt", false)]),
-                create_builtin("len", &[("value", false)]),
-                create_builtin("push", &[("list", false), ("value", false)]),
-                create_builtin("pop", &[("list", false)]),
+                create_builtin(
+                    "format",
+                    &[("format", false, &["string"]), ("args", true, &[])],
+                    &["string"],
+                ),
+                create_builtin("print", &[("values", true, &[])], &["nil"]),
+                create_builtin("println", &[("values", true, &[])], &["nil"]),
+                create_builtin("input", &[("prompt", false, &["string"])], &["string"]),
+                create_builtin(
+                    "len",
+                    &[("value", false, &["array", "string", "map"])],
+                    &["int"],
+                ),
+                create_builtin(
+                    "push",
+                    &[("list", false, &["array"]), ("value", false, &[])],
+                    &["array"],
+                ),
+                create_builtin("pop", &[("list", false, &["array"])], &["array"]),
+                create_builtin(
+                    "get",
+                    &[("map", false, &["array", "map"]), ("key", false, &[])],
+                    &[],
+                ),
+                create_builtin(
+                    "set",
+                    &[
+                        ("map", false, &["map"]),
+                        ("key", false, &[]),
+                        ("value", false, &[]),
+                    ],
+                    &["map"],
+                ),
+                create_builtin(
+                    "remove",
+                    &[("map", false, &["map"]), ("key", false, &[])],
+                    &["map"],
+                ),
+                create_builtin("keys", &[("map", false, &["map"])], &["array"]),
+                create_builtin(
+                    "has",
+                    &[("map", false, &["map"]), ("key", false, &[])],
+                    &["bool"],
+                ),
+                create_builtin("throw", &[("value", false, &[])], &[]),
+                create_builtin("clone", &[("value", false, &[])], &[]),
             ],
+            natives: HashMap::new(),
         }
     }
 
     /// Initializes the environment with the builtins functions.
-    pub fn env_init(self, env: &mut Environment) -> OYResult<()> {
-        for function in self.functions {
-            env.add_global_function(function)?;
+    pub fn env_init(&self, env: &mut Environment) -> OYResult<()> {
+        for function in &self.functions {
+            env.add_global_function(Arc::new(FunctionValue::new(function.clone())))?;
         }
         Ok(())
     }
 
-    /// Executes the builtin function.
+    /// Registers a native function with the given params, each param is its name and whether it's a pack param.
+    /// The params and the return value can be of any type. Returns the statement of the registered function.
+    pub fn register(
+        &mut self,
+        name: &str,
+        params: &[(&str, bool)],
+        function: NativeFunction,
+    ) -> OYResult<FunctionStatement> {
+        check_native_name(name, "function")?;
+        for (param, _) in params {
+            check_native_name(param, "parameter")?;
+        }
+        let params = params
+            .iter()
+            .map(|(param, is_pack)| (*param, *is_pack, &[][..]))
+            .collect::<Vec<_>>();
+        let statement = create_builtin(name, &params, &[]);
+        let ident = statement.ident.as_ref().unwrap();
+        if let Some(old_function) = self
+            .functions
+            .iter()
+            .find(|function| function.ident.as_ref().is_some_and(|old| old.ident == name))
+        {
+            return Err(OYError::new(
+                OYErrorKind::AlreadyDeclared(name.to_owned(), old_function.span.span()),
+                ident.span,
+            ));
+        }
+        utils::cheeck_params(statement.params.clone(), ident)?;
+        self.functions.push(statement.clone());
+        self.natives.insert(name.to_owned(), function);
+        Ok(statement)
+    }
+
+    /// Executes the builtin function, the native functions are called with the span of the call.
     pub fn execute_builtin_funtion(
+        &self,
         fn_ident: &str,
         call_span: Span,
-        args: Vec<ObjectExpression>,
-    ) -> OYResult<ObjectExpression> {
+        args: Vec<Value>,
+    ) -> OYResult<Value> {
+        if let Some(native) = self.natives.get(fn_ident) {
+            return native(args, call_span);
+        }
         match_builtin!(
             call: call_span; ident: fn_ident; args: args;
-            format, print, println, input, len, push, pop,
+            format, print, println, input, len, push, pop, get, set, remove, keys, has, throw, clone,
         )
     }
 }
 
-fn create_builtin(name: &str, params: &[(&str, bool)]) -> FunctionStatement {
+/// Checks that the given name of a native function or param is a snake_case identifier, like the names in the programs,
+/// otherwise it can't be used by the programs.
+fn check_native_name(name: &str, ident_name: &str) -> OYResult<()> {
+    let ident = Ident {
+        ident: name.to_owned(),
+        span: Span::new(0, 0),
+    };
+    if !OYParser::parse(Rule::IDENT, name).is_ok_and(|pairs| pairs.as_str() == name) {
+        return Err(OYError::new(
+            OYErrorKind::InvalidName(
+                ident.ident,
+                format!("the {ident_name} name must be an identifier"),
+                Case::Snake.apply(name),
+                ident_name.to_owned(),
+            ),
+            ident.span,
+        ));
+    }
+    utils::check_ident_case(
+        ident,
+        ident_name,
+        format!("the {ident_name} name must be snake_case"),
+        Case::Snake,
+    )?;
+    Ok(())
+}
+
+/// Creates a builtin function with the given params, each param is its name, whether it's a pack param and its types.
+/// The params and the return value without types can be of any type.
+fn create_builtin(
+    name: &str,
+    params: &[(&str, bool, &[&str])],
+    return_type: &[&str],
+) -> FunctionStatement {
     FunctionStatement {
         ident: Some(Ident {
             ident: name.to_string(),
@@ -71,10 +208,27 @@ fn create_builtin(name: &str, params: &[(&str, bool)]) -> FunctionStatement {
                     span: Span::new(0, 0),
                 },
                 is_pack: param.1,
+                default: None,
+                type_annotation: builtin_type(param.2),
             })
             .collect(),
         block: None,
         visibility: Visibility::Public,
+        return_type: builtin_type(return_type),
         span: Span::new(0, 0),
     }
 }
+
+/// Returns the type annotation of a builtin function with the given types, or `None` if there are no types.
+fn builtin_type(types: &[&str]) -> Option<TypeAnnotation> {
+    (!types.is_empty()).then(|| TypeAnnotation {
+        types: types
+            .iter()
+            .map(|ty| Ident {
+                ident: ty.to_string(),
+                span: Span::new(0, 0),
+            })
+            .collect(),
+        span: Span::new(0, 0),
+    })
+}
@@ -0,0 +1,780 @@
+//! The bytecode of the virtual machine (see [`super::vm`]) and its compiler.
+//!
+//! Each function with a block is compiled once, before running, to a [`Chunk`], a flat list of instructions that
+//! work on a stack of values. The control flow (the `if` statements, the loops, the `try` statements, the match
+//! arms and the short-circuit operators) is compiled to jumps. The big operands of the instructions, e.g. the idents
+//! and the patterns, are in the tables of the chunk, and the instructions have their indexes. The resolved slots of the
+//! local idents and the scopes (see [`crate::resolver`]) are in the instructions, so they are not looked up at runtime.
+//!
+//! The arguments of a call are executed after they are bound to the params of the called function, which is only
+//! known at runtime, so the expression of each argument is compiled to its own code that the call executes
+//! (see [`CallCode`]). The default values of the params are compiled the same way.
+
+use std::{ops::Range, sync::Arc};
+
+use super::{
+    symbols::SpanMap,
+    value::{FunctionValue, Value},
+};
+use crate::{
+    ast::*,
+    modules::Module,
+    resolver::{Binding, Resolutions},
+};
+
+/// An instruction of the bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// Pushes a copy of the constant with the given index.
+    Constant(usize),
+    /// Moves the value of the local ident out of the given slot and pushes it, a function is copied.
+    /// The ident with the given index is used when the slot is empty or moved, see [`Environment::take_slot`].
+    ///
+    /// [`Environment::take_slot`]: super::environment::Environment::take_slot
+    Take(usize, usize),
+    /// Pushes the global function of the ident with the given index.
+    Global(usize),
+    /// Pushes a copy of the value of the local ident in the given slot, with the span of the borrow.
+    /// The ident with the given index is used when the slot is empty or moved.
+    Borrow(usize, usize, Span),
+    /// Pushes the global function of the ident with the given index, with the span of the borrow.
+    BorrowGlobal(usize, Span),
+    /// Pushes the variant of the ident with the given index, which has no fields.
+    Variant(usize, Span),
+    /// Pops the given number of values and pushes an array of them.
+    Array(usize),
+    /// Pushes an empty map with the span of the map expression.
+    Map(Span),
+    /// Pops a value and its key, and inserts them into the map under them.
+    Insert,
+    /// Pushes a closure of the anonymous function with the given index, which captures its idents.
+    Closure(usize),
+    /// Sets the span of the top value, which is an operand or a key.
+    Respan(Span),
+    /// Pops the right and the left operands, and pushes the result of the operator.
+    Binary(BinaryOperator, Span),
+    /// Replaces the left operand of `&&` (or `||`) with the result and jumps to the given instruction,
+    /// if the left operand decides the result.
+    ShortCircuit(BinaryOperator, Span, usize),
+    /// Pops the operand, and pushes the result of the operator.
+    Unary(UnaryOperator, Span),
+    /// Pops a record, and pushes the value of the field access with the given index.
+    Field(usize),
+    /// Checks that the record type of the record expression with the given index is declared.
+    RecordType(usize),
+    /// Pops the values of the fields, and pushes the record of the record expression with the given index.
+    Record(usize),
+    /// Matches the top value with the pattern with the given index and declares its idents,
+    /// or jumps to the given instruction if the value doesn't match.
+    Arm(usize, usize),
+    /// Fails with the top value, because it doesn't match any arm. The span is the span of the matched value.
+    NoMatch(Span),
+    /// Removes the value under the top value, which is the matched value of a match expression.
+    PopUnder,
+    /// Calls the function of the call with the given index, and pushes the result.
+    Call(usize),
+    /// Pops the top value.
+    Pop,
+    /// Jumps to the given instruction.
+    Jump(usize),
+    /// Pops the top value, and jumps to the given instruction if it's falsy.
+    JumpIfFalsy(usize),
+    /// Clears the slots of a scope, from the first given slot to the second one (exclusive),
+    /// see [`crate::resolver::Resolutions::scope`].
+    ExitScope(usize, usize),
+    /// Declares the local function with the given index.
+    Function(usize),
+    /// Pops the value of the assignment with the given index, and declares its variable.
+    Assign(usize),
+    /// Pops the value of the field assignment with the given index, and assigns it to the field.
+    FieldAssign(usize),
+    /// Pops the iterable of a for loop and starts iterating over its items, the span is the span of the iterable.
+    Iterate(Span),
+    /// Declares the next item of the current iteration in the given slot, with the ident with the given index,
+    /// or jumps to the given instruction if there are no more items.
+    Next(usize, usize, usize),
+    /// Stops the current iteration.
+    EndIterate,
+    /// Starts the try statement with the given index, an error jumps to its catch block.
+    Try(usize),
+    /// Ends the current try statement.
+    EndTry,
+    /// Pops the top value, and returns it from the function (or from the code of an argument or a default value).
+    Return,
+}
+
+/// A function call.
+#[derive(Debug, Clone)]
+pub struct CallCode {
+    /// The called function.
+    pub callee: Callee,
+    /// The arguments of the call.
+    pub args: Vec<Arg>,
+    /// The start of the code of each argument expression, which returns the value of the expression.
+    pub arg_code: Vec<usize>,
+    /// Whether the arguments are positional, without unpacked and named arguments, so they are bound in their order.
+    pub is_positional: bool,
+    /// The span of the call.
+    pub span: Span,
+    /// Whether the call is returned outside a try block, so it's a tail call.
+    pub is_tail: bool,
+}
+
+/// The called function of a call.
+#[derive(Debug, Clone)]
+pub enum Callee {
+    /// A variant, the call constructs it.
+    Variant(Ident),
+    /// A local ident with its slot, it's moved out of its slot when it's a variable.
+    Local(usize, Ident),
+    /// A global function.
+    Ident(Ident),
+    /// The anonymous function with the given index, it's captured then called.
+    Closure(usize),
+    /// The result of an expression, which is pushed before the call. The span is the span of the expression.
+    Value(Span),
+}
+
+/// A record expression, without the values of its fields.
+#[derive(Debug, Clone)]
+pub struct RecordCode {
+    /// The name of the record type.
+    pub ident: Ident,
+    /// The fields, in the written order.
+    pub fields: Vec<Ident>,
+    /// The span of the record expression.
+    pub span: Span,
+}
+
+/// A field access expression, without its record.
+#[derive(Debug, Clone)]
+pub struct FieldCode {
+    /// The accessed field.
+    pub field: Ident,
+    /// The span of the record.
+    pub record_span: Span,
+    /// The span of the field access expression.
+    pub span: Span,
+}
+
+/// An assignment statement, without its value.
+#[derive(Debug, Clone)]
+pub struct AssignCode {
+    /// The assigned variable.
+    pub ident: Ident,
+    /// The slot of the variable.
+    pub slot: usize,
+    /// Whether the assignment updates a variable of an enclosing scope, see [`Resolutions::is_update`].
+    pub is_update: bool,
+    /// The type annotation of the variable.
+    pub type_annotation: Option<TypeAnnotation>,
+    /// The span of the assigned value.
+    pub value_span: Span,
+    /// The span of the assignment statement.
+    pub span: Span,
+}
+
+/// A field assignment statement, without its value.
+#[derive(Debug, Clone)]
+pub struct FieldAssignCode {
+    /// The record variable.
+    pub ident: Ident,
+    /// The path of the assigned field.
+    pub fields: Vec<Ident>,
+}
+
+/// A try statement, without its blocks.
+#[derive(Debug, Clone)]
+pub struct TryCode {
+    /// The slots of the scope of the try block.
+    pub block: Range<usize>,
+    /// The ident of the caught error.
+    pub ident: Ident,
+    /// The slot of the caught error.
+    pub slot: usize,
+    /// The start of the catch block.
+    pub catch: usize,
+}
+
+/// The bytecode of a function.
+#[derive(Debug, Clone, Default)]
+pub struct Chunk {
+    /// The instructions, the function starts from the first one.
+    pub code: Vec<Instruction>,
+    /// The start of the code of the default value of each param, `None` for the params without default values.
+    pub defaults: Vec<Option<usize>>,
+    /// The literal values.
+    pub constants: Vec<Value>,
+    /// The used idents.
+    pub idents: Vec<Ident>,
+    /// The local functions.
+    pub functions: Vec<Arc<FunctionValue>>,
+    /// The anonymous functions.
+    pub closures: Vec<Arc<FunctionStatement>>,
+    /// The function calls.
+    pub calls: Vec<CallCode>,
+    /// The record expressions.
+    pub records: Vec<RecordCode>,
+    /// The field access expressions.
+    pub fields: Vec<FieldCode>,
+    /// The patterns of the match arms.
+    pub patterns: Vec<Pattern>,
+    /// The assignment statements.
+    pub assignments: Vec<AssignCode>,
+    /// The field assignment statements.
+    pub field_assignments: Vec<FieldAssignCode>,
+    /// The try statements.
+    pub tries: Vec<TryCode>,
+}
+
+/// The compiled functions of a program.
+#[derive(Debug, Clone, Default)]
+pub struct Bytecode {
+    /// The chunks of the functions, by the spans of the functions.
+    chunks: SpanMap<Arc<Chunk>>,
+}
+
+impl Bytecode {
+    /// Returns the chunk of the given function.
+    pub fn chunk(&self, function: &FunctionStatement) -> Arc<Chunk> {
+        Arc::clone(
+            self.chunks
+                .get(&function.span)
+                .expect("The functions are compiled before running"),
+        )
+    }
+}
+
+/// Compiles the functions of the given modules, including their local and anonymous functions.
+/// The idents must be resolved, the variants are found by their bindings.
+pub fn compile(modules: &[Module], resolutions: &Resolutions) -> Bytecode {
+    let mut bytecode = Bytecode::default();
+    for function in modules.iter().flat_map(|module| &module.functions) {
+        compile_function(function, resolutions, &mut bytecode.chunks);
+    }
+    bytecode
+}
+
+/// Compiles the given function and its local and anonymous functions, a builtin function has nothing to compile.
+fn compile_function(
+    function: &FunctionStatement,
+    resolutions: &Resolutions,
+    chunks: &mut SpanMap<Arc<Chunk>>,
+) {
+    let Some(block) = &function.block else {
+        return;
+    };
+    let mut compiler = Compiler {
+        resolutions,
+        chunks,
+        chunk: Chunk::default(),
+        exits: Vec::new(),
+        loops: Vec::new(),
+        try_blocks: 0,
+        args: Vec::new(),
+    };
+    // The block of the function is not a scope, its slots are dropped with the frame.
+    for statement in &block.statements {
+        compiler.statement(statement);
+    }
+    compiler.constant(Value::Nil(function.span));
+    compiler.emit(Instruction::Return);
+    for param in &function.params {
+        let default = param.default.as_ref().map(|default| {
+            let start = compiler.chunk.code.len();
+            compiler.expression(default);
+            compiler.emit(Instruction::Return);
+            start
+        });
+        compiler.chunk.defaults.push(default);
+    }
+    // The arguments are compiled after the function, the arguments of their calls are added while compiling them.
+    while let Some((call, arg)) = compiler.args.pop() {
+        let expr = compiler.chunk.calls[call].args[arg].expr.clone();
+        compiler.chunk.calls[call].arg_code[arg] = compiler.chunk.code.len();
+        compiler.expression(&expr);
+        compiler.emit(Instruction::Return);
+    }
+    let chunk = compiler.chunk;
+    chunks.insert(function.span, Arc::new(chunk));
+}
+
+/// A scope or a try block that a `break` or a `continue` statement exits.
+#[derive(Debug, Clone)]
+enum Exit {
+    /// A scope with its slots.
+    Scope(Range<usize>),
+    /// A try block.
+    Try,
+}
+
+/// A loop that is being compiled.
+#[derive(Debug, Default)]
+struct Loop {
+    /// The number of the exits outside the loop.
+    exits: usize,
+    /// The slots of the scope of a for loop, the scope of its item is exited by `break` too.
+    for_scope: Option<Range<usize>>,
+    /// The jumps of the `break` statements, to the end of the loop.
+    breaks: Vec<usize>,
+    /// The jumps of the `continue` statements, to the next iteration.
+    continues: Vec<usize>,
+}
+
+/// The compiler of a function.
+struct Compiler<'a> {
+    /// The resolved idents of the program.
+    resolutions: &'a Resolutions,
+    /// The compiled functions, the local and anonymous functions are added to them.
+    chunks: &'a mut SpanMap<Arc<Chunk>>,
+    /// The chunk of the function.
+    chunk: Chunk,
+    /// The scopes and the try blocks that the compiled code is in, from the outermost.
+    exits: Vec<Exit>,
+    /// The loops that the compiled code is in, from the outermost.
+    loops: Vec<Loop>,
+    /// The number of the try blocks that the compiled code is in, a returned call in a try block is not a tail call.
+    try_blocks: usize,
+    /// The arguments that are not compiled yet, by the index of their call and their index in it.
+    args: Vec<(usize, usize)>,
+}
+
+impl Compiler<'_> {
+    /// Adds the instruction to the chunk, and returns its index.
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.chunk.code.push(instruction);
+        self.chunk.code.len() - 1
+    }
+
+    /// Adds a jump instruction that its target is the next instruction after the given one,
+    /// see [`Compiler::patch`].
+    fn emit_jump(&mut self, instruction: fn(usize) -> Instruction) -> usize {
+        self.emit(instruction(usize::MAX))
+    }
+
+    /// Sets the target of the jump instruction with the given index to the next instruction.
+    fn patch(&mut self, jump: usize) {
+        let target = self.chunk.code.len();
+        self.patch_to(jump, target);
+    }
+
+    /// Sets the target of the jump instruction with the given index.
+    fn patch_to(&mut self, jump: usize, target: usize) {
+        match &mut self.chunk.code[jump] {
+            Instruction::Jump(to)
+            | Instruction::JumpIfFalsy(to)
+            | Instruction::ShortCircuit(_, _, to)
+            | Instruction::Arm(_, to)
+            | Instruction::Next(_, _, to) => *to = target,
+            instruction => unreachable!("`{instruction:?}` is not a jump"),
+        }
+    }
+
+    /// Adds the constant, and pushes it.
+    fn constant(&mut self, value: Value) {
+        self.chunk.constants.push(value);
+        self.emit(Instruction::Constant(self.chunk.constants.len() - 1));
+    }
+
+    /// Adds the ident, and returns its index.
+    fn ident(&mut self, ident: &Ident) -> usize {
+        self.chunk.idents.push(ident.clone());
+        self.chunk.idents.len() - 1
+    }
+
+    /// Returns whether the used ident is a variant.
+    fn is_variant(&self, ident: &Ident) -> bool {
+        matches!(self.resolutions.binding(ident), Some(Binding::Variant(_)))
+    }
+
+    /// Adds the instruction that exits the scope with the given span, which clears its slots.
+    fn exit_scope(&mut self, span: Span) {
+        let scope = self.resolutions.scope(span);
+        self.emit(Instruction::ExitScope(scope.start, scope.end));
+    }
+
+    /// Compiles the given statement.
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Function(function) => {
+                compile_function(function, self.resolutions, self.chunks);
+                self.chunk
+                    .functions
+                    .push(Arc::new(FunctionValue::new(function.clone())));
+                self.emit(Instruction::Function(self.chunk.functions.len() - 1));
+            }
+            Statement::Assignment(assign) => {
+                self.expression(&assign.expression);
+                self.chunk.assignments.push(AssignCode {
+                    ident: assign.ident.clone(),
+                    slot: self.resolutions.declaration(&assign.ident),
+                    is_update: self.resolutions.is_update(&assign.ident),
+                    type_annotation: assign.type_annotation.clone(),
+                    value_span: assign.expression.span(),
+                    span: assign.span,
+                });
+                self.emit(Instruction::Assign(self.chunk.assignments.len() - 1));
+            }
+            // A returned function call is a tail call, except in a try block, where its errors must be caught by the block.
+            Statement::Return(ReturnStatement {
+                value: ExpressionStatement::FunctionCall(func_call),
+                ..
+            }) if self.try_blocks == 0 => {
+                self.call(func_call, true);
+                self.emit(Instruction::Return);
+            }
+            Statement::Return(return_stmt) => {
+                self.expression(&return_stmt.value);
+                self.emit(Instruction::Return);
+            }
+            Statement::If(if_stmt) => self.if_statement(if_stmt),
+            Statement::Try(try_stmt) => self.try_statement(try_stmt),
+            Statement::Break(break_stmt) => {
+                match &break_stmt.value {
+                    Some(value) => self.expression(value),
+                    None => self.constant(Value::Nil(break_stmt.span)),
+                }
+                let loop_ = self
+                    .loops
+                    .last()
+                    .expect("The parser checks that `break` is inside a loop");
+                let (exits, for_scope) = (loop_.exits, loop_.for_scope.clone());
+                self.exit_to(exits);
+                if let Some(scope) = for_scope {
+                    self.emit(Instruction::ExitScope(scope.start, scope.end));
+                }
+                let jump = self.emit_jump(Instruction::Jump);
+                self.loops.last_mut().unwrap().breaks.push(jump);
+            }
+            Statement::Continue(_) => {
+                let exits = self
+                    .loops
+                    .last()
+                    .expect("The parser checks that `continue` is inside a loop")
+                    .exits;
+                self.exit_to(exits);
+                let jump = self.emit_jump(Instruction::Jump);
+                self.loops.last_mut().unwrap().continues.push(jump);
+            }
+            Statement::Expression(expr) => {
+                self.expression(expr);
+                self.emit(Instruction::Pop);
+            }
+            Statement::FieldAssignment(assign) => {
+                self.expression(&assign.expression);
+                self.chunk.field_assignments.push(FieldAssignCode {
+                    ident: assign.ident.clone(),
+                    fields: assign.fields.clone(),
+                });
+                self.emit(Instruction::FieldAssign(
+                    self.chunk.field_assignments.len() - 1,
+                ));
+            }
+            Statement::Import(_) | Statement::Record(_) | Statement::Enum(_) => {
+                unreachable!("The import and type statements can only be at the top of a file")
+            }
+        }
+    }
+
+    /// Exits the scopes and the try blocks after the given number of exits, from the innermost.
+    fn exit_to(&mut self, exits: usize) {
+        for index in (exits..self.exits.len()).rev() {
+            match &self.exits[index] {
+                Exit::Scope(scope) => self.emit(Instruction::ExitScope(scope.start, scope.end)),
+                Exit::Try => self.emit(Instruction::EndTry),
+            };
+        }
+    }
+
+    /// Compiles the given block, its scope is exited at its end.
+    fn block(&mut self, block: &Block) {
+        self.exits
+            .push(Exit::Scope(self.resolutions.scope(block.span)));
+        for statement in &block.statements {
+            self.statement(statement);
+        }
+        self.exits.pop();
+        self.exit_scope(block.span);
+    }
+
+    /// Compiles the given if statement, each condition jumps to the next one if it's falsy.
+    fn if_statement(&mut self, if_stmt: &IfStatement) {
+        let mut ends = Vec::new();
+        let branches = std::iter::once((&if_stmt.condition, &if_stmt.block)).chain(
+            if_stmt
+                .else_ifs
+                .iter()
+                .map(|else_if| (&else_if.condition, &else_if.block)),
+        );
+        for (condition, block) in branches {
+            self.expression(condition);
+            let next = self.emit_jump(Instruction::JumpIfFalsy);
+            self.block(block);
+            ends.push(self.emit_jump(Instruction::Jump));
+            self.patch(next);
+        }
+        if let Some(else_block) = &if_stmt.else_block {
+            self.block(else_block);
+        }
+        for end in ends {
+            self.patch(end);
+        }
+    }
+
+    /// Compiles the given try statement, the catch block is after the try block.
+    /// The error is in its own scope (the scope of the try statement), so the catch block can shadow it.
+    fn try_statement(&mut self, try_stmt: &TryStatement) {
+        self.chunk.tries.push(TryCode {
+            block: self.resolutions.scope(try_stmt.block.span),
+            ident: try_stmt.ident.clone(),
+            slot: self.resolutions.declaration(&try_stmt.ident),
+            catch: usize::MAX,
+        });
+        let index = self.chunk.tries.len() - 1;
+        self.emit(Instruction::Try(index));
+        self.exits.push(Exit::Try);
+        self.try_blocks += 1;
+        self.block(&try_stmt.block);
+        self.try_blocks -= 1;
+        self.exits.pop();
+        self.emit(Instruction::EndTry);
+        let end = self.emit_jump(Instruction::Jump);
+        self.chunk.tries[index].catch = self.chunk.code.len();
+        self.exits
+            .push(Exit::Scope(self.resolutions.scope(try_stmt.span)));
+        self.block(&try_stmt.catch_block);
+        self.exits.pop();
+        self.exit_scope(try_stmt.span);
+        self.patch(end);
+    }
+
+    /// Compiles the given loop, which pushes its value, the value of `break` or `nil`.
+    fn loop_expression(&mut self, loop_expr: &LoopExpression) {
+        let (start, exit) = match &loop_expr.kind {
+            LoopKind::For(ident, iterable) => {
+                self.expression(iterable);
+                self.emit(Instruction::Iterate(iterable.span()));
+                let slot = self.resolutions.declaration(ident);
+                let ident = self.ident(ident);
+                let start = self.emit(Instruction::Next(slot, ident, usize::MAX));
+                (start, Some(start))
+            }
+            LoopKind::While(condition) => {
+                let start = self.chunk.code.len();
+                self.expression(condition);
+                (start, Some(self.emit_jump(Instruction::JumpIfFalsy)))
+            }
+            LoopKind::Loop => (self.chunk.code.len(), None),
+        };
+        let for_scope = matches!(loop_expr.kind, LoopKind::For(..))
+            .then(|| self.resolutions.scope(loop_expr.span));
+        self.loops.push(Loop {
+            exits: self.exits.len(),
+            for_scope: for_scope.clone(),
+            ..Default::default()
+        });
+        self.block(&loop_expr.block);
+        let loop_ = self.loops.pop().unwrap();
+        // The item of a for loop is in its own scope, so the block can shadow it.
+        let next = match &for_scope {
+            Some(scope) => self.emit(Instruction::ExitScope(scope.start, scope.end)),
+            None => start,
+        };
+        for continue_ in loop_.continues {
+            self.patch_to(continue_, next);
+        }
+        self.emit(Instruction::Jump(start));
+        if let Some(exit) = exit {
+            self.patch(exit);
+        }
+        self.constant(Value::Nil(loop_expr.span));
+        for break_ in loop_.breaks {
+            self.patch(break_);
+        }
+        if for_scope.is_some() {
+            self.emit(Instruction::EndIterate);
+        }
+    }
+
+    /// Compiles the given expression, which pushes its value.
+    fn expression(&mut self, expr: &ExpressionStatement) {
+        match expr {
+            ExpressionStatement::FunctionCall(func_call) => self.call(func_call, false),
+            ExpressionStatement::Value(value) => self.value(value),
+            ExpressionStatement::Loop(loop_expr) => self.loop_expression(loop_expr),
+            ExpressionStatement::Binary(binary) => {
+                self.operand(&binary.left);
+                let short_circuit =
+                    matches!(binary.operator, BinaryOperator::And | BinaryOperator::Or).then(
+                        || {
+                            self.emit(Instruction::ShortCircuit(
+                                binary.operator,
+                                binary.span,
+                                usize::MAX,
+                            ))
+                        },
+                    );
+                self.operand(&binary.right);
+                self.emit(Instruction::Binary(binary.operator, binary.span));
+                if let Some(short_circuit) = short_circuit {
+                    self.patch(short_circuit);
+                }
+            }
+            ExpressionStatement::Unary(unary) => {
+                self.operand(&unary.operand);
+                self.emit(Instruction::Unary(unary.operator, unary.span));
+            }
+            ExpressionStatement::Match(match_expr) => self.match_expression(match_expr),
+            ExpressionStatement::Record(record) => {
+                self.chunk.records.push(RecordCode {
+                    ident: record.ident.clone(),
+                    fields: record
+                        .fields
+                        .iter()
+                        .map(|(field, _)| field.clone())
+                        .collect(),
+                    span: record.span,
+                });
+                let index = self.chunk.records.len() - 1;
+                self.emit(Instruction::RecordType(index));
+                for (_, value) in &record.fields {
+                    self.expression(value);
+                }
+                self.emit(Instruction::Record(index));
+            }
+            ExpressionStatement::Borrow(borrow) => {
+                let index = self.ident(&borrow.ident);
+                // Borrowing a variant constructs it, like using its ident.
+                match self.resolutions.binding(&borrow.ident) {
+                    Some(Binding::Variant(_)) => {
+                        self.emit(Instruction::Variant(index, borrow.span))
+                    }
+                    Some(Binding::Local { slot, .. }) => {
+                        self.emit(Instruction::Borrow(*slot, index, borrow.span))
+                    }
+                    _ => self.emit(Instruction::BorrowGlobal(index, borrow.span)),
+                };
+            }
+            ExpressionStatement::FieldAccess(field_access) => {
+                self.expression(&field_access.value);
+                self.chunk.fields.push(FieldCode {
+                    field: field_access.field.clone(),
+                    record_span: field_access.value.span(),
+                    span: field_access.span,
+                });
+                self.emit(Instruction::Field(self.chunk.fields.len() - 1));
+            }
+        }
+    }
+
+    /// Compiles the operand of an operator, the span of its value will be the span of the operand.
+    fn operand(&mut self, operand: &ExpressionStatement) {
+        self.expression(operand);
+        self.emit(Instruction::Respan(operand.span()));
+    }
+
+    /// Compiles the given value.
+    fn value(&mut self, value: &ValueExpression) {
+        match value {
+            ValueExpression::Object(ObjectExpression::Array(array, _)) => {
+                for expr in array {
+                    self.expression(expr);
+                }
+                self.emit(Instruction::Array(array.len()));
+            }
+            ValueExpression::Object(ObjectExpression::Map(map, span)) => {
+                self.emit(Instruction::Map(*span));
+                for (key, value) in map {
+                    self.operand(key);
+                    self.expression(value);
+                    self.emit(Instruction::Insert);
+                }
+            }
+            ValueExpression::Object(ObjectExpression::Function(function)) => {
+                let closure = self.closure(function);
+                self.emit(Instruction::Closure(closure));
+            }
+            ValueExpression::Object(object) => self.constant(Value::literal(object)),
+            ValueExpression::Ident(ident) => {
+                let index = self.ident(ident);
+                match self.resolutions.binding(ident) {
+                    Some(Binding::Variant(_)) => self.emit(Instruction::Variant(index, ident.span)),
+                    Some(Binding::Local { slot, .. }) => self.emit(Instruction::Take(*slot, index)),
+                    _ => self.emit(Instruction::Global(index)),
+                };
+            }
+        }
+    }
+
+    /// Compiles the given anonymous function, and returns its index.
+    fn closure(&mut self, function: &FunctionStatement) -> usize {
+        compile_function(function, self.resolutions, self.chunks);
+        self.chunk.closures.push(Arc::new(function.clone()));
+        self.chunk.closures.len() - 1
+    }
+
+    /// Compiles the given match expression. The matched value stays under the arms until one of them matches,
+    /// an arm jumps to the next one if its pattern doesn't match the value or its guard is falsy.
+    fn match_expression(&mut self, match_expr: &MatchExpression) {
+        self.expression(&match_expr.value);
+        let mut ends = Vec::new();
+        for arm in &match_expr.arms {
+            self.chunk.patterns.push(arm.pattern.clone());
+            let next = self.emit(Instruction::Arm(self.chunk.patterns.len() - 1, usize::MAX));
+            let guard = arm.guard.as_ref().map(|guard| {
+                self.expression(guard);
+                self.emit_jump(Instruction::JumpIfFalsy)
+            });
+            self.expression(&arm.expression);
+            self.exit_scope(arm.span);
+            ends.push(self.emit_jump(Instruction::Jump));
+            if let Some(guard) = guard {
+                self.patch(guard);
+                self.exit_scope(arm.span);
+            }
+            self.patch(next);
+        }
+        self.emit(Instruction::NoMatch(match_expr.value.span()));
+        for end in ends {
+            self.patch(end);
+        }
+        self.emit(Instruction::PopUnder);
+    }
+
+    /// Compiles the given function call, the code of its arguments is compiled later.
+    fn call(&mut self, func_call: &FunctionCallExpression, is_tail: bool) {
+        let callee = match func_call.callable.as_ref() {
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) if self.is_variant(ident) => {
+                Callee::Variant(ident.clone())
+            }
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                match self.resolutions.binding(ident) {
+                    Some(Binding::Local { slot, .. }) => Callee::Local(*slot, ident.clone()),
+                    _ => Callee::Ident(ident.clone()),
+                }
+            }
+            ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Function(
+                function,
+            ))) => Callee::Closure(self.closure(function)),
+            callable => {
+                self.expression(callable);
+                Callee::Value(callable.span())
+            }
+        };
+        let index = self.chunk.calls.len();
+        self.args
+            .extend((0..func_call.args.len()).map(|arg| (index, arg)));
+        self.chunk.calls.push(CallCode {
+            callee,
+            args: func_call.args.clone(),
+            arg_code: vec![usize::MAX; func_call.args.len()],
+            is_positional: func_call
+                .args
+                .iter()
+                .all(|arg| !arg.is_unpack && arg.name.is_none()),
+            span: func_call.span,
+            is_tail,
+        });
+        self.emit(Instruction::Call(index));
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use super::{environment::Environment, value::FunctionValue};
+use crate::ast::*;
+
+/// Creates a closure from the given anonymous function, by capturing the variables and the local functions that
+/// it uses from the environment. The captured variables are moved to the closure, and the local functions are copied.
+/// The variables that the function only borrows are copied, so they stay in the environment.
+/// The idents that are not in the environment (e.g. global functions) are not captured.
+pub fn capture(
+    environment: &mut Environment,
+    function: Arc<FunctionStatement>,
+) -> Arc<FunctionValue> {
+    Arc::new(FunctionValue {
+        ident: None,
+        captures: environment.capture(&function),
+        statement: function,
+    })
+}
+
+/// Returns the free idents of the anonymous function, which are the idents that it uses and the idents that it only borrows.
+pub(crate) fn free_idents(function: &FunctionStatement) -> (Vec<String>, Vec<String>) {
+    let mut free_idents = FreeIdents::default();
+    free_idents.function(function);
+    (free_idents.idents, free_idents.borrowed)
+}
+
+/// Collects the idents that a function uses before declaring them, which are the idents that the function
+/// needs from the environment where it's created.
+#[derive(Debug, Default)]
+struct FreeIdents {
+    /// The declared idents of each scope, from the outermost to the innermost.
+    scopes: Vec<Vec<String>>,
+    /// The free idents, in the order of their first use.
+    idents: Vec<String>,
+    /// The free idents that are only borrowed, in the order of their first borrow.
+    borrowed: Vec<String>,
+}
+
+impl FreeIdents {
+    /// Visits the function, the params are declared in the function scope.
+    /// The default values of the params are executed in the function scope, so they can use the params.
+    fn function(&mut self, function: &FunctionStatement) {
+        self.scopes.push(
+            function
+                .params
+                .iter()
+                .map(|param| param.ident.ident.clone())
+                .collect(),
+        );
+        for default in function
+            .params
+            .iter()
+            .filter_map(|param| param.default.as_ref())
+        {
+            self.expression(default);
+        }
+        if let Some(block) = &function.block {
+            self.statements(&block.statements);
+        }
+        self.scopes.pop();
+    }
+
+    /// Visits the block in a new scope.
+    fn block(&mut self, block: &Block) {
+        self.scopes.push(Vec::new());
+        self.statements(&block.statements);
+        self.scopes.pop();
+    }
+
+    /// Declares the ident in the current scope.
+    fn declare(&mut self, ident: &Ident) {
+        self.scopes
+            .last_mut()
+            .expect("There must be at least one scope")
+            .push(ident.ident.clone());
+    }
+
+    /// Uses the ident, it's free if it's not declared in any scope.
+    fn use_ident(&mut self, ident: &Ident) {
+        if !self.is_declared(ident) && !self.idents.contains(&ident.ident) {
+            self.borrowed.retain(|name| name != &ident.ident);
+            self.idents.push(ident.ident.clone());
+        }
+    }
+
+    /// Borrows the ident, it's borrowed if it's free and not used.
+    fn borrow_ident(&mut self, ident: &Ident) {
+        if !self.is_declared(ident)
+            && !self.idents.contains(&ident.ident)
+            && !self.borrowed.contains(&ident.ident)
+        {
+            self.borrowed.push(ident.ident.clone());
+        }
+    }
+
+    /// Returns whether the ident is declared in any scope.
+    fn is_declared(&self, ident: &Ident) -> bool {
+        self.scopes
+            .iter()
+            .flatten()
+            .any(|name| name == &ident.ident)
+    }
+
+    fn statements(&mut self, statements: &[Statement]) {
+        statements
+            .iter()
+            .for_each(|statement| self.statement(statement));
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            // The named functions don't capture, so their blocks can't use the idents of this function.
+            Statement::Function(function) => {
+                if let Some(ident) = &function.ident {
+                    self.declare(ident);
+                }
+            }
+            Statement::Assignment(assignment) => {
+                self.expression(&assignment.expression);
+                self.declare(&assignment.ident);
+            }
+            Statement::FieldAssignment(assignment) => {
+                self.expression(&assignment.expression);
+                self.use_ident(&assignment.ident);
+            }
+            Statement::Return(return_stmt) => self.expression(&return_stmt.value),
+            Statement::If(if_stmt) => {
+                self.expression(&if_stmt.condition);
+                self.block(&if_stmt.block);
+                for else_if in &if_stmt.else_ifs {
+                    self.expression(&else_if.condition);
+                    self.block(&else_if.block);
+                }
+                if let Some(else_block) = &if_stmt.else_block {
+                    self.block(else_block);
+                }
+            }
+            Statement::Try(try_stmt) => {
+                self.block(&try_stmt.block);
+                // The caught error is in its own scope, like the item of a for loop.
+                self.scopes.push(vec![try_stmt.ident.ident.clone()]);
+                self.block(&try_stmt.catch_block);
+                self.scopes.pop();
+            }
+            Statement::Break(break_stmt) => {
+                if let Some(value) = &break_stmt.value {
+                    self.expression(value);
+                }
+            }
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Continue(_)
+            | Statement::Import(_)
+            | Statement::Record(_)
+            | Statement::Enum(_) => {}
+        }
+    }
+
+    fn expression(&mut self, expression: &ExpressionStatement) {
+        match expression {
+            ExpressionStatement::FunctionCall(func_call) => {
+                self.expression(&func_call.callable);
+                for arg in &func_call.args {
+                    self.expression(&arg.expr);
+                }
+            }
+            ExpressionStatement::Value(value) => self.value(value),
+            ExpressionStatement::Loop(loop_expr) => match &loop_expr.kind {
+                LoopKind::Loop => self.block(&loop_expr.block),
+                LoopKind::While(condition) => {
+                    self.expression(condition);
+                    self.block(&loop_expr.block);
+                }
+                LoopKind::For(ident, iterable) => {
+                    self.expression(iterable);
+                    self.scopes.push(vec![ident.ident.clone()]);
+                    self.block(&loop_expr.block);
+                    self.scopes.pop();
+                }
+            },
+            ExpressionStatement::Binary(binary) => {
+                self.expression(&binary.left);
+                self.expression(&binary.right);
+            }
+            ExpressionStatement::Unary(unary) => self.expression(&unary.operand),
+            // The name of the record type is not a variable, so only the values of the fields are visited.
+            ExpressionStatement::Record(record) => record
+                .fields
+                .iter()
+                .for_each(|(_, value)| self.expression(value)),
+            ExpressionStatement::FieldAccess(field_access) => self.expression(&field_access.value),
+            ExpressionStatement::Borrow(borrow) => self.borrow_ident(&borrow.ident),
+            ExpressionStatement::Match(match_expr) => {
+                self.expression(&match_expr.value);
+                for arm in &match_expr.arms {
+                    // The arm is executed in its own scope, with the bound idents of its pattern.
+                    self.scopes.push(Vec::new());
+                    self.pattern(&arm.pattern);
+                    if let Some(guard) = &arm.guard {
+                        self.expression(guard);
+                    }
+                    self.expression(&arm.expression);
+                    self.scopes.pop();
+                }
+            }
+        }
+    }
+
+    /// Declares the idents that are bound by the pattern.
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Ident(ident) => self.declare(ident),
+            Pattern::Array(patterns, rest, _) => {
+                patterns.iter().for_each(|pattern| self.pattern(pattern));
+                if let Some(rest) = rest {
+                    self.pattern(rest);
+                }
+            }
+            Pattern::Variant(_, patterns, _) => {
+                patterns.iter().for_each(|pattern| self.pattern(pattern))
+            }
+            Pattern::Literal(_) | Pattern::Wildcard(_) => {}
+        }
+    }
+
+    fn value(&mut self, value: &ValueExpression) {
+        match value {
+            ValueExpression::Ident(ident) => self.use_ident(ident),
+            // The nested anonymous functions are created in this function, so their free idents are captured from it.
+            ValueExpression::Object(ObjectExpression::Function(function)) => {
+                self.function(function)
+            }
+            ValueExpression::Object(ObjectExpression::Array(array, _)) => {
+                array.iter().for_each(|expr| self.expression(expr));
+            }
+            ValueExpression::Object(ObjectExpression::Map(map, _)) => {
+                for (key, value) in map {
+                    self.expression(key);
+                    self.expression(value);
+                }
+            }
+            ValueExpression::Object(_) => {}
+        }
+    }
+}
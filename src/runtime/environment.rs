@@ -1,202 +1,738 @@
+use std::{ops::Range, sync::Arc};
+
 use crate::ast::*;
 use crate::errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult, SpanError};
+use crate::modules::Module as SourceModule;
+use crate::resolver::{self, Binding, Resolutions};
+
+use super::{
+    builtins::{Builtins, NativeFunction},
+    interpreter::Argument,
+    symbols::{Symbol, SymbolMap, Symbols},
+    types,
+    value::{FunctionValue, Value},
+};
+
+/// A variable of a frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    /// The name of the variable.
+    pub ident: Ident,
+    /// The value of the variable.
+    pub value: Value,
+    /// The span where the variable is declared.
+    pub span: Span,
+}
 
-use super::builtins::Builtins;
+/// A local of a frame, which is a variable or a local function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Local {
+    /// A variable, it's moved out of the frame when it's used.
+    Variable(Variable),
+    /// A local function, it's copied when it's used. The global functions are used as local functions.
+    Function(Arc<FunctionValue>),
+}
 
+impl Local {
+    /// Returns the value of the local with the given span, a local function is a function value.
+    pub fn into_value(self, span: Span) -> Value {
+        match self {
+            Local::Variable(Variable { mut value, .. }) => {
+                *value.span_mut() = span;
+                value
+            }
+            Local::Function(function) => Value::Function(function, span),
+        }
+    }
+}
+
+/// A slot of a frame.
+#[derive(Debug, Clone)]
+enum Slot {
+    /// The slot is not declared yet, or its scope is exited.
+    Empty,
+    /// A variable or a local function.
+    Local(Local),
+    /// The variable is moved out of the slot, with the span where it's moved.
+    Moved((usize, usize)),
+}
+
+/// The frame of a function call, with the slots of the function (see [`crate::resolver`]).
+/// The blocks of the function don't have their own frames, their slots are cleared when they are exited.
 #[derive(Debug, Clone)]
 pub struct Frame {
-    /// The local functions that are available in the frame.
-    local_functions: Vec<FunctionStatement>,
-    /// The variables that are available in the frame.
-    variables: Vec<AssignmentStatement>,
+    slots: Vec<Slot>,
+}
+
+impl Frame {
+    /// Returns the variable or the local function of the slot, the variable will be moved out of the slot.
+    /// The span is where the variable is moved, it's used to report the use of the variable after the move.
+    /// Returns `None` if the slot is empty or moved.
+    fn take_local(&mut self, slot: usize, span: impl SpanError) -> Option<Local> {
+        match &self.slots[slot] {
+            Slot::Local(Local::Variable(_)) => {
+                let Slot::Local(local) =
+                    std::mem::replace(&mut self.slots[slot], Slot::Moved(span.span()))
+                else {
+                    unreachable!("The slot has a variable")
+                };
+                Some(local)
+            }
+            Slot::Local(Local::Function(function)) => Some(Local::Function(Arc::clone(function))),
+            Slot::Empty | Slot::Moved(_) => None,
+        }
+    }
+
+    /// Returns a copy of the variable or the local function of the slot, without moving it.
+    fn borrow_local(&self, slot: usize) -> Option<Local> {
+        match &self.slots[slot] {
+            Slot::Local(local) => Some(local.clone()),
+            Slot::Empty | Slot::Moved(_) => None,
+        }
+    }
+}
+
+/// The global functions and the types of a module (file).
+#[derive(Debug, Clone, Default)]
+struct Module {
+    /// The offset of the source of the module, the spans of the module start from it.
+    offset: usize,
+    /// The global functions of the module, which are its functions,
+    /// the public functions of its imported modules and the builtin functions.
+    functions: SymbolMap<Arc<FunctionValue>>,
+    /// The record types of the module, which are its records and the public records of its imported modules.
+    records: SymbolMap<RecordStatement>,
+    /// The enum types of the module, which are its enums and the public enums of its imported modules.
+    enums: SymbolMap<EnumStatement>,
+    /// The variants of the enum types, with the symbol of their enum type and their index in it.
+    variants: SymbolMap<(Symbol, usize)>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct Environment {
-    /// The global functions that are available in the environment.
-    global_functions: Vec<FunctionStatement>,
-    /// The frames, new frame are created when entering a function, and are removed when exiting a function.
+    /// The builtin functions, which are declared in each module, with the native functions of the host application.
+    builtins: Builtins,
+    /// The interned identifiers, the global functions and the types are keyed by their symbols.
+    symbols: Symbols,
+    /// The resolved idents of the program, the idents are found by their bindings.
+    resolutions: Resolutions,
+    /// The modules, the first one is the main module.
+    modules: Vec<Module>,
+    /// The index of the current module, which is the module of the executed function.
+    module: usize,
+    /// The frames, a new frame is created when entering a function, and is removed when exiting the function.
     frames: Vec<Frame>,
 }
 
 impl Environment {
-    /// Creates a new environment.
+    /// Creates a new environment, with the main module.
     pub fn new() -> Self {
-        let mut env = Self::default();
-        Builtins::new().env_init(&mut env).unwrap();
+        let mut env = Self {
+            builtins: Builtins::new(),
+            ..Self::default()
+        };
+        env.add_module();
         env
     }
 
-    /// Creates a new environment with the variables. Is used when passing arguments to a function.
-    /// The arguments are added to the environment and are available in the function.
-    /// The number of arguments must be equal to the number of parameter or it will panic.
-    pub fn new_for_function(&mut self, params: Vec<Param>, args: Vec<Arg>) -> OYResult<()> {
-        let get_ident = |expr: &ExpressionStatement| match &expr {
-            ExpressionStatement::Value(ValueExpression::Ident(ident)) => Some(ident.ident.clone()),
-            _ => None,
-        };
+    /// Adds a new module to the environment with the builtin functions, and returns its index.
+    pub fn add_module(&mut self) -> usize {
+        self.modules.push(Module::default());
+        let index = self.modules.len() - 1;
+        let current_module = self.enter_module(index);
+        self.builtins.clone().env_init(self).unwrap();
+        self.enter_module(current_module);
+        index
+    }
 
-        let mut variables = Vec::new();
-        let mut local_functions = Vec::new();
-
-        for (param, arg) in params.into_iter().zip(args) {
-            if let Some(ident) = get_ident(&arg.expr) {
-                match self.take(&ident, arg.span)? {
-                    Statement::Assignment(assignment) => variables.push({
-                        AssignmentStatement {
-                            ident: param.ident,
-                            expression: assignment.expression,
-                            span: arg.span,
-                        }
-                    }),
-                    Statement::Function(function) => local_functions.push({
-                        FunctionStatement {
-                            ident: Some(param.ident),
-                            params: function.params,
-                            block: function.block,
-                            visibility: Visibility::Private,
-                            span: arg.span,
-                        }
+    /// Returns the builtin functions, with the registered native functions.
+    pub fn builtins(&self) -> &Builtins {
+        &self.builtins
+    }
+
+    /// Registers a native function of the host application as a builtin function, with the given params, each param
+    /// is its name and whether it's a pack param. The function is declared in all the modules.
+    pub fn register_function(
+        &mut self,
+        name: &str,
+        params: &[(&str, bool)],
+        function: NativeFunction,
+    ) -> OYResult<()> {
+        let statement = self.builtins.register(name, params, function)?;
+        let current_module = self.module;
+        for index in 0..self.modules.len() {
+            self.enter_module(index);
+            self.add_global_function(Arc::new(FunctionValue::new(statement.clone())))?;
+        }
+        self.enter_module(current_module);
+        Ok(())
+    }
+
+    /// Returns the resolved idents of the program.
+    pub fn resolutions(&self) -> &Resolutions {
+        &self.resolutions
+    }
+
+    /// Adds the given modules, the first module is the main module. Each module has its own global functions and types,
+    /// which are its own and the public ones of its imports. The idents of the modules are resolved after adding them,
+    /// so the undeclared idents are reported before running. The current module is the main module after adding them.
+    pub fn add_modules(&mut self, modules: &[SourceModule]) -> OYResult<()> {
+        for (index, module) in modules.iter().enumerate() {
+            if index != 0 {
+                self.add_module();
+            }
+            self.enter_module(index);
+            self.modules[index].offset = module.offset;
+            for function in &module.functions {
+                self.add_global_function(Arc::new(FunctionValue::new(function.clone())))?;
+            }
+            for record in &module.records {
+                self.add_record(record.clone())?;
+            }
+            for enum_stmt in &module.enums {
+                self.add_enum(enum_stmt.clone())?;
+            }
+            // The names of the types and the variants declared in the module.
+            let declared_types = || {
+                module.records.iter().map(|record| &record.ident).chain(
+                    module.enums.iter().flat_map(|enum_stmt| {
+                        std::iter::once(&enum_stmt.ident)
+                            .chain(enum_stmt.variants.iter().map(|variant| &variant.ident))
                     }),
-                    _ => unreachable!(),
+                )
+            };
+            // The imported functions, types and variants with the span of their import statement.
+            let mut imported_functions: Vec<(&str, Span)> = Vec::new();
+            let mut imported_types: Vec<(&str, Span)> = Vec::new();
+            for (import, import_span) in &module.imports {
+                for function in modules[*import]
+                    .functions
+                    .iter()
+                    .filter(|function| function.visibility == Visibility::Public)
+                {
+                    // Global functions must have an identifier.
+                    let ident = function.ident.as_ref().unwrap();
+                    check_import(
+                        &imported_functions,
+                        module.functions.iter().filter_map(|f| f.ident.as_ref()),
+                        ident,
+                        *import_span,
+                    )?;
+                    imported_functions.push((&ident.ident, *import_span));
+                    self.add_global_function(Arc::new(FunctionValue::new(function.clone())))?;
+                }
+                for record in modules[*import]
+                    .records
+                    .iter()
+                    .filter(|record| record.visibility == Visibility::Public)
+                {
+                    check_import(
+                        &imported_types,
+                        declared_types(),
+                        &record.ident,
+                        *import_span,
+                    )?;
+                    imported_types.push((&record.ident.ident, *import_span));
+                    self.add_record(record.clone())?;
                 }
-            } else {
-                variables.push({
-                    AssignmentStatement {
-                        ident: param.ident,
-                        expression: arg.expr,
-                        span: arg.span,
+                for enum_stmt in modules[*import]
+                    .enums
+                    .iter()
+                    .filter(|enum_stmt| enum_stmt.visibility == Visibility::Public)
+                {
+                    for ident in std::iter::once(&enum_stmt.ident)
+                        .chain(enum_stmt.variants.iter().map(|variant| &variant.ident))
+                    {
+                        check_import(&imported_types, declared_types(), ident, *import_span)?;
+                        imported_types.push((&ident.ident, *import_span));
                     }
-                });
+                    self.add_enum(enum_stmt.clone())?;
+                }
             }
         }
-
-        self.enter_frame(local_functions, variables);
+        self.enter_module(0);
+        // The idents are resolved before running, so the undeclared idents are reported before running.
+        self.resolutions =
+            resolver::resolve_modules(modules, &self.builtins.functions, &mut self.symbols)
+                .map_err(|errors| {
+                    errors
+                        .into_iter()
+                        .next()
+                        .expect("The resolver returns at least one error")
+                })?;
         Ok(())
     }
 
-    /// Adds a function to the environment.
-    pub fn add_global_function(&mut self, new_function: FunctionStatement) -> OYResult<()> {
+    /// Returns the index of the module that contains the given span.
+    pub fn module_of(&self, span: Span) -> usize {
+        self.modules
+            .partition_point(|module| module.offset <= span.start)
+            .saturating_sub(1)
+    }
+
+    /// Returns the caught error as a map, with the keys:
+    /// - `kind`: The code of the error, e.g. `runtime::idents::undeclared_ident`, or `runtime::thrown` for the thrown values.
+    /// - `message`: The message of the error.
+    /// - `span`: The start and the end of the error in its file, as an array.
+    /// - `value`: The thrown value, or `nil` if the error is not thrown by the `throw` builtin function.
+    pub fn error_object(&self, err: OYError, span: Span) -> Value {
+        let (kind, message) = (err.code(), err.message());
+        let value = match err.kind {
+            OYErrorKind::Thrown(value) => *value,
+            _ => Value::Nil(span),
+        };
+        let start = err.span.offset()
+            - self.modules[self.module_of(Span::new(err.span.offset(), 0))].offset;
+        let entries = [
+            ("kind", Value::String(kind.into(), span)),
+            ("message", Value::String(message.into(), span)),
+            (
+                "span",
+                Value::Array(
+                    Arc::new(vec![
+                        Value::Int((start as u64).into(), span),
+                        Value::Int(((start + err.span.len()) as u64).into(), span),
+                    ]),
+                    span,
+                ),
+            ),
+            ("value", value),
+        ];
+        Value::Map(
+            Arc::new(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Value::String(key.into(), span), value))
+                    .collect(),
+            ),
+            span,
+        )
+    }
+
+    /// Sets the current module, the global functions will be from this module.
+    /// Returns the index of the previous module.
+    pub fn enter_module(&mut self, module: usize) -> usize {
+        std::mem::replace(&mut self.module, module)
+    }
+
+    /// Returns the state of the environment, which is the current module and the number of frames.
+    /// The environment can be restored to this state with [`Environment::restore`].
+    pub fn state(&self) -> (usize, usize) {
+        (self.module, self.frames.len())
+    }
+
+    /// Restores the environment to the given state, the frames that are added after it are removed.
+    /// This is used when an error is caught, because the frames of the failed functions are not removed.
+    pub fn restore(&mut self, (module, frames): (usize, usize)) {
+        self.module = module;
+        self.frames.truncate(frames);
+    }
+
+    /// Adds a global function to the current module.
+    pub fn add_global_function(&mut self, new_function: Arc<FunctionValue>) -> OYResult<()> {
+        // Global functions must have an identifier.
         let new_function_ident = new_function.ident.clone().unwrap();
-        if let Some(FunctionStatement {
+        let symbol = self.symbols.intern(&new_function_ident.ident);
+        if let Some(FunctionValue {
             ident: Some(ident), ..
-        }) = self
-            .global_functions
-            .iter()
-            // Global functions must have an identifier.
-            .find(|f| f.ident.as_ref().unwrap().ident == new_function_ident.ident)
+        }) = self.modules[self.module]
+            .functions
+            .get(&symbol)
+            .map(AsRef::as_ref)
         {
             Err(OYError::new(
                 OYErrorKind::AlreadyDeclared(new_function_ident.ident, ident.span.span()),
                 new_function_ident.span,
             ))
         } else {
-            self.global_functions.push(new_function);
+            self.modules[self.module]
+                .functions
+                .insert(symbol, new_function);
             Ok(())
         }
     }
 
-    /// Adds a local function to the environment.
-    pub fn add_local_function(&mut self, new_function: FunctionStatement) -> OYResult<()> {
-        let new_function_ident = new_function.ident.clone().unwrap();
-        if let Some(FunctionStatement {
-            ident: Some(ident), ..
-        }) = self
-            .frame()
-            .local_functions
-            .iter()
-            // Local functions must have an identifier.
-            .find(|f| f.ident.as_ref().unwrap().ident == new_function_ident.ident)
-        {
+    /// Adds a record type to the current module.
+    pub fn add_record(&mut self, new_record: RecordStatement) -> OYResult<()> {
+        let symbol = self.check_type(&new_record.ident)?;
+        self.modules[self.module].records.insert(symbol, new_record);
+        Ok(())
+    }
+
+    /// Adds an enum type to the current module.
+    /// The names of its variants must be unique in the module, because the variants are used without the enum name.
+    pub fn add_enum(&mut self, new_enum: EnumStatement) -> OYResult<()> {
+        let symbol = self.check_type(&new_enum.ident)?;
+        let mut variants = Vec::with_capacity(new_enum.variants.len());
+        for (index, variant) in new_enum.variants.iter().enumerate() {
+            if let Some((_, old_variant)) = self.get_variant(&variant.ident.ident) {
+                return Err(OYError::new(
+                    OYErrorKind::AlreadyDeclared(
+                        variant.ident.ident.clone(),
+                        old_variant.ident.span.span(),
+                    ),
+                    variant.ident.span,
+                ));
+            }
+            variants.push((self.symbols.intern(&variant.ident.ident), (symbol, index)));
+        }
+        let module = &mut self.modules[self.module];
+        module.variants.extend(variants);
+        module.enums.insert(symbol, new_enum);
+        Ok(())
+    }
+
+    /// Returns the symbol of the given type ident, or an error if a record or an enum type of the current module has it.
+    fn check_type(&mut self, ident: &Ident) -> OYResult<Symbol> {
+        let symbol = self.symbols.intern(&ident.ident);
+        let module = &self.modules[self.module];
+        let old_ident = module
+            .records
+            .get(&symbol)
+            .map(|record| &record.ident)
+            .or_else(|| module.enums.get(&symbol).map(|enum_| &enum_.ident));
+        if let Some(old_ident) = old_ident {
             Err(OYError::new(
-                OYErrorKind::AlreadyDeclared(new_function_ident.ident, ident.span.span()),
-                new_function_ident.span,
+                OYErrorKind::AlreadyDeclared(ident.ident.clone(), old_ident.span.span()),
+                ident.span,
             ))
         } else {
-            self.frame().local_functions.push(new_function);
+            Ok(symbol)
+        }
+    }
+
+    /// Returns the record type of the current module by ident.
+    pub fn get_record(&self, ident: &str) -> Option<&RecordStatement> {
+        let symbol = self.symbols.get(ident)?;
+        self.modules[self.module].records.get(&symbol)
+    }
+
+    /// Returns the variant of an enum type of the current module by ident, with its enum type.
+    pub fn get_variant(&self, ident: &str) -> Option<(&EnumStatement, &Variant)> {
+        let symbol = self.symbols.get(ident)?;
+        let module = &self.modules[self.module];
+        let (enum_symbol, index) = module.variants.get(&symbol)?;
+        let enum_ = &module.enums[enum_symbol];
+        Some((enum_, &enum_.variants[*index]))
+    }
+
+    /// Returns the variant of an enum type that the used ident is bound to, with its enum type.
+    pub fn variant(&self, ident: &Ident) -> Option<(&EnumStatement, &Variant)> {
+        let Some(Binding::Variant(symbol)) = self.resolutions.binding(ident) else {
+            return None;
+        };
+        let module = &self.modules[self.module];
+        let (enum_symbol, index) = module.variants.get(symbol)?;
+        let enum_ = &module.enums[enum_symbol];
+        Some((enum_, &enum_.variants[*index]))
+    }
+
+    /// Adds a local function to the environment.
+    pub fn add_local_function(&mut self, new_function: Arc<FunctionValue>) -> OYResult<()> {
+        // Local functions must have an identifier.
+        let new_function_ident = new_function.ident.clone().unwrap();
+        let slot = self.resolutions.declaration(&new_function_ident);
+        match &mut self.frame().slots[slot] {
+            Slot::Local(Local::Function(function)) => Err(OYError::new(
+                OYErrorKind::AlreadyDeclared(
+                    new_function_ident.ident,
+                    // Local functions must have an identifier.
+                    function.ident.as_ref().unwrap().span.span(),
+                ),
+                new_function_ident.span,
+            )),
+            slot => {
+                *slot = Slot::Local(Local::Function(new_function));
+                Ok(())
+            }
+        }
+    }
+
+    /// Adds a variable to the environment, a moved variable is declared again in its slot.
+    pub fn add_variable(&mut self, new_variable: Variable) -> OYResult<()> {
+        let slot = self.resolutions.declaration(&new_variable.ident);
+        self.declare_variable(slot, new_variable)
+    }
+
+    /// Assigns the variable of an assignment statement, it updates the variable of an enclosing scope with the same name
+    /// (see [`Resolutions::is_update`]), otherwise it's added like [`Environment::add_variable`].
+    pub fn assign_variable(&mut self, new_variable: Variable) -> OYResult<()> {
+        let slot = self.resolutions.declaration(&new_variable.ident);
+        if self.resolutions.is_update(&new_variable.ident) {
+            self.update_variable(slot, new_variable);
             Ok(())
+        } else {
+            self.declare_variable(slot, new_variable)
         }
     }
 
-    /// Adds a variable to the environment.
-    pub fn add_variable(&mut self, new_variable: AssignmentStatement) -> OYResult<()> {
-        if let Some(old_variable) = self
-            .frame()
-            .variables
-            .iter()
-            .find(|v| v.ident.ident == new_variable.ident.ident)
-        {
-            Err(OYError::new(
+    /// Updates the variable in the given slot with the new one, a moved variable owns the new value.
+    pub fn update_variable(&mut self, slot: usize, new_variable: Variable) {
+        self.frame().slots[slot] = Slot::Local(Local::Variable(new_variable));
+    }
+
+    /// Declares the variable in the given slot, which is the slot of its declaration (see [`Resolutions::declaration`]).
+    pub fn declare_variable(&mut self, slot: usize, new_variable: Variable) -> OYResult<()> {
+        match &mut self.frame().slots[slot] {
+            Slot::Local(Local::Variable(old_variable)) => Err(OYError::new(
                 OYErrorKind::AlreadyDeclared(new_variable.ident.ident, old_variable.span.span()),
                 new_variable.span,
-            ))
-        } else {
-            self.frame().variables.push(new_variable);
-            Ok(())
+            )),
+            slot => {
+                *slot = Slot::Local(Local::Variable(new_variable));
+                Ok(())
+            }
         }
     }
 
-    /// Adds a frame to the environment.
-    pub fn enter_frame(
-        &mut self,
-        local_functions: Vec<FunctionStatement>,
-        variables: Vec<AssignmentStatement>,
-    ) {
-        self.frames.push(Frame {
-            local_functions,
-            variables,
-        });
+    /// Creates the frame of a function with the argument of each parameter, `None` for the parameters that use their default value.
+    /// The arguments and the captures of the function (if it's a closure) are added to the frame and are available in the function.
+    /// The arguments are checked against the type annotations of their params, and are named by their params.
+    /// The frame is not entered, so a tail call can create the frame of the callee before leaving the frame of the caller,
+    /// use [`Environment::enter_function_frame`] to enter it.
+    pub fn function_frame(
+        &self,
+        function: &FunctionValue,
+        args: Vec<Option<Argument>>,
+    ) -> OYResult<Frame> {
+        let layout = self.resolutions.function(&function.statement);
+        let mut slots = vec![Slot::Empty; layout.slots];
+
+        // The params are the first slots.
+        for ((param, arg), slot) in function.statement.params.iter().zip(args).zip(&mut slots) {
+            match arg {
+                Some(Argument::Value(value, span)) => {
+                    types::check_param(param, &value, span)?;
+                    *slot = Slot::Local(Local::Variable(Variable {
+                        ident: param.ident.clone(),
+                        value,
+                        span,
+                    }));
+                }
+                Some(Argument::Function(function, span)) => {
+                    types::check(param.type_annotation.as_ref(), "function", span)?;
+                    *slot = Slot::Local(Local::Function(Arc::new(FunctionValue {
+                        ident: Some(param.ident.clone()),
+                        ..FunctionValue::clone(&function)
+                    })));
+                }
+                None => {}
+            }
+        }
+
+        for (slot, capture) in &function.captures {
+            slots[*slot] = Slot::Local(capture.clone());
+        }
+
+        Ok(Frame { slots })
     }
 
-    /// Returns the last frame.
-    pub fn frame(&mut self) -> &mut Frame {
+    /// Adds the frame of a function, which is created by [`Environment::function_frame`], to the environment.
+    pub fn enter_function_frame(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    /// Clears the slots of the scope with the given span, they are declared again when the scope is entered again.
+    /// This is used when exiting a block, a match arm, a for loop or a catch block (see [`Resolutions::scope`]).
+    pub fn exit_scope(&mut self, span: Span) {
+        let scope = self.resolutions.scope(span);
+        self.clear_slots(scope);
+    }
+
+    /// Clears the given slots of the current frame, which are the slots of a scope.
+    pub fn clear_slots(&mut self, slots: Range<usize>) {
+        self.frame().slots[slots].fill(Slot::Empty);
+    }
+
+    /// Returns the frame of the current function.
+    fn frame(&mut self) -> &mut Frame {
         self.frames
             .last_mut()
             .expect("There must be at least one frame.")
     }
 
-    /// Exit the current frame.
-    /// This is used to remove the frame from the environment.
+    /// Exit the frame of the current function.
     pub fn exit_frame(&mut self) {
         self.frames.pop();
     }
 
-    /// Return the global function by ident
-    pub fn get_global_function(&self, ident: &str) -> Option<FunctionStatement> {
-        self.global_functions
+    /// Return the global function of the current module by ident
+    pub fn get_global_function(&self, ident: &str) -> Option<Arc<FunctionValue>> {
+        let symbol = self.symbols.get(ident)?;
+        self.modules[self.module].functions.get(&symbol).cloned()
+    }
+
+    /// Returns a mutable reference to the variable that the ident is bound to, without moving it.
+    /// This is used to update the fields of a record variable.
+    pub fn variable_mut(&mut self, ident: &Ident) -> Option<&mut Variable> {
+        let Some(Binding::Local { slot, .. }) = self.resolutions.binding(ident).copied() else {
+            return None;
+        };
+        match &mut self.frame().slots[slot] {
+            Slot::Local(Local::Variable(variable)) => Some(variable),
+            _ => None,
+        }
+    }
+
+    /// Captures the idents of the given anonymous function from the current frame, with their slots in the function.
+    /// The captured variables are moved to the function, except the borrowed ones, and the local functions are copied.
+    /// The idents that are not in the frame (e.g. moved variables) are not captured.
+    pub fn capture(&mut self, function: &FunctionStatement) -> Vec<(usize, Local)> {
+        // The resolutions and the frames are borrowed separately, so the captures are not copied.
+        let frame = self
+            .frames
+            .last_mut()
+            .expect("There must be at least one frame.");
+        self.resolutions
+            .function(function)
+            .captures
             .iter()
-            // Global functions must have an identifier.
-            .find(|f| f.ident.as_ref().unwrap().ident == ident)
-            .cloned()
+            .filter_map(|capture| {
+                let local = if capture.is_borrowed {
+                    frame.borrow_local(capture.from)
+                } else {
+                    frame.take_local(capture.from, function.span)
+                };
+                local.map(|local| (capture.slot, local))
+            })
+            .collect()
+    }
+
+    /// Returns the global function of the current module by symbol.
+    fn global_function(&self, symbol: Symbol) -> Option<Arc<FunctionValue>> {
+        self.modules[self.module].functions.get(&symbol).cloned()
+    }
+
+    /// Returns a copy of the value that the ident is bound to, without removing it from the environment.
+    /// This is used when the value is borrowed, e.g. `&x`, so the ident can be used again.
+    pub fn borrow(&self, ident: &Ident, span: impl SpanError) -> OYResult<Local> {
+        let global = match self.resolutions.binding(ident) {
+            Some(Binding::Local { slot, global }) => {
+                let frame = self
+                    .frames
+                    .last()
+                    .expect("There must be at least one frame.");
+                if let Some(local) = frame.borrow_local(*slot) {
+                    return Ok(local);
+                }
+                *global
+            }
+            Some(Binding::Global(symbol) | Binding::Builtin(symbol)) => Some(*symbol),
+            Some(Binding::Variant(_)) | None => None,
+        };
+        global
+            .and_then(|symbol| self.global_function(symbol))
+            .map(Local::Function)
+            .ok_or_else(|| self.undeclared_error(ident, span))
     }
 
-    /// Returns the value from the environment, and removes it from the environment.
+    /// Returns the value that the ident is bound to, and removes it from the environment.
     /// This is used when you want to move the value to another owner.
     /// If the ident is a global function, its will not be removed from the environment.
-    pub fn take(&mut self, ident: &str, span: impl SpanError) -> OYResult<Statement> {
-        if let Some(var) = self
-            .frame()
-            .variables
-            .iter()
-            .position(|v| v.ident.ident == ident)
-        {
-            Ok(Statement::Assignment(self.frame().variables.remove(var)))
-        } else if let Some(local_func) = self
-            .frame()
-            .local_functions
-            .iter()
-            // Local functions must have an identifier.
-            .find(|f| f.ident.as_ref().unwrap().ident == ident)
-        {
-            Ok(Statement::Function(local_func.clone()))
-        } else if let Some(func) = self.get_global_function(ident) {
-            // Not removing the global function from the environment.
-            Ok(Statement::Function(func))
-        } else {
-            Err(OYError::new(
-                OYErrorKind::UnDeclaredIdent(ident.to_owned()),
+    pub fn take(&mut self, ident: &Ident, span: impl SpanError + Copy) -> OYResult<Local> {
+        let global = match self.resolutions.binding(ident).copied() {
+            Some(Binding::Local { slot, global }) => {
+                if let Some(local) = self.frame().take_local(slot, span) {
+                    return Ok(local);
+                }
+                global
+            }
+            Some(Binding::Global(symbol) | Binding::Builtin(symbol)) => Some(symbol),
+            Some(Binding::Variant(_)) | None => None,
+        };
+        // Not removing the global function from the environment.
+        global
+            .and_then(|symbol| self.global_function(symbol))
+            .map(Local::Function)
+            .ok_or_else(|| self.undeclared_error(ident, span))
+    }
+
+    /// Moves the variable out of the given slot, which is the slot of the ident (see [`Binding::Local`]), a local function
+    /// is copied. The ident is taken by [`Environment::take`] when the slot is empty or moved, so its global function is
+    /// used or its error is returned.
+    pub fn take_slot(
+        &mut self,
+        slot: usize,
+        ident: &Ident,
+        span: impl SpanError + Copy,
+    ) -> OYResult<Local> {
+        match self.frame().take_local(slot, span) {
+            Some(local) => Ok(local),
+            None => self.take(ident, span),
+        }
+    }
+
+    /// Returns a copy of the value of the given slot, which is the slot of the ident, with the span of the borrow.
+    /// Only the value of a variable is copied, without its ident. The ident is borrowed by [`Environment::borrow`]
+    /// when the slot is empty or moved.
+    pub fn borrow_slot(&self, slot: usize, ident: &Ident, span: Span) -> OYResult<Value> {
+        let frame = self
+            .frames
+            .last()
+            .expect("There must be at least one frame.");
+        match &frame.slots[slot] {
+            Slot::Local(Local::Variable(variable)) => {
+                let mut value = variable.value.clone();
+                *value.span_mut() = span;
+                Ok(value)
+            }
+            Slot::Local(Local::Function(function)) => {
+                Ok(Value::Function(Arc::clone(function), span))
+            }
+            Slot::Empty | Slot::Moved(_) => Ok(self.borrow(ident, span)?.into_value(span)),
+        }
+    }
+
+    /// Returns the error of using the ident that is not in the environment.
+    /// If the ident is a moved variable, the error is a use after move error with the span where it's moved,
+    /// otherwise it's an undeclared ident error.
+    pub fn undeclared_error(&self, ident: &Ident, span: impl SpanError) -> OYError {
+        let moved_span = match self.resolutions.binding(ident) {
+            Some(Binding::Local { slot, .. }) => {
+                let frame = self
+                    .frames
+                    .last()
+                    .expect("There must be at least one frame.");
+                match frame.slots[*slot] {
+                    Slot::Moved(moved_span) => Some(moved_span),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match moved_span {
+            Some(moved_span) => OYError::new(
+                OYErrorKind::UseAfterMove(ident.ident.clone(), moved_span),
                 span,
-            ))
+            ),
+            None => OYError::new(OYErrorKind::UnDeclaredIdent(ident.ident.clone()), span),
         }
     }
 }
+
+/// Checks that the imported ident is not declared in the importer module, and is not imported by a previous import.
+/// The imported idents are with the span of their import statement.
+fn check_import<'a>(
+    imported: &[(&str, Span)],
+    declared: impl IntoIterator<Item = &'a Ident>,
+    ident: &Ident,
+    import_span: Span,
+) -> OYResult<()> {
+    let old_decl = imported
+        .iter()
+        .find(|(name, _)| name == &ident.ident)
+        .map(|(_, span)| *span)
+        .or_else(|| {
+            declared
+                .into_iter()
+                .find(|declared| declared.ident == ident.ident)
+                .map(|declared| declared.span)
+        });
+    if let Some(old_decl) = old_decl {
+        return Err(OYError::new(
+            OYErrorKind::AlreadyDeclared(ident.ident.clone(), old_decl.span()),
+            import_span,
+        ));
+    }
+    Ok(())
+}
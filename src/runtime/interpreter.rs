@@ -1,17 +1,99 @@
+use std::sync::Arc;
+
 use bigdecimal::ToPrimitive;
 
-use super::{builtins::Builtins, environment::Environment};
+use super::{
+    builtins::Builtins,
+    closures,
+    environment::{Environment, Frame, Local, Variable},
+    maps, operators, patterns, records, types,
+    value::{FunctionValue, Value, VariantValue},
+};
 use crate::{
     ast::*,
-    errors::{Error as OYError, ErrorKind, Result as OYResult, SpanError},
-    utils,
+    errors::{CallSite, Error as OYError, ErrorKind, Result as OYResult, SpanError},
+    modules::Module,
+    utils::{self, ArgExecutor, CallArg},
 };
 
+/// The control flow of a statement.
+#[derive(Debug, Clone)]
+pub enum ControlFlow {
+    /// Return from the function with the value.
+    Return(Value),
+    /// Stop the loop with the value.
+    Break(Value),
+    /// Skip to the next iteration of the loop.
+    Continue,
+    /// Return from the function with the result of the call, the call is executed after leaving the function.
+    TailCall(Box<Call>),
+}
+
+/// An executed argument of a function call, with the span of the argument.
+#[derive(Debug, Clone)]
+pub enum Argument {
+    /// A value, e.g. the result of an expression or a moved variable.
+    Value(Value, Span),
+    /// A local (or global) function that is passed by its ident, it's a local function of the called function,
+    /// so it can be called more than once.
+    Function(Arc<FunctionValue>, Span),
+}
+
+/// A function call that is ready to be executed, its arguments are in the frame of the function.
+#[derive(Debug, Clone)]
+pub struct Call {
+    /// The called function.
+    pub function: Arc<FunctionValue>,
+    /// The frame of the function, with the given arguments and the captures.
+    pub frame: Frame,
+    /// The indexes of the params that use their default values, which are executed in the frame of the function.
+    pub defaults: Vec<usize>,
+    /// The span of the call, `None` for the call of the main function.
+    pub span: Option<Span>,
+}
+
+impl Call {
+    /// Returns the call site of the call, for the traceback of the runtime errors.
+    pub fn call_site(&self) -> CallSite {
+        call_site(&self.function, self.span)
+    }
+}
+
+/// A resolved function call, the called function with the argument of each parameter,
+/// `None` for the parameters that use their default value.
+type ResolvedCall = (Arc<FunctionValue>, Vec<Option<Argument>>);
+
+/// The default maximum number of nested function calls.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+
+/// The size of the native stack for each nested function call, the stack of the program is big enough for the maximum call depth.
+const STACK_SIZE_PER_CALL: usize = 128 * 1024;
+
+/// The maximum size of the native stack of the program (4 GiB), a bigger stack can't be allocated on most machines.
+const MAX_STACK_SIZE: usize = u32::MAX as usize;
+
+/// The maximum number of nested function calls, a bigger maximum call depth is clamped to it, so the stack of the program
+/// is not bigger than its maximum size.
+pub const MAX_CALL_DEPTH: usize = MAX_STACK_SIZE / STACK_SIZE_PER_CALL - 1;
+
 /// The interpreter. This will execute the AST of Ocypode and return the result. check the AST in `src/front/ast.rs`.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Interpreter {
     /// The current environment.
     environment: Environment,
+    /// The call stack, from the call of the main function to the call of the current function. A tail call replaces its caller.
+    calls: Vec<CallSite>,
+    /// The maximum number of nested function calls, a deeper call is a stack overflow error.
+    max_call_depth: usize,
+    /// The number of the executing try blocks of the current function. A call that is returned from a try block
+    /// is not a tail call, so its errors are caught by the block.
+    try_blocks: usize,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
@@ -19,235 +101,816 @@ impl Interpreter {
     pub fn new() -> Self {
         Self {
             environment: Environment::new(),
+            calls: Vec::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            try_blocks: 0,
         }
     }
 
+    /// Sets the maximum number of nested function calls, the default is [`DEFAULT_MAX_CALL_DEPTH`] and the maximum is
+    /// [`MAX_CALL_DEPTH`]. The tail calls (`return f<...>;`) replace their caller, so they are not counted.
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth.min(MAX_CALL_DEPTH);
+        self
+    }
+
+    /// Registers a native Rust function as a builtin function, with the given params, each param is its name and whether
+    /// it's a pack param, whose arguments are packed into an array. The function is called with the arguments and the span
+    /// of the call, which is the span of its errors, e.g. `Error::new(ErrorKind::Runtime(reason), span)`.
+    pub fn register_function(
+        &mut self,
+        name: &str,
+        params: &[(&str, bool)],
+        function: impl Fn(Vec<Value>, Span) -> OYResult<Value> + Send + Sync + 'static,
+    ) -> OYResult<()> {
+        self.environment
+            .register_function(name, params, Arc::new(function))
+    }
+
+    /// Returns the builtin functions with the registered native functions, the program is checked with them before
+    /// running, see [`ownership::check_modules`](crate::ownership::check_modules).
+    pub fn builtins(&self) -> &Builtins {
+        self.environment.builtins()
+    }
+
     /// Interprets the given program. This will return the exit code of the program.
-    pub fn interpret(mut self, program: Program, argc: usize, argv: Vec<String>) -> OYResult<u8> {
-        let mut exit_code = 0;
-        // The program contains only functions. So we need to add them to the environment.
-        for function in program.0 {
-            match function {
-                Statement::Function(function) => {
-                    self.environment.add_global_function(function)?;
-                }
-                _ => unreachable!("The program only contains functions"),
-            }
-        }
+    /// The program can't contain imports, to run a program with imports use [`Interpreter::interpret_modules`].
+    pub fn interpret(self, program: Program, argc: usize, argv: Vec<String>) -> OYResult<u8> {
+        self.interpret_modules(vec![Module::from_program(program)?], argc, argv)
+    }
 
-        // Then we need to find the main function.
-        if let Some(main_function) = self.environment.get_global_function("main") {
-            let args = vec![
-                Arg {
-                    expr: ExpressionStatement::Value(ValueExpression::Object(
-                        ObjectExpression::Int(argc.to_string().parse().unwrap(), Span::new(0, 0)),
-                    )),
-                    is_unpack: false,
-                    span: Span::new(0, 0),
-                },
-                Arg {
-                    expr: ExpressionStatement::Value(ValueExpression::Object(
-                        ObjectExpression::Array(
-                            argv.into_iter()
-                                .map(|v| {
-                                    ExpressionStatement::Value(ValueExpression::Object(
-                                        ObjectExpression::String(v, Span::new(0, 0)),
-                                    ))
-                                })
-                                .collect(),
-                            Span::new(0, 0),
-                        ),
-                    )),
-                    is_unpack: false,
-                    span: Span::new(0, 0),
-                },
-            ];
-            exit_code = match self.execute_function(main_function, args)? {
-                ObjectExpression::Int(int, span) => int
-                    .to_u8()
-                    .ok_or_else(|| OYError::new(ErrorKind::InvalidExitCode(int), span))?,
-                _ => exit_code,
-            };
-            Ok(exit_code)
-        } else {
-            Err(OYError::new(ErrorKind::MissingMainFunction, (0, 0)))
-        }
+    /// Interprets the given modules, the first module is the main module. This will return the exit code of the program.
+    pub fn interpret_modules(
+        mut self,
+        modules: Vec<Module>,
+        argc: usize,
+        argv: Vec<String>,
+    ) -> OYResult<u8> {
+        self.environment.add_modules(&modules)?;
+        let main_function = self
+            .environment
+            .get_global_function("main")
+            .ok_or_else(|| OYError::new(ErrorKind::MissingMainFunction, (0, 0)))?;
+        run_main(self.max_call_depth, argc, argv, |args| {
+            self.execute_function(main_function, args, None)
+        })
     }
 
-    /// Executes the given function with the given arguments.
+    /// Executes the given function with the argument of each parameter, `None` for the parameters that use their default value.
     /// This will return the result of the function. If the function does not return anything, it will return `nil`.
+    /// The arguments and the result are checked against the type annotations of the function.
+    /// The span is the span of the call, `None` for the call of the main function.
     ///
-    /// Note: The environment should contain a fream for the function (Will removed after the function is executed)
+    /// The error of the function has the traceback of the calls, from the main function to the function where it happened.
     pub fn execute_function(
         &mut self,
-        function: FunctionStatement,
-        args: Vec<Arg>,
-    ) -> OYResult<ObjectExpression> {
-        self.environment.new_for_function(function.params, args)?;
-        let mut result = ObjectExpression::Nil(function.span);
-        if let Some(block) = function.block {
-            for statement in block.statements {
-                if let Some(return_value) = self.execute_statement(statement)? {
-                    result = return_value;
-                    break;
+        function: Arc<FunctionValue>,
+        args: Vec<Option<Argument>>,
+        span: Option<Span>,
+    ) -> OYResult<Value> {
+        let call = self.prepare_call(function, args, span)?;
+        self.calls.push(call.call_site());
+        // The try blocks of the caller don't catch the tail calls of the function.
+        let caller_try_blocks = std::mem::take(&mut self.try_blocks);
+        let result = self.execute_call(call).map_err(|err| {
+            // The traceback is taken in the innermost function, where the stack has all the calls.
+            if err.traceback.is_empty() {
+                err.with_traceback(self.calls.clone())
+            } else {
+                err
+            }
+        })?;
+        self.try_blocks = caller_try_blocks;
+        self.calls.pop();
+        Ok(result)
+    }
+
+    /// Creates the frame of the given function with the argument of each parameter, `None` for the parameters that use their default value.
+    pub fn prepare_call(
+        &mut self,
+        function: Arc<FunctionValue>,
+        args: Vec<Option<Argument>>,
+        span: Option<Span>,
+    ) -> OYResult<Call> {
+        let defaults = args
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| arg.is_none())
+            .map(|(index, _)| index)
+            .collect();
+        let frame = self.environment.function_frame(&function, args)?;
+        Ok(Call {
+            function,
+            frame,
+            defaults,
+            span,
+        })
+    }
+
+    /// Executes the given call, then the tail calls that it returns, one after the other in the same Rust stack frame,
+    /// so a tail recursion runs in a constant stack. The result is checked against the return types of all the executed functions.
+    fn execute_call(&mut self, mut call: Call) -> OYResult<Value> {
+        let caller_module = self.environment.state().0;
+        let mut return_types: Vec<TypeAnnotation> = Vec::new();
+        let result = loop {
+            let function = Arc::clone(&call.function);
+            let Some(block) = &function.statement.block else {
+                unreachable!("The builtin function should call in the call expression")
+            };
+            self.environment.enter_function_frame(call.frame);
+            // The function is executed in its module, so it can use the global functions of its file.
+            self.environment
+                .enter_module(self.environment.module_of(block.span));
+            // The default values are executed in the function scope, after the given arguments.
+            for index in call.defaults {
+                let param = &function.statement.params[index];
+                let default = param
+                    .default
+                    .as_ref()
+                    .expect("The params without arguments have default values");
+                let span = default.span();
+                let value = self.execute_expression(default)?;
+                types::check_param(param, &value, span)?;
+                self.environment.add_variable(Variable {
+                    ident: param.ident.clone(),
+                    value,
+                    span,
+                })?;
+            }
+            let mut result = Value::Nil(function.statement.span);
+            let mut tail_call = None;
+            for statement in &block.statements {
+                match self.execute_statement(statement)? {
+                    Some(ControlFlow::Return(return_value)) => {
+                        result = return_value;
+                        break;
+                    }
+                    Some(ControlFlow::TailCall(next_call)) => {
+                        tail_call = Some(next_call);
+                        break;
+                    }
+                    Some(_) => unreachable!(
+                        "The parser checks that `break` and `continue` are inside a loop"
+                    ),
+                    None => {}
                 }
             }
-        } else {
-            unreachable!("The builtin function should call in the call expression")
+            self.environment.exit_frame();
+            if let Some(return_type) = &function.statement.return_type {
+                // The calls of a tail recursion have the same return type, so it's checked once.
+                if return_types.last() != Some(return_type) {
+                    return_types.push(return_type.clone());
+                }
+            }
+            match tail_call {
+                Some(next_call) => {
+                    call = *next_call;
+                    *self.calls.last_mut().expect("The caller is in the calls") = call.call_site();
+                }
+                None => break result,
+            }
+        };
+        self.environment.enter_module(caller_module);
+        // The result of the last tail call is the result of all its callers.
+        for return_type in return_types.iter().rev() {
+            types::check(Some(return_type), result.type_name(), result.span())?;
         }
-        self.environment.exit_frame();
         Ok(result)
     }
 
     /// Executes the given statement.
-    /// This will return the result of the value of return statement. if there is no return statement, it will return `nil`.
-    pub fn execute_statement(
-        &mut self,
-        statement: Statement,
-    ) -> OYResult<Option<ObjectExpression>> {
+    /// This will return the control flow of the statement, if there is a return, break or continue statement.
+    pub fn execute_statement(&mut self, statement: &Statement) -> OYResult<Option<ControlFlow>> {
         match statement {
-            Statement::Function(function) => {
-                self.environment.add_local_function(function).map(|_| None)
+            Statement::Function(function) => self
+                .environment
+                .add_local_function(Arc::new(FunctionValue::new(function.clone())))
+                .map(|_| None),
+            Statement::Assignment(assign) => self.execute_assign(assign),
+            // A returned function call is a tail call, it's executed after leaving the function.
+            // Except in a try block, where its errors must be caught by the block.
+            Statement::Return(ReturnStatement {
+                value: ExpressionStatement::FunctionCall(func_call),
+                ..
+            }) if self.try_blocks == 0 => {
+                let span = func_call.span;
+                Ok(Some(match self.resolve_function_call(func_call)? {
+                    Ok((function, args)) => ControlFlow::TailCall(Box::new(self.prepare_call(
+                        function,
+                        args,
+                        Some(span),
+                    )?)),
+                    Err(return_value) => ControlFlow::Return(return_value),
+                }))
             }
-            Statement::Assignment(assign) => self.execute_assign(assign).map(|_| None),
-            Statement::Return(return_stmt) => {
-                let return_value = self.execute_expression(return_stmt.value)?;
-                Ok(Some(return_value))
+            Statement::Return(return_stmt) => Ok(Some(
+                match self.execute_statement_expression(&return_stmt.value)? {
+                    Ok(return_value) => ControlFlow::Return(return_value),
+                    Err(control_flow) => control_flow,
+                },
+            )),
+            Statement::If(if_stmt) => self.execute_if(if_stmt),
+            Statement::Try(try_stmt) => self.execute_try(try_stmt),
+            Statement::Break(break_stmt) => Ok(Some(ControlFlow::Break(
+                break_stmt
+                    .value
+                    .as_ref()
+                    .map(|value| self.execute_expression(value))
+                    .transpose()?
+                    .unwrap_or(Value::Nil(break_stmt.span)),
+            ))),
+            Statement::Continue(_) => Ok(Some(ControlFlow::Continue)),
+            Statement::Expression(expr) => Ok(self.execute_statement_expression(expr)?.err()),
+            Statement::FieldAssignment(assignment) => self.execute_field_assign(assignment),
+            Statement::Import(_) | Statement::Record(_) | Statement::Enum(_) => {
+                unreachable!("The import and type statements can only be at the top of a file")
             }
-            Statement::Expression(expr) => {
-                self.execute_expression(expr)?;
-                Ok(None)
+        }
+    }
+
+    /// Executes the given if statement.
+    /// This will execute the block of the first truthy condition, or the else block if there is no truthy condition.
+    /// The control flow of the executed block will be returned.
+    pub fn execute_if(&mut self, if_stmt: &IfStatement) -> OYResult<Option<ControlFlow>> {
+        if self.execute_expression(&if_stmt.condition)?.is_truthy() {
+            return self.execute_block(&if_stmt.block);
+        }
+        for else_if in &if_stmt.else_ifs {
+            if self.execute_expression(&else_if.condition)?.is_truthy() {
+                return self.execute_block(&else_if.block);
             }
         }
+        if let Some(else_block) = &if_stmt.else_block {
+            self.execute_block(else_block)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Executes the given try statement.
+    /// If the try block fails, the environment is restored to its state before the block, and the catch block
+    /// is executed with the error in the ident. The control flow of the executed block will be returned.
+    pub fn execute_try(&mut self, try_stmt: &TryStatement) -> OYResult<Option<ControlFlow>> {
+        let state = self.environment.state();
+        let (calls, try_blocks) = (self.calls.len(), self.try_blocks);
+        self.try_blocks += 1;
+        let result = self.execute_block(&try_stmt.block);
+        self.try_blocks = try_blocks;
+        let err = match result {
+            Ok(control_flow) => return Ok(control_flow),
+            Err(err) => err,
+        };
+        self.environment.restore(state);
+        // The failed block is not exited, so its slots are cleared here.
+        self.environment.exit_scope(try_stmt.block.span);
+        self.calls.truncate(calls);
+        // The error is in its own scope, so the catch block can shadow it.
+        self.environment.add_variable(Variable {
+            ident: try_stmt.ident.clone(),
+            value: self.environment.error_object(err, try_stmt.ident.span),
+            span: try_stmt.ident.span,
+        })?;
+        let control_flow = self.execute_block(&try_stmt.catch_block)?;
+        self.environment.exit_scope(try_stmt.span);
+        Ok(control_flow)
+    }
+
+    /// Executes the given loop.
+    /// This will return `ControlFlow::Break` with the value of the loop, or `ControlFlow::Return` if the loop block returns from the function.
+    pub fn execute_loop(&mut self, loop_expr: &LoopExpression) -> OYResult<ControlFlow> {
+        if let LoopKind::For(ident, iterable) = &loop_expr.kind {
+            for item in self.execute_iterable(iterable)? {
+                // The item is in its own scope, so the block can shadow it.
+                self.environment.add_variable(Variable {
+                    ident: ident.clone(),
+                    value: item,
+                    span: ident.span,
+                })?;
+                let control_flow = self.execute_block(&loop_expr.block)?;
+                self.environment.exit_scope(loop_expr.span);
+                match control_flow {
+                    Some(ControlFlow::Continue) | None => {}
+                    Some(control_flow) => return Ok(control_flow),
+                }
+            }
+        } else {
+            loop {
+                if let LoopKind::While(condition) = &loop_expr.kind {
+                    if !self.execute_expression(condition)?.is_truthy() {
+                        break;
+                    }
+                }
+                match self.execute_block(&loop_expr.block)? {
+                    Some(ControlFlow::Continue) | None => {}
+                    Some(control_flow) => return Ok(control_flow),
+                }
+            }
+        }
+        Ok(ControlFlow::Break(Value::Nil(loop_expr.span)))
+    }
+
+    /// Executes the iterable of a for loop.
+    /// This will return the elements of the array, or the characters of the string.
+    pub fn execute_iterable(&mut self, iterable: &ExpressionStatement) -> OYResult<Vec<Value>> {
+        let value = self.execute_expression(iterable)?;
+        items(value, iterable.span())
+    }
+
+    /// Executes the given block in a new scope.
+    /// This will return the control flow of the block, if there is one.
+    pub fn execute_block(&mut self, block: &Block) -> OYResult<Option<ControlFlow>> {
+        let mut result = None;
+        for statement in &block.statements {
+            if let Some(control_flow) = self.execute_statement(statement)? {
+                result = Some(control_flow);
+                break;
+            }
+        }
+        self.environment.exit_scope(block.span);
+        Ok(result)
     }
 
     /// Executes the given assign statement.
-    /// This will execute the expr and save the varibal with the object
-    pub fn execute_assign(&mut self, assign: AssignmentStatement) -> OYResult<()> {
-        let object = self.execute_expression(assign.expression)?;
-        let assign = AssignmentStatement {
-            ident: assign.ident,
-            expression: ExpressionStatement::Value(ValueExpression::Object(object)),
+    /// This will execute the expr and save the varibal with the value, the value is checked against the type annotation of the variable.
+    /// A variable of an enclosing block is updated.
+    /// If the assigned value is a loop that returns from the function, the return control flow will be returned.
+    pub fn execute_assign(
+        &mut self,
+        assign: &AssignmentStatement,
+    ) -> OYResult<Option<ControlFlow>> {
+        let span = assign.expression.span();
+        let value = match self.execute_statement_expression(&assign.expression)? {
+            Ok(value) => value,
+            Err(control_flow) => return Ok(Some(control_flow)),
+        };
+        types::check(assign.type_annotation.as_ref(), value.type_name(), span)?;
+        self.environment.assign_variable(Variable {
+            ident: assign.ident.clone(),
+            value,
             span: assign.span,
+        })?;
+        Ok(None)
+    }
+
+    /// Executes the given field assignment statement.
+    /// The value is moved to the field of the record variable, the variable stays in its frame.
+    /// If the assigned value is a loop that returns from the function, the return control flow will be returned.
+    pub fn execute_field_assign(
+        &mut self,
+        assign: &FieldAssignmentStatement,
+    ) -> OYResult<Option<ControlFlow>> {
+        let value = match self.execute_statement_expression(&assign.expression)? {
+            Ok(value) => value,
+            Err(control_flow) => return Ok(Some(control_flow)),
+        };
+        let Some(variable) = self.environment.variable_mut(&assign.ident) else {
+            return Err(self
+                .environment
+                .undeclared_error(&assign.ident, assign.ident.span));
         };
-        self.environment.add_variable(assign)?;
-        Ok(())
+        records::assign(
+            &mut variable.value,
+            &assign.fields,
+            value,
+            assign.ident.span,
+        )?;
+        Ok(None)
+    }
+
+    /// Executes the expression of a statement, which can be a loop unlike the other expressions.
+    /// This will return the result of the expression, or `Err` with the control flow if the loop returns from the function.
+    pub fn execute_statement_expression(
+        &mut self,
+        expr: &ExpressionStatement,
+    ) -> OYResult<Result<Value, ControlFlow>> {
+        match expr {
+            ExpressionStatement::Loop(loop_expr) => match self.execute_loop(loop_expr)? {
+                ControlFlow::Break(value) => Ok(Ok(value)),
+                control_flow => Ok(Err(control_flow)),
+            },
+            expr => self.execute_expression(expr).map(Ok),
+        }
     }
 
     /// Executes the given expression.
     /// This will return the result of the expression.
-    pub fn execute_expression(&mut self, expr: ExpressionStatement) -> OYResult<ObjectExpression> {
+    pub fn execute_expression(&mut self, expr: &ExpressionStatement) -> OYResult<Value> {
         match expr {
             ExpressionStatement::FunctionCall(func_call) => self.execute_function_call(func_call),
             ExpressionStatement::Value(value) => self.execute_value(value),
+            ExpressionStatement::Loop(_) => unreachable!(
+                "The loop can only be a statement, an assigned value or a returned value"
+            ),
+            ExpressionStatement::Binary(binary) => self.execute_binary(binary),
+            ExpressionStatement::Unary(unary) => self.execute_unary(unary),
+            ExpressionStatement::Match(match_expr) => self.execute_match(match_expr),
+            ExpressionStatement::Record(record) => self.execute_record(record),
+            ExpressionStatement::Borrow(borrow) => self.execute_borrow(borrow),
+            ExpressionStatement::FieldAccess(field_access) => {
+                let span = field_access.value.span();
+                let value = self.execute_expression(&field_access.value)?;
+                let mut field = records::field(value, &field_access.field, span)?;
+                *field.span_mut() = field_access.span;
+                Ok(field)
+            }
+        }
+    }
+
+    /// Executes the given borrow expression, the borrowed value is copied and stays in the environment.
+    /// Borrowing a variant constructs it, like using its ident.
+    pub fn execute_borrow(&mut self, borrow: &BorrowExpression) -> OYResult<Value> {
+        if self.environment.variant(&borrow.ident).is_some() {
+            return self.execute_variant(&borrow.ident, &[], borrow.span);
         }
+        let mut value = match self.environment.borrow(&borrow.ident, borrow.span)? {
+            Local::Variable(variable) => variable.value,
+            Local::Function(function) => Value::Function(function, borrow.span),
+        };
+        *value.span_mut() = borrow.span;
+        Ok(value)
+    }
+
+    /// Executes the given record expression.
+    /// The values of the fields are executed in the written order, then the record is created with its record type.
+    pub fn execute_record(&mut self, record: &RecordExpression) -> OYResult<Value> {
+        if self.environment.get_record(&record.ident.ident).is_none() {
+            return Err(OYError::new(
+                ErrorKind::UnDeclaredIdent(record.ident.ident.clone()),
+                record.ident.span,
+            ));
+        }
+        let fields = record
+            .fields
+            .iter()
+            .map(|(field, value)| Ok((field.clone(), self.execute_expression(value)?)))
+            .collect::<OYResult<Vec<_>>>()?;
+        let record_type = self
+            .environment
+            .get_record(&record.ident.ident)
+            .expect("The record type is checked above");
+        records::create(record_type, record.ident.clone(), fields, record.span)
+    }
+
+    /// Executes the given match expression.
+    /// The value is executed once, then the arms are checked in order. The expression of the first arm whose
+    /// pattern matches the value and whose guard is truthy is executed in a new scope with the bound idents.
+    pub fn execute_match(&mut self, match_expr: &MatchExpression) -> OYResult<Value> {
+        let value_span = match_expr.value.span();
+        let value = self.execute_expression(&match_expr.value)?;
+        for arm in &match_expr.arms {
+            let Some(bindings) = patterns::bindings(&arm.pattern, value.clone()) else {
+                continue;
+            };
+            for binding in bindings {
+                self.environment.add_variable(binding)?;
+            }
+            let is_matched = match &arm.guard {
+                Some(guard) => self.execute_expression(guard)?.is_truthy(),
+                None => true,
+            };
+            if is_matched {
+                let result = self.execute_expression(&arm.expression);
+                self.environment.exit_scope(arm.span);
+                return result;
+            }
+            self.environment.exit_scope(arm.span);
+        }
+        Err(OYError::new(
+            ErrorKind::NoMatchingArm(value.to_string()),
+            value_span,
+        ))
+    }
+
+    /// Executes the given binary expression.
+    /// The left operand is executed first, then the right operand.
+    /// The right operand of `&&` and `||` is only executed if the left operand doesn't decide the result.
+    pub fn execute_binary(&mut self, binary: &BinaryExpression) -> OYResult<Value> {
+        let left = self.execute_operand(&binary.left)?;
+        match (binary.operator, left.is_truthy()) {
+            (BinaryOperator::And, false) => return Ok(Value::Bool(false, binary.span)),
+            (BinaryOperator::Or, true) => return Ok(Value::Bool(true, binary.span)),
+            _ => {}
+        }
+        let right = self.execute_operand(&binary.right)?;
+        operators::binary(binary.operator, left, right, binary.span)
+    }
+
+    /// Executes the given unary expression.
+    pub fn execute_unary(&mut self, unary: &UnaryExpression) -> OYResult<Value> {
+        let operand = self.execute_operand(&unary.operand)?;
+        operators::unary(unary.operator, operand, unary.span)
+    }
+
+    /// Executes the operand of an operator, the span of the result will be the span of the operand.
+    fn execute_operand(&mut self, operand: &ExpressionStatement) -> OYResult<Value> {
+        let span = operand.span();
+        let mut value = self.execute_expression(operand)?;
+        *value.span_mut() = span;
+        Ok(value)
     }
 
     /// Executes the given function call.
     /// This will return the result of the function call.
-    pub fn execute_function_call(
+    pub fn execute_function_call(&mut self, func_call: &FunctionCallExpression) -> OYResult<Value> {
+        let span = func_call.span;
+        match self.resolve_function_call(func_call)? {
+            Ok((function, args)) => {
+                if self.calls.len() >= self.max_call_depth {
+                    return Err(OYError::new(
+                        ErrorKind::StackOverflow(self.max_call_depth),
+                        span,
+                    ));
+                }
+                self.execute_function(function, args, Some(span))
+            }
+            Err(result) => Ok(result),
+        }
+    }
+
+    /// Resolves the called function of the given function call and executes its arguments in the current scope.
+    /// This will return the function with the argument of each parameter, `None` for the parameters that use their default value.
+    /// The variants and the builtin functions are executed here, and their result is returned as `Err`.
+    pub fn resolve_function_call(
         &mut self,
-        func_call: FunctionCallExpression,
-    ) -> OYResult<ObjectExpression> {
-        let function = match func_call.callable {
-            ValueExpression::Ident(ident) => {
-                let function = self.environment.take(&ident.ident, func_call.span)?;
-                match function {
-                    Statement::Assignment(assign) => match assign.expression {
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::Function(function),
-                        )) => function,
-                        _ => {
-                            return Err(OYError::new(
-                                ErrorKind::NotCallable(func_call.span.span()),
-                                assign.span,
-                            ));
-                        }
-                    },
-                    Statement::Function(function) => function,
-                    _ => {
+        func_call: &FunctionCallExpression,
+    ) -> OYResult<Result<ResolvedCall, Value>> {
+        let function = match func_call.callable.as_ref() {
+            ExpressionStatement::Value(ValueExpression::Ident(ident))
+                if self.environment.variant(ident).is_some() =>
+            {
+                return self
+                    .execute_variant(ident, &func_call.args, func_call.span)
+                    .map(Err);
+            }
+            ExpressionStatement::Value(ValueExpression::Ident(ident)) => {
+                match self.environment.take(ident, func_call.span)? {
+                    Local::Variable(Variable {
+                        value: Value::Function(function, _),
+                        ..
+                    }) => function,
+                    Local::Variable(variable) => {
                         return Err(OYError::new(
                             ErrorKind::NotCallable(func_call.span.span()),
-                            function.span(),
+                            variable.span,
                         ));
                     }
+                    Local::Function(function) => function,
                 }
             }
-            ValueExpression::Object(ObjectExpression::Function(anonymous_function)) => {
-                anonymous_function
-            }
-            _ => unreachable!(
-                "The function call can only be an anonymous function or a function identifier"
-            ),
+            ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Function(
+                anonymous_function,
+            ))) => closures::capture(&mut self.environment, Arc::new(anonymous_function.clone())),
+            callable => match self.execute_expression(callable)? {
+                Value::Function(function, _) => function,
+                _ => {
+                    return Err(OYError::new(
+                        ErrorKind::NotCallable(func_call.span.span()),
+                        callable.span(),
+                    ));
+                }
+            },
         };
 
-        let args = if func_call.args.iter().any(|arg| arg.is_unpack) {
-            utils::unpack_args(self, func_call.args)?
+        let args = utils::unpack_args(self, &func_call.args)?;
+        let args = utils::bind_args(self, &function.statement, args, func_call.span)?;
+        if function.is_builtin() {
+            let ident = &function.ident.as_ref().unwrap().ident;
+            // The `clone` builtin borrows its argument, so the cloned variable is not moved.
+            let borrows = ident == "clone";
+            let args = args
+                .into_iter()
+                // The builtin functions have no default values, so all their params have arguments.
+                .flatten()
+                .map(|arg| {
+                    let span = arg.span();
+                    let mut value = match arg {
+                        CallArg::Arg(Arg {
+                            expr: ExpressionStatement::Value(ValueExpression::Ident(ident)),
+                            ..
+                        }) if borrows => self.execute_borrow(&BorrowExpression {
+                            ident: ident.clone(),
+                            span: ident.span,
+                        })?,
+                        CallArg::Arg(arg) => self.execute_expression(&arg.expr)?,
+                        CallArg::Value(value, _) => value,
+                    };
+                    *value.span_mut() = span;
+                    Ok(value)
+                })
+                .collect::<OYResult<Vec<Value>>>()?;
+            self.environment
+                .builtins()
+                .execute_builtin_funtion(ident, func_call.span, args)
+                .map(Err)
         } else {
-            func_call.args
-        };
-        let args = if args.len() >= function.params.len()
-            && function.params.last().map(|p| p.is_pack).unwrap_or(false)
-        {
-            utils::pack_args(self, &function, args)?
-        } else {
-            args
-        };
-        if function.params.len() != args.len() {
-            return Err(OYError::new(
-                ErrorKind::UncorrectArguments(
-                    args.len(),
-                    function
-                        .ident
-                        .clone()
-                        .map_or_else(|| function.span.span(), |ident| ident.span.span()),
-                    function.params,
-                    function
-                        .ident
-                        .map_or_else(|| "Anonymous function".to_owned(), |ident| ident.ident),
-                ),
-                func_call.span,
-            ));
-        }
-        if function.block.is_none() {
-            Builtins::execute_builtin_funtion(
-                &function.ident.unwrap().ident,
-                func_call.span,
-                args.into_iter()
-                    .map(|arg| {
-                        let arg_span = arg.span;
-                        let mut expr = self.execute_expression(arg.expr)?;
-                        *expr.span_mut() = arg_span;
-                        Ok(expr)
+            // The arguments are executed in the caller scope, except the idents, which are moved to the function scope
+            // after the other arguments, so the other arguments can borrow them. The variants are constructed here.
+            let args = args
+                .into_iter()
+                .map(|arg| {
+                    arg.map(|arg| match arg {
+                        CallArg::Arg(Arg {
+                            expr: ExpressionStatement::Value(ValueExpression::Ident(ident)),
+                            span,
+                            ..
+                        }) if self.environment.variant(ident).is_none() => Ok(Err((ident, *span))),
+                        CallArg::Arg(arg) => Ok(Ok(Argument::Value(
+                            self.execute_expression(&arg.expr)?,
+                            arg.span,
+                        ))),
+                        CallArg::Value(value, span) => Ok(Ok(Argument::Value(value, span))),
                     })
-                    .collect::<OYResult<Vec<ObjectExpression>>>()?,
-            )
-        } else {
-            self.execute_function(function, args)
+                    .transpose()
+                })
+                .collect::<OYResult<Vec<_>>>()?;
+            let args = args
+                .into_iter()
+                .map(|arg| {
+                    arg.map(|arg| match arg {
+                        Ok(arg) => Ok(arg),
+                        Err((ident, span)) => Ok(match self.environment.take(ident, span)? {
+                            Local::Variable(variable) => Argument::Value(variable.value, span),
+                            Local::Function(function) => Argument::Function(function, span),
+                        }),
+                    })
+                    .transpose()
+                })
+                .collect::<OYResult<Vec<_>>>()?;
+            Ok(Ok((function, args)))
         }
     }
 
+    /// Executes the construction of the given variant, a variant without fields is constructed without arguments.
+    /// The arguments are executed in order, and their number must be the number of the variant fields.
+    pub fn execute_variant(&mut self, ident: &Ident, args: &[Arg], span: Span) -> OYResult<Value> {
+        let args = utils::unpack_args(self, args)?;
+        let (enum_stmt, variant) = self
+            .environment
+            .variant(ident)
+            .expect("The variant is checked by the caller");
+        if variant.fields.len() != args.len() {
+            return Err(variant_arguments_error(variant, args.len(), span));
+        }
+        let enum_ident = enum_stmt.ident.clone();
+        let values = args
+            .into_iter()
+            .map(|arg| match arg {
+                CallArg::Arg(arg) => self.execute_operand(&arg.expr),
+                CallArg::Value(value, _) => Ok(value),
+            })
+            .collect::<OYResult<Vec<_>>>()?;
+        Ok(Value::Variant(
+            Arc::new(VariantValue {
+                enum_ident,
+                ident: ident.clone(),
+                values,
+            }),
+            span,
+        ))
+    }
+
     /// Executes the given value.
     /// This will return the result of the value.
-    pub fn execute_value(&mut self, value: ValueExpression) -> OYResult<ObjectExpression> {
+    pub fn execute_value(&mut self, value: &ValueExpression) -> OYResult<Value> {
         match value {
             ValueExpression::Object(ObjectExpression::Array(arr, _)) => {
                 let mut result = Vec::new();
                 for expr in arr {
-                    result.push(ExpressionStatement::Value(ValueExpression::Object(
-                        self.execute_expression(expr)?,
-                    )));
+                    result.push(self.execute_expression(expr)?);
                 }
-                Ok(ObjectExpression::Array(result, Span::new(0, 0)))
-            }
-            ValueExpression::Object(obj) => Ok(obj),
-            ValueExpression::Ident(ident) => {
-                match self.environment.take(&ident.ident, ident.span)? {
-                    Statement::Assignment(assign) => self.execute_expression(assign.expression),
-                    Statement::Function(function) => Ok(ObjectExpression::Function(function)),
-                    _ => unreachable!(),
+                Ok(Value::Array(Arc::new(result), Span::new(0, 0)))
+            }
+            ValueExpression::Object(ObjectExpression::Map(map, span)) => {
+                let mut result = Vec::new();
+                for (key, value) in map {
+                    let key = self.execute_operand(key)?;
+                    let value = self.execute_expression(value)?;
+                    maps::insert(&mut result, key, value)?;
                 }
+                Ok(Value::Map(Arc::new(result), *span))
+            }
+            ValueExpression::Object(ObjectExpression::Function(function)) => Ok(Value::Function(
+                closures::capture(&mut self.environment, Arc::new(function.clone())),
+                function.span,
+            )),
+            ValueExpression::Object(object) => Ok(Value::literal(object)),
+            ValueExpression::Ident(ident) if self.environment.variant(ident).is_some() => {
+                self.execute_variant(ident, &[], ident.span)
             }
+            ValueExpression::Ident(ident) => match self.environment.take(ident, ident.span)? {
+                Local::Variable(variable) => Ok(variable.value),
+                Local::Function(function) => {
+                    let span = function.statement.span;
+                    Ok(Value::Function(function, span))
+                }
+            },
         }
     }
 }
+
+/// Returns the call site of a call of the given function, for the traceback of the runtime errors.
+/// The span is the span of the call, `None` for the call of the main function.
+pub(crate) fn call_site(function: &FunctionValue, span: Option<Span>) -> CallSite {
+    CallSite {
+        name: function.ident.as_ref().map(|ident| ident.ident.clone()),
+        function_span: function.statement.span.span().into(),
+        span: span.map(|span| span.span().into()),
+    }
+}
+
+/// Returns the items of the iterable of a for loop, which are the elements of the array, or the characters of the string.
+/// The span is the span of the iterable.
+pub(crate) fn items(iterable: Value, span: Span) -> OYResult<Vec<Value>> {
+    match iterable {
+        Value::Array(array, _) => Ok(Arc::unwrap_or_clone(array)),
+        Value::String(string, _) => Ok(string
+            .chars()
+            .map(|c| Value::String(c.to_string().into(), span))
+            .collect()),
+        value => Err(OYError::new(
+            ErrorKind::UnexpectedType("Array or String".to_owned(), value.type_name().to_owned()),
+            span,
+        )),
+    }
+}
+
+/// Returns the error of constructing the variant with a wrong number of arguments, the span is the span of the construction.
+pub(crate) fn variant_arguments_error(variant: &Variant, args: usize, span: Span) -> OYError {
+    OYError::new(
+        ErrorKind::UncorrectArguments(
+            args,
+            variant.ident.span.span(),
+            variant
+                .fields
+                .iter()
+                .map(|field| Param {
+                    ident: field.clone(),
+                    is_pack: false,
+                    default: None,
+                    type_annotation: None,
+                })
+                .collect(),
+            variant.ident.ident.clone(),
+        ),
+        span,
+    )
+}
+
+impl ArgExecutor for Interpreter {
+    fn execute_arg(&mut self, arg: &Arg) -> OYResult<Value> {
+        self.execute_expression(&arg.expr)
+    }
+}
+
+/// Runs the main function of the program with `execute`, which is given the arguments of `argc` and `argv`.
+/// This will return the exit code of the program, which is the result of the main function if it's an integer, otherwise 0.
+///
+/// The program is executed in a thread with a stack for the maximum call depth, so a deep recursion
+/// is a stack overflow error instead of overflowing the stack of the caller thread.
+pub(crate) fn run_main(
+    max_call_depth: usize,
+    argc: usize,
+    argv: Vec<String>,
+    execute: impl FnOnce(Vec<Option<Argument>>) -> OYResult<Value> + Send,
+) -> OYResult<u8> {
+    let args = vec![
+        Argument::Value(
+            Value::Int(argc.to_string().parse().unwrap(), Span::new(0, 0)),
+            Span::new(0, 0),
+        ),
+        Argument::Value(
+            Value::Array(
+                Arc::new(
+                    argv.into_iter()
+                        .map(|v| Value::String(v.into(), Span::new(0, 0)))
+                        .collect(),
+                ),
+                Span::new(0, 0),
+            ),
+            Span::new(0, 0),
+        ),
+    ];
+    let stack_size = max_call_depth
+        .checked_add(1)
+        .and_then(|calls| calls.checked_mul(STACK_SIZE_PER_CALL))
+        .map_or(MAX_STACK_SIZE, |stack_size| stack_size.min(MAX_STACK_SIZE));
+    let result = std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .stack_size(stack_size)
+            .spawn_scoped(scope, || execute(args.into_iter().map(Some).collect()))
+            .map_err(|err| {
+                OYError::new(
+                    ErrorKind::StackUnavailable(max_call_depth, stack_size, err.to_string()),
+                    (0, 0),
+                )
+            })?
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    });
+    match result? {
+        Value::Int(int, span) => int
+            .to_u8()
+            .ok_or_else(|| OYError::new(ErrorKind::InvalidExitCode(int), span)),
+        _ => Ok(0),
+    }
+}
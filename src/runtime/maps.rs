@@ -0,0 +1,37 @@
+use super::value::Value;
+use crate::errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult};
+
+/// The entries of a map value.
+pub type MapEntries = Vec<(Value, Value)>;
+
+/// Checks that the given value can be a map key, the map keys are strings and integers.
+pub fn check_key(key: &Value) -> OYResult<()> {
+    match key {
+        Value::String(_, _) | Value::Int(_, _) => Ok(()),
+        _ => Err(OYError::new(
+            OYErrorKind::UnexpectedType("String or Int".to_owned(), key.type_name().to_owned()),
+            key.span(),
+        )),
+    }
+}
+
+/// Returns the index of the entry with the given key.
+pub fn position(entries: &MapEntries, key: &Value) -> Option<usize> {
+    entries
+        .iter()
+        .position(|(entry_key, _)| match (entry_key, key) {
+            (Value::String(left, _), Value::String(right, _)) => left == right,
+            (Value::Int(left, _), Value::Int(right, _)) => left == right,
+            _ => false,
+        })
+}
+
+/// Inserts the given entry to the map, if the key already exists its value will be replaced.
+pub fn insert(entries: &mut MapEntries, key: Value, value: Value) -> OYResult<()> {
+    check_key(&key)?;
+    match position(entries, &key) {
+        Some(index) => entries[index].1 = value,
+        None => entries.push((key, value)),
+    }
+    Ok(())
+}
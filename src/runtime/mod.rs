@@ -1,3 +1,13 @@
 pub mod builtins;
+pub mod bytecode;
+pub mod closures;
 pub mod environment;
 pub mod interpreter;
+pub mod maps;
+pub mod operators;
+pub mod patterns;
+pub mod records;
+pub mod symbols;
+pub mod types;
+pub mod value;
+pub mod vm;
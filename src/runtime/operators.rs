@@ -0,0 +1,175 @@
+use bigdecimal::{BigDecimal, Zero};
+
+use super::{maps, value::Value};
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult},
+};
+
+/// Applies the binary operator to the given operands.
+///
+/// The arithmetic rules are:
+/// - If the two operands are integers, the result is an integer, and the division is truncated toward zero.
+/// - If one of the operands is a float, the result is a float.
+/// - Dividing by zero (or the modulo of zero) is an error.
+///
+/// The comparison rules are:
+/// - `==` and `!=` accept any operands, the operands of different types are not equal (except an `int` and a `float`).
+/// - `<`, `<=`, `>` and `>=` accept two numbers or two strings.
+///
+/// The logical operators `&&` and `||` are short-circuited by the interpreter,
+/// here both operands are already evaluated.
+pub fn binary(operator: BinaryOperator, left: Value, right: Value, span: Span) -> OYResult<Value> {
+    match operator {
+        BinaryOperator::Eq => return Ok(Value::Bool(equals(&left, &right), span)),
+        BinaryOperator::Ne => return Ok(Value::Bool(!equals(&left, &right), span)),
+        BinaryOperator::Lt | BinaryOperator::Le | BinaryOperator::Gt | BinaryOperator::Ge => {
+            return compare(operator, left, right, span)
+        }
+        BinaryOperator::And => return Ok(Value::Bool(left.is_truthy() && right.is_truthy(), span)),
+        BinaryOperator::Or => return Ok(Value::Bool(left.is_truthy() || right.is_truthy(), span)),
+        _ => {}
+    }
+    let (left_number, right_number, is_float) = match (&left, &right) {
+        (Value::Int(left, _), Value::Int(right, _)) => (left, right, false),
+        (
+            Value::Int(left, _) | Value::Float(left, _),
+            Value::Int(right, _) | Value::Float(right, _),
+        ) => (left, right, true),
+        _ => {
+            return Err(OYError::new(
+                OYErrorKind::InvalidOperands(
+                    operator.to_string(),
+                    left.type_name().to_owned(),
+                    right.type_name().to_owned(),
+                ),
+                span,
+            ))
+        }
+    };
+    if matches!(operator, BinaryOperator::Div | BinaryOperator::Mod) && right_number.is_zero() {
+        return Err(OYError::new(OYErrorKind::DivisionByZero, right.span()));
+    }
+    let result = match operator {
+        BinaryOperator::Add => left_number + right_number,
+        BinaryOperator::Sub => left_number - right_number,
+        BinaryOperator::Mul => left_number * right_number,
+        BinaryOperator::Div if is_float => left_number / right_number,
+        BinaryOperator::Div => (left_number / right_number).with_scale(0),
+        BinaryOperator::Mod => left_number % right_number,
+        _ => unreachable!("The comparison and logical operators are handled above"),
+    };
+    Ok(number(result, is_float, span))
+}
+
+/// Applies the unary operator to the given operand.
+pub fn unary(operator: UnaryOperator, operand: Value, span: Span) -> OYResult<Value> {
+    match (operator, operand) {
+        (UnaryOperator::Neg, Value::Int(number, _)) => Ok(Value::Int(-number, span)),
+        (UnaryOperator::Neg, Value::Float(number, _)) => Ok(Value::Float(-number, span)),
+        (UnaryOperator::Not, operand) => Ok(Value::Bool(!operand.is_truthy(), span)),
+        (UnaryOperator::Neg, operand) => Err(OYError::new(
+            OYErrorKind::UnexpectedType("Int or Float".to_owned(), operand.type_name().to_owned()),
+            operand.span(),
+        )),
+    }
+}
+
+/// Returns whether the two values are equal, the arrays are compared element by element,
+/// and the maps are equal if they have the same entries in any order.
+/// The records are equal if they have the same record type and their fields are equal.
+/// The variants are equal if they are the same variant of the same enum type and their values are equal.
+/// The functions are equal if they have the same name, the anonymous functions are never equal.
+pub fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (
+            Value::Int(left, _) | Value::Float(left, _),
+            Value::Int(right, _) | Value::Float(right, _),
+        ) => left == right,
+        (Value::String(left, _), Value::String(right, _)) => left == right,
+        (Value::Bool(left, _), Value::Bool(right, _)) => left == right,
+        (Value::Nil(_), Value::Nil(_)) => true,
+        (Value::Array(left, _), Value::Array(right, _)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| equals(left, right))
+        }
+        (Value::Map(left, _), Value::Map(right, _)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, left)| {
+                    maps::position(right, key).is_some_and(|index| equals(left, &right[index].1))
+                })
+        }
+        (Value::Record(left, _), Value::Record(right, _)) => {
+            left.ident.ident == right.ident.ident
+                && left.fields.len() == right.fields.len()
+                && left.fields.iter().zip(&right.fields).all(
+                    |((left_field, left), (right_field, right))| {
+                        left_field.ident == right_field.ident && equals(left, right)
+                    },
+                )
+        }
+        (Value::Variant(left, _), Value::Variant(right, _)) => {
+            left.enum_ident.ident == right.enum_ident.ident
+                && left.ident.ident == right.ident.ident
+                && left.values.len() == right.values.len()
+                && left
+                    .values
+                    .iter()
+                    .zip(&right.values)
+                    .all(|(left, right)| equals(left, right))
+        }
+        (Value::Function(left, _), Value::Function(right, _)) => {
+            match (&left.ident, &right.ident) {
+                (Some(left), Some(right)) => left.ident == right.ident,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Compares the two values with the given comparison operator,
+/// the operands must be two numbers or two strings.
+fn compare(operator: BinaryOperator, left: Value, right: Value, span: Span) -> OYResult<Value> {
+    let ordering = match (&left, &right) {
+        (
+            Value::Int(left, _) | Value::Float(left, _),
+            Value::Int(right, _) | Value::Float(right, _),
+        ) => left.cmp(right),
+        (Value::String(left, _), Value::String(right, _)) => left.cmp(right),
+        _ => {
+            return Err(OYError::new(
+                OYErrorKind::InvalidOperands(
+                    operator.to_string(),
+                    left.type_name().to_owned(),
+                    right.type_name().to_owned(),
+                ),
+                span,
+            ))
+        }
+    };
+    let result = match operator {
+        BinaryOperator::Lt => ordering.is_lt(),
+        BinaryOperator::Le => ordering.is_le(),
+        BinaryOperator::Gt => ordering.is_gt(),
+        BinaryOperator::Ge => ordering.is_ge(),
+        _ => unreachable!("This function only compare with the comparison operators"),
+    };
+    Ok(Value::Bool(result, span))
+}
+
+/// Creates a number value. The float always has a fractional part, so `6.0 / 2.0` is `3.0` not `3`.
+fn number(number: BigDecimal, is_float: bool, span: Span) -> Value {
+    if is_float {
+        if number.as_bigint_and_exponent().1 < 1 {
+            Value::Float(number.with_scale(1), span)
+        } else {
+            Value::Float(number, span)
+        }
+    } else {
+        Value::Int(number, span)
+    }
+}
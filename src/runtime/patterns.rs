@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use super::{environment::Variable, operators, value::Value};
+use crate::ast::*;
+
+/// Matches the value with the pattern, and returns the bound idents of the pattern as variables,
+/// or `None` if the value doesn't match the pattern.
+pub fn bindings(pattern: &Pattern, value: Value) -> Option<Vec<Variable>> {
+    let mut bindings = Vec::new();
+    bind(pattern, value, &mut bindings).then_some(bindings)
+}
+
+/// Matches the value with the pattern, the bound idents are added to the given bindings.
+fn bind(pattern: &Pattern, value: Value, bindings: &mut Vec<Variable>) -> bool {
+    match (pattern, value) {
+        (Pattern::Wildcard(_), _) => true,
+        (Pattern::Ident(ident), value) => {
+            bindings.push(Variable {
+                ident: ident.clone(),
+                value,
+                span: ident.span,
+            });
+            true
+        }
+        (Pattern::Literal(literal), value) => operators::equals(&Value::literal(literal), &value),
+        (Pattern::Array(patterns, rest, _), Value::Array(elements, span)) => {
+            if elements.len() < patterns.len()
+                || (rest.is_none() && elements.len() != patterns.len())
+            {
+                return false;
+            }
+            let mut elements = Arc::unwrap_or_clone(elements);
+            let rest_elements = elements.split_off(patterns.len());
+            patterns
+                .iter()
+                .zip(elements)
+                .all(|(pattern, element)| bind(pattern, element, bindings))
+                && rest.as_ref().is_none_or(|rest| {
+                    bind(rest, Value::Array(Arc::new(rest_elements), span), bindings)
+                })
+        }
+        (Pattern::Array(..), _) => false,
+        (Pattern::Variant(ident, patterns, _), Value::Variant(variant, _)) => {
+            variant.ident.ident == ident.ident
+                && patterns.len() == variant.values.len()
+                && patterns
+                    .iter()
+                    .zip(&variant.values)
+                    .all(|(pattern, value)| bind(pattern, value.clone(), bindings))
+        }
+        (Pattern::Variant(..), _) => false,
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use super::value::{RecordValue, Value};
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult},
+};
+
+/// Creates a record of the given record type, the fields are ordered by their declaration.
+/// The fields must be the fields of the record type, and all of them must be given.
+pub fn create(
+    record: &RecordStatement,
+    ident: Ident,
+    fields: Vec<(Ident, Value)>,
+    span: Span,
+) -> OYResult<Value> {
+    if let Some((field, _)) = fields
+        .iter()
+        .find(|(field, _)| !record.fields.iter().any(|f| f.ident == field.ident))
+    {
+        return Err(unknown_field(&ident.ident, field, &record.fields));
+    }
+    let missing_fields = record
+        .fields
+        .iter()
+        .filter(|f| !fields.iter().any(|(field, _)| field.ident == f.ident))
+        .map(|f| f.ident.clone())
+        .collect::<Vec<_>>();
+    if !missing_fields.is_empty() {
+        return Err(OYError::new(
+            OYErrorKind::MissingFields(ident.ident, missing_fields),
+            span,
+        ));
+    }
+    let mut fields = fields;
+    fields.sort_by_key(|(field, _)| record.fields.iter().position(|f| f.ident == field.ident));
+    Ok(Value::Record(Arc::new(RecordValue { ident, fields }), span))
+}
+
+/// Returns the value of the field, the record is moved so the other fields are dropped.
+/// The span is the span of the record, for the error if the value is not a record.
+pub fn field(value: Value, field: &Ident, span: Span) -> OYResult<Value> {
+    match value {
+        Value::Record(record, _) => match position(&record, field) {
+            Some(index) => Ok(Arc::unwrap_or_clone(record).fields.swap_remove(index).1),
+            None => Err(unknown_field(
+                &record.ident.ident,
+                field,
+                record_fields(&record),
+            )),
+        },
+        value => Err(not_record(&value, span)),
+    }
+}
+
+/// Returns a mutable reference to the value of the field, to update it.
+/// The record is copied first if it's shared with other values.
+/// The span is the span of the record, for the error if the value is not a record.
+pub fn field_mut<'a>(value: &'a mut Value, field: &Ident, span: Span) -> OYResult<&'a mut Value> {
+    match value {
+        Value::Record(record, _) => match position(record, field) {
+            Some(index) => Ok(&mut Arc::make_mut(record).fields[index].1),
+            None => Err(unknown_field(
+                &record.ident.ident,
+                field,
+                record_fields(record),
+            )),
+        },
+        value => Err(not_record(value, span)),
+    }
+}
+
+/// Assigns the value to the field at the end of the given fields path, e.g. `x` of `point.x` or of `line.start.x`.
+/// The span is the span of the record, for the error if the value is not a record.
+pub fn assign(record: &mut Value, fields: &[Ident], value: Value, span: Span) -> OYResult<()> {
+    let mut field_value = record;
+    let mut record_span = span;
+    for field in fields {
+        field_value = field_mut(field_value, field, record_span)?;
+        record_span.end = field.span.end;
+    }
+    *field_value = value;
+    Ok(())
+}
+
+/// Returns the index of the field in the fields of the record.
+fn position(record: &RecordValue, field: &Ident) -> Option<usize> {
+    record
+        .fields
+        .iter()
+        .position(|(f, _)| f.ident == field.ident)
+}
+
+/// Returns the names of the fields of the record.
+fn record_fields(record: &RecordValue) -> impl Iterator<Item = &Ident> {
+    record.fields.iter().map(|(field, _)| field)
+}
+
+fn unknown_field<'a>(
+    record: &str,
+    field: &Ident,
+    fields: impl IntoIterator<Item = &'a Ident>,
+) -> OYError {
+    OYError::new(
+        OYErrorKind::UnknownField(
+            record.to_owned(),
+            field.ident.clone(),
+            fields.into_iter().map(|f| f.ident.clone()).collect(),
+        ),
+        field.span,
+    )
+}
+
+fn not_record(value: &Value, span: Span) -> OYError {
+    OYError::new(
+        OYErrorKind::UnexpectedType("Record".to_owned(), value.type_name().to_owned()),
+        span,
+    )
+}
@@ -0,0 +1,94 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+    sync::Arc,
+};
+
+use crate::ast::Span;
+
+/// A map keyed by symbols.
+pub type SymbolMap<V> = HashMap<Symbol, V, BuildHasherDefault<IdentHasher>>;
+
+/// A map keyed by spans, the resolved idents and the compiled functions are found by their spans.
+pub type SpanMap<V> = HashMap<Span, V, BuildHasherDefault<IdentHasher>>;
+
+/// An interned identifier, two symbols are equal if their identifiers are equal.
+/// The symbols are cheap to copy, compare and hash, so the scopes of the environment are keyed by them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+/// The interner of the identifiers, it gives each identifier a unique symbol.
+#[derive(Debug, Clone, Default)]
+pub struct Symbols {
+    /// The symbols of the interned identifiers.
+    symbols: HashMap<Arc<str>, Symbol, BuildHasherDefault<IdentHasher>>,
+    /// The interned identifiers, indexed by their symbols.
+    names: Vec<Arc<str>>,
+}
+
+impl Symbols {
+    /// Returns the symbol of the given identifier, the identifier is interned if it's new.
+    pub fn intern(&mut self, ident: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(ident) {
+            return *symbol;
+        }
+        let symbol = Symbol(self.names.len() as u32);
+        let ident: Arc<str> = ident.into();
+        self.names.push(Arc::clone(&ident));
+        self.symbols.insert(ident, symbol);
+        symbol
+    }
+
+    /// Returns the symbol of the given identifier, `None` if it's not interned.
+    /// An identifier that is not interned is not declared anywhere.
+    pub fn get(&self, ident: &str) -> Option<Symbol> {
+        self.symbols.get(ident).copied()
+    }
+
+    /// Returns the identifier of the given symbol.
+    pub fn name(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.0 as usize]
+    }
+}
+
+/// A fast hasher of the identifiers, the symbols and the spans (the FxHash algorithm of rustc).
+/// The default hasher resists hash flooding, which is not needed for the identifiers of a program.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentHasher {
+    hash: u64,
+}
+
+impl IdentHasher {
+    /// Adds a word to the hash.
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for IdentHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for byte in chunks.remainder() {
+            self.add(u64::from(*byte));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(u64::from(i));
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
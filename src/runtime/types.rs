@@ -0,0 +1,44 @@
+use super::value::Value;
+use crate::{
+    ast::*,
+    errors::{Error as OYError, ErrorKind as OYErrorKind, Result as OYResult, SpanError},
+};
+
+/// Checks that the type of the value is one of the annotated types, a value without annotation can be of any type.
+/// The checker reports the mismatched types that it knows before running, the values that it can't know their types
+/// are checked with this function when they are passed to a function, returned from it or assigned to a variable.
+pub fn check(
+    type_annotation: Option<&TypeAnnotation>,
+    type_name: &str,
+    span: impl SpanError,
+) -> OYResult<()> {
+    match type_annotation {
+        Some(type_annotation) if !type_annotation.accepts(type_name) => Err(OYError::new(
+            OYErrorKind::MismatchedType(
+                type_annotation.to_string(),
+                type_name.to_owned(),
+                Some(type_annotation.span.span()),
+            ),
+            span,
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the value of the given param, the value of a pack param is an array of the packed arguments,
+/// and each one of them must be of the annotated type.
+pub fn check_param(param: &Param, value: &Value, span: Span) -> OYResult<()> {
+    match value {
+        Value::Array(values, _) if param.is_pack => {
+            for value in values.iter() {
+                check(
+                    param.type_annotation.as_ref(),
+                    value.type_name(),
+                    value.span(),
+                )?;
+            }
+            Ok(())
+        }
+        value => check(param.type_annotation.as_ref(), value.type_name(), span),
+    }
+}
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, Zero};
+
+use super::environment::Local;
+use crate::ast::*;
+
+/// A runtime value, the result of executing an expression.
+///
+/// The strings, arrays, maps, records, variants and functions are shared with reference counting,
+/// so moving or copying a value doesn't copy its content. Updating a shared value (e.g. pushing to an array
+/// or assigning a field) copies its content first, so the other owners of the value are not affected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A function.
+    Function(Arc<FunctionValue>, Span),
+    /// A string.
+    String(Arc<str>, Span),
+    /// A integer number.
+    Int(BigDecimal, Span),
+    /// A float number.
+    Float(BigDecimal, Span),
+    /// A boolean.
+    Bool(bool, Span),
+    /// A array.
+    Array(Arc<Vec<Value>>, Span),
+    /// A map of keys to values, the entries are in the insertion order.
+    Map(Arc<Vec<(Value, Value)>>, Span),
+    /// A record.
+    Record(Arc<RecordValue>, Span),
+    /// A variant of a enum.
+    Variant(Arc<VariantValue>, Span),
+    /// A nil.
+    Nil(Span),
+}
+
+/// A function value, a global function, a local function or an anonymous function with its captures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionValue {
+    /// The name of the function, `None` for an anonymous function.
+    /// A function that is passed as an argument is named by its param.
+    pub ident: Option<Ident>,
+    /// The declaration of the function, which is shared by all the values of the function.
+    pub statement: Arc<FunctionStatement>,
+    /// The variables and the local functions that are captured by an anonymous function when it's created,
+    /// with their slots in the function.
+    pub captures: Vec<(usize, Local)>,
+}
+
+/// A record value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordValue {
+    /// The name of the record type.
+    pub ident: Ident,
+    /// The fields with their values, in the declaration order.
+    pub fields: Vec<(Ident, Value)>,
+}
+
+/// A variant value of a enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantValue {
+    /// The name of the enum type.
+    pub enum_ident: Ident,
+    /// The name of the variant.
+    pub ident: Ident,
+    /// The payload values.
+    pub values: Vec<Value>,
+}
+
+impl FunctionValue {
+    /// Creates the value of a named function, without captures.
+    pub fn new(statement: FunctionStatement) -> Self {
+        Self {
+            ident: statement.ident.clone(),
+            statement: Arc::new(statement),
+            captures: Vec::new(),
+        }
+    }
+
+    /// Returns whether the function is a builtin function, which has no block.
+    pub fn is_builtin(&self) -> bool {
+        self.statement.block.is_none()
+    }
+}
+
+impl Value {
+    /// Creates the value of the given literal object, which is a string, a number, a boolean or nil.
+    /// The arrays, maps and functions are created by the interpreter, because they contain expressions.
+    pub fn literal(object: &ObjectExpression) -> Self {
+        match object {
+            ObjectExpression::String(string, span) => Value::String(string.as_str().into(), *span),
+            ObjectExpression::Int(int, span) => Value::Int(int.clone(), *span),
+            ObjectExpression::Float(float, span) => Value::Float(float.clone(), *span),
+            ObjectExpression::Bool(boolean, span) => Value::Bool(*boolean, *span),
+            ObjectExpression::Nil(span) => Value::Nil(*span),
+            ObjectExpression::Function(_)
+            | ObjectExpression::Array(_, _)
+            | ObjectExpression::Map(_, _) => {
+                unreachable!("The interpreter executes the arrays, maps and functions")
+            }
+        }
+    }
+
+    /// Returns the name of the type of the value, which is the name of the record (or enum) type for the records (and variants).
+    pub fn type_name(&self) -> &str {
+        match self {
+            Value::Function(_, _) => "function",
+            Value::String(_, _) => "string",
+            Value::Int(_, _) => "int",
+            Value::Float(_, _) => "float",
+            Value::Bool(_, _) => "bool",
+            Value::Array(_, _) => "array",
+            Value::Map(_, _) => "map",
+            Value::Record(record, _) => &record.ident.ident,
+            Value::Variant(variant, _) => &variant.enum_ident.ident,
+            Value::Nil(_) => "nil",
+        }
+    }
+
+    /// Returns whether the value is truthy.
+    /// The falsy values are `false`, `nil`, zero numbers, the empty string, the empty array and the empty map,
+    /// everything else (including functions, records and variants) is truthy.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Function(_, _) => true,
+            Value::String(string, _) => !string.is_empty(),
+            Value::Int(number, _) | Value::Float(number, _) => !number.is_zero(),
+            Value::Bool(boolean, _) => *boolean,
+            Value::Array(array, _) => !array.is_empty(),
+            Value::Map(map, _) => !map.is_empty(),
+            Value::Record(_, _) | Value::Variant(_, _) => true,
+            Value::Nil(_) => false,
+        }
+    }
+
+    /// Returns the span of the value, which is the span of the expression that created (or used) it.
+    pub fn span(&self) -> Span {
+        match self {
+            Value::Function(_, span)
+            | Value::String(_, span)
+            | Value::Int(_, span)
+            | Value::Float(_, span)
+            | Value::Bool(_, span)
+            | Value::Array(_, span)
+            | Value::Map(_, span)
+            | Value::Record(_, span)
+            | Value::Variant(_, span)
+            | Value::Nil(span) => *span,
+        }
+    }
+
+    /// Returns a mutable reference to the span of the value.
+    pub fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Value::Function(_, span)
+            | Value::String(_, span)
+            | Value::Int(_, span)
+            | Value::Float(_, span)
+            | Value::Bool(_, span)
+            | Value::Array(_, span)
+            | Value::Map(_, span)
+            | Value::Record(_, span)
+            | Value::Variant(_, span)
+            | Value::Nil(span) => span,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// Writes the values separated by commas.
+        fn list<T>(
+            f: &mut std::fmt::Formatter<'_>,
+            items: impl IntoIterator<Item = T>,
+            item: impl Fn(&mut std::fmt::Formatter<'_>, T) -> std::fmt::Result,
+        ) -> std::fmt::Result {
+            for (index, value) in items.into_iter().enumerate() {
+                if index != 0 {
+                    write!(f, ", ")?;
+                }
+                item(f, value)?;
+            }
+            Ok(())
+        }
+
+        /// Writes the value inside another value, the strings are quoted so they are not shown like the other values,
+        /// e.g. `["1"]` and `[1]`.
+        fn nested(f: &mut std::fmt::Formatter<'_>, value: &Value) -> std::fmt::Result {
+            match value {
+                Value::String(string, _) => write!(f, "{}", quote(string)),
+                value => write!(f, "{value}"),
+            }
+        }
+
+        match self {
+            Value::Function(function, _) => {
+                if function.is_builtin() {
+                    write!(f, "<builtin-function> ")?;
+                }
+                match &function.ident {
+                    Some(ident) => write!(f, "{}", ident.ident)?,
+                    None => write!(f, "Anonymous Function")?,
+                }
+                for param in &function.statement.params {
+                    write!(f, "<{}>", param.ident.ident)?;
+                }
+                Ok(())
+            }
+            Value::String(string, _) => write!(f, "{string}"),
+            Value::Int(int, _) => write!(f, "{int}"),
+            Value::Float(float, _) => write!(f, "{float}"),
+            Value::Bool(boolean, _) => write!(f, "{boolean}"),
+            Value::Array(array, _) => {
+                write!(f, "[")?;
+                list(f, array.iter(), nested)?;
+                write!(f, "]")
+            }
+            Value::Map(map, _) => {
+                write!(f, "{{")?;
+                list(f, map.iter(), |f, (key, value)| {
+                    nested(f, key)?;
+                    write!(f, ": ")?;
+                    nested(f, value)
+                })?;
+                write!(f, "}}")
+            }
+            Value::Record(record, _) => {
+                write!(f, "{} {{", record.ident.ident)?;
+                list(f, record.fields.iter(), |f, (field, value)| {
+                    write!(f, "{}: ", field.ident)?;
+                    nested(f, value)
+                })?;
+                write!(f, "}}")
+            }
+            Value::Variant(variant, _) => {
+                write!(f, "{}", variant.ident.ident)?;
+                for value in &variant.values {
+                    write!(f, "<")?;
+                    nested(f, value)?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            Value::Nil(_) => write!(f, "nil"),
+        }
+    }
+}
+
+/// Returns the string as an Ocypode string literal, in double quotes with the escapes of the string literals,
+/// so it's shown as it's written in the source, e.g. `"say \"hi\"\n"`.
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str(r#"\""#),
+            '\\' => quoted.push_str(r"\\"),
+            '\n' => quoted.push_str(r"\n"),
+            '\r' => quoted.push_str(r"\r"),
+            '\t' => quoted.push_str(r"\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,954 @@
+//! The virtual machine, it executes the bytecode of the program (see [`super::bytecode`]).
+//!
+//! The virtual machine has the same environment, builtin functions and values as the interpreter, so the programs
+//! have the same results and errors on both of them. The functions are compiled once before running, and each call
+//! executes the instructions of its function on a stack of values, instead of walking the AST of the function.
+//!
+//! A call pushes a frame to the frame stack of the virtual machine, and its instructions are executed by the same loop
+//! as the instructions of its caller, so the depth of the calls doesn't use the Rust stack. The arguments and the default
+//! values are executed by a nested loop, because they are executed while their call is bound (see [`utils::bind_args`]).
+
+use std::sync::Arc;
+
+use super::{
+    builtins::Builtins,
+    bytecode::{self, Bytecode, CallCode, Callee, Chunk, Instruction},
+    closures,
+    environment::{Environment, Local, Variable},
+    interpreter::{self, Argument, Call, DEFAULT_MAX_CALL_DEPTH, MAX_CALL_DEPTH},
+    maps, operators, patterns, records, types,
+    value::{FunctionValue, Value, VariantValue},
+};
+use crate::{
+    ast::*,
+    errors::{CallSite, Error as OYError, ErrorKind, Result as OYResult, SpanError},
+    modules::Module,
+    utils::{self, ArgExecutor, CallArg},
+};
+
+/// The code that the virtual machine is executing, a function or the code of an argument or a default value.
+#[derive(Debug)]
+struct Frame {
+    /// The chunk of the code.
+    chunk: Arc<Chunk>,
+    /// The next instruction, it's saved when the frame calls a function.
+    ip: usize,
+    /// The number of the values in the stack before the frame.
+    stack: usize,
+    /// The number of the iterations before the frame.
+    iterations: usize,
+    /// The number of the handlers before the frame.
+    handlers: usize,
+    /// The called function, `None` for the code of an argument or a default value, which is executed in the frame
+    /// of its function.
+    function: Option<FunctionFrame>,
+}
+
+/// The function of a frame.
+#[derive(Debug)]
+struct FunctionFrame {
+    /// The called function.
+    function: Arc<FunctionValue>,
+    /// The span of the call, `None` for the call of the main function.
+    span: Option<Span>,
+    /// The module of the caller, it's entered again when the function returns.
+    caller_module: usize,
+    /// The return types of the functions that tail called the function, the result is checked against them.
+    return_types: Vec<TypeAnnotation>,
+}
+
+/// A try statement that is being executed, with the state to restore when its block fails.
+#[derive(Debug, Clone)]
+struct Handler {
+    /// The index of the frame of the try statement.
+    frame: usize,
+    /// The index of the try statement in the chunk of its frame.
+    try_index: usize,
+    /// The state of the environment, see [`Environment::state`].
+    state: (usize, usize),
+    /// The number of the calls.
+    calls: usize,
+    /// The number of the values in the stack.
+    stack: usize,
+    /// The number of the iterations.
+    iterations: usize,
+}
+
+/// A resolved function call, the called function with the argument of each parameter,
+/// `None` for the parameters that use their default value.
+type ResolvedCall = (Arc<FunctionValue>, Vec<Option<Argument>>);
+
+/// The virtual machine. This will compile the AST of Ocypode to bytecode and execute it.
+#[derive(Debug)]
+pub struct Vm {
+    /// The current environment.
+    environment: Environment,
+    /// The compiled functions.
+    bytecode: Bytecode,
+    /// The number of the nested function calls, from the call of the main function to the call of the current function.
+    /// The call stack is the function frames, a tail call replaces the frame of its caller.
+    calls: usize,
+    /// The maximum number of nested function calls, a deeper call is a stack overflow error.
+    max_call_depth: usize,
+    /// The executed frames, from the frame of the main function to the current one.
+    frames: Vec<Frame>,
+    /// The values of the executed expressions.
+    stack: Vec<Value>,
+    /// The remaining items of the executed for loops.
+    iterations: Vec<std::vec::IntoIter<Value>>,
+    /// The executed try statements.
+    handlers: Vec<Handler>,
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Vm {
+    /// Creates a new virtual machine.
+    pub fn new() -> Self {
+        Self {
+            environment: Environment::new(),
+            bytecode: Bytecode::default(),
+            calls: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            frames: Vec::new(),
+            stack: Vec::new(),
+            iterations: Vec::new(),
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Sets the maximum number of nested function calls, the default is [`DEFAULT_MAX_CALL_DEPTH`] and the maximum is
+    /// [`MAX_CALL_DEPTH`]. The tail calls (`return f<...>;`) replace their caller, so they are not counted.
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth.min(MAX_CALL_DEPTH);
+        self
+    }
+
+    /// Registers a native Rust function as a builtin function, with the given params, each param is its name and whether
+    /// it's a pack param, whose arguments are packed into an array. The function is called with the arguments and the span
+    /// of the call, which is the span of its errors, e.g. `Error::new(ErrorKind::Runtime(reason), span)`.
+    pub fn register_function(
+        &mut self,
+        name: &str,
+        params: &[(&str, bool)],
+        function: impl Fn(Vec<Value>, Span) -> OYResult<Value> + Send + Sync + 'static,
+    ) -> OYResult<()> {
+        self.environment
+            .register_function(name, params, Arc::new(function))
+    }
+
+    /// Returns the builtin functions with the registered native functions, the program is checked with them before
+    /// running, see [`ownership::check_modules`](crate::ownership::check_modules).
+    pub fn builtins(&self) -> &Builtins {
+        self.environment.builtins()
+    }
+
+    /// Compiles and executes the given program. This will return the exit code of the program.
+    /// The program can't contain imports, to run a program with imports use [`Vm::interpret_modules`].
+    pub fn interpret(self, program: Program, argc: usize, argv: Vec<String>) -> OYResult<u8> {
+        self.interpret_modules(vec![Module::from_program(program)?], argc, argv)
+    }
+
+    /// Compiles and executes the given modules, the first module is the main module. This will return the exit code of the program.
+    pub fn interpret_modules(
+        mut self,
+        modules: Vec<Module>,
+        argc: usize,
+        argv: Vec<String>,
+    ) -> OYResult<u8> {
+        self.environment.add_modules(&modules)?;
+        self.bytecode = bytecode::compile(&modules, self.environment.resolutions());
+        let main_function = self
+            .environment
+            .get_global_function("main")
+            .ok_or_else(|| OYError::new(ErrorKind::MissingMainFunction, (0, 0)))?;
+        interpreter::run_main(self.max_call_depth, argc, argv, |args| {
+            self.execute_function(main_function, args, None)
+        })
+    }
+
+    /// Executes the given function with the argument of each parameter, `None` for the parameters that use their default value.
+    /// The error of the function has the traceback of the calls, from the main function to the function where it happened.
+    fn execute_function(
+        &mut self,
+        function: Arc<FunctionValue>,
+        args: Vec<Option<Argument>>,
+        span: Option<Span>,
+    ) -> OYResult<Value> {
+        let call = self.prepare_call(function, args, span)?;
+        self.calls += 1;
+        let depth = self.frames.len();
+        let caller_module = self.environment.state().0;
+        match self.enter(call, caller_module, Vec::new()) {
+            Ok(()) => self.run(depth),
+            Err(err) => Err(self.leave_frames(err, depth)),
+        }
+    }
+
+    /// Creates the frame of the given function with the argument of each parameter, `None` for the parameters that use their default value.
+    fn prepare_call(
+        &mut self,
+        function: Arc<FunctionValue>,
+        args: Vec<Option<Argument>>,
+        span: Option<Span>,
+    ) -> OYResult<Call> {
+        let defaults = args
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| arg.is_none())
+            .map(|(index, _)| index)
+            .collect();
+        let frame = self.environment.function_frame(&function, args)?;
+        Ok(Call {
+            function,
+            frame,
+            defaults,
+            span,
+        })
+    }
+
+    /// Enters the frame of the given call, its instructions are executed next. The default values are executed in the
+    /// frame of the function, after the given arguments.
+    fn enter(
+        &mut self,
+        call: Call,
+        caller_module: usize,
+        return_types: Vec<TypeAnnotation>,
+    ) -> OYResult<()> {
+        let Some(block) = &call.function.statement.block else {
+            unreachable!("The builtin function should call in the call expression")
+        };
+        let chunk = self.bytecode.chunk(&call.function.statement);
+        self.environment.enter_function_frame(call.frame);
+        // The function is executed in its module, so it can use the global functions of its file.
+        self.environment
+            .enter_module(self.environment.module_of(block.span));
+        self.frames.push(Frame {
+            chunk: Arc::clone(&chunk),
+            ip: 0,
+            stack: self.stack.len(),
+            iterations: self.iterations.len(),
+            handlers: self.handlers.len(),
+            function: Some(FunctionFrame {
+                function: Arc::clone(&call.function),
+                span: call.span,
+                caller_module,
+                return_types,
+            }),
+        });
+        for index in call.defaults {
+            let param = &call.function.statement.params[index];
+            let span = param
+                .default
+                .as_ref()
+                .expect("The params without arguments have default values")
+                .span();
+            let value = self.evaluate(
+                &chunk,
+                chunk.defaults[index].expect("The default values are compiled"),
+            )?;
+            types::check_param(param, &value, span)?;
+            self.environment.add_variable(Variable {
+                ident: param.ident.clone(),
+                value,
+                span,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Leaves the current frame with its returned value. This will return the result of the function, which is
+    /// checked against the return types of the function and the functions that tail called it.
+    fn leave(&mut self, value: Value) -> OYResult<Value> {
+        if let Some(FunctionFrame {
+            function,
+            return_types,
+            ..
+        }) = &self.frames.last().expect("The frame is executing").function
+        {
+            // The calls of a tail recursion have the same return type, so it's checked once.
+            let return_type = function
+                .statement
+                .return_type
+                .as_ref()
+                .filter(|return_type| return_types.last() != Some(return_type));
+            // The result of the last tail call is the result of all its callers.
+            for return_type in return_type.into_iter().chain(return_types.iter().rev()) {
+                types::check(Some(return_type), value.type_name(), value.span())
+                    .map_err(|err| err.with_traceback(self.traceback()))?;
+            }
+        }
+        let frame = self.frames.pop().expect("The frame is executing");
+        self.stack.truncate(frame.stack);
+        self.iterations.truncate(frame.iterations);
+        self.handlers.truncate(frame.handlers);
+        if let Some(function) = frame.function {
+            self.environment.exit_frame();
+            self.environment.enter_module(function.caller_module);
+            self.calls -= 1;
+        }
+        Ok(value)
+    }
+
+    /// Returns the traceback of the calls, from the call of the main function to the call of the current function.
+    fn traceback(&self) -> Vec<CallSite> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.function.as_ref())
+            .map(|function| interpreter::call_site(&function.function, function.span))
+            .collect()
+    }
+
+    /// Executes the code of the chunk that starts from the given instruction, which is the code of an expression,
+    /// in the frame of the current function. This will return the value of the expression.
+    fn evaluate(&mut self, chunk: &Arc<Chunk>, start: usize) -> OYResult<Value> {
+        let depth = self.frames.len();
+        self.frames.push(Frame {
+            chunk: Arc::clone(chunk),
+            ip: start,
+            stack: self.stack.len(),
+            iterations: self.iterations.len(),
+            handlers: self.handlers.len(),
+            function: None,
+        });
+        self.run(depth)
+    }
+
+    /// Executes the frames from the current one, until the frame at the given depth returns, and returns its value.
+    /// An error is caught by the innermost try statement of the executed frames, if there is one.
+    fn run(&mut self, depth: usize) -> OYResult<Value> {
+        let handlers = self.frames[depth].handlers;
+        loop {
+            match self.execute(depth) {
+                Ok(value) => return Ok(value),
+                Err(err) => self.catch(err, depth, handlers)?,
+            }
+        }
+    }
+
+    /// Catches the error by the innermost try statement after the given number of handlers, and continues the frame of
+    /// the try statement from its catch block. The environment is restored to its state before the try block, and the
+    /// error is declared in the ident of the try statement.
+    /// Returns the error if there is no try statement to catch it, after leaving the frames from the given depth.
+    fn catch(&mut self, mut err: OYError, depth: usize, handlers: usize) -> OYResult<()> {
+        while self.handlers.len() > handlers {
+            let handler = self.handlers.pop().expect("The handlers are not empty");
+            err = self.leave_frames(err, handler.frame + 1);
+            let chunk = Arc::clone(&self.frames[handler.frame].chunk);
+            let try_code = &chunk.tries[handler.try_index];
+            self.environment.restore(handler.state);
+            // The failed block is not exited, so its slots are cleared here.
+            self.environment.clear_slots(try_code.block.clone());
+            self.calls = handler.calls;
+            self.stack.truncate(handler.stack);
+            self.iterations.truncate(handler.iterations);
+            let value = self.environment.error_object(err, try_code.ident.span);
+            match self.environment.declare_variable(
+                try_code.slot,
+                Variable {
+                    ident: try_code.ident.clone(),
+                    value,
+                    span: try_code.ident.span,
+                },
+            ) {
+                Ok(()) => {
+                    self.frames[handler.frame].ip = try_code.catch;
+                    return Ok(());
+                }
+                Err(declare_err) => err = declare_err,
+            }
+        }
+        Err(self.leave_frames(err, depth))
+    }
+
+    /// Removes the frames from the given depth because of the error. The error has the traceback of the calls if it
+    /// leaves a function, it's taken in the innermost function, where the stack has all the calls.
+    fn leave_frames(&mut self, err: OYError, depth: usize) -> OYError {
+        let leaves_function = self
+            .frames
+            .get(depth..)
+            .is_some_and(|frames| frames.iter().any(|frame| frame.function.is_some()));
+        let err = if leaves_function && err.traceback.is_empty() {
+            let traceback = self.traceback();
+            err.with_traceback(traceback)
+        } else {
+            err
+        };
+        self.frames.truncate(depth);
+        err
+    }
+
+    /// Pops the top value of the stack.
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("The stack has the operands")
+    }
+
+    /// Pops the given number of values from the stack, in the order that they are pushed.
+    fn pop_many(&mut self, count: usize) -> Vec<Value> {
+        self.stack.split_off(self.stack.len() - count)
+    }
+
+    /// Returns the top value of the stack.
+    fn top(&mut self) -> &mut Value {
+        self.stack.last_mut().expect("The stack has the operands")
+    }
+
+    /// Returns the current frame.
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("The frame is executing")
+    }
+
+    /// Executes the instructions of the current frame, and the frames of its calls, until the frame at the given depth
+    /// returns. This will return the returned value of the frame.
+    fn execute(&mut self, depth: usize) -> OYResult<Value> {
+        let mut chunk = Arc::clone(&self.frame().chunk);
+        let mut ip = self.frame().ip;
+        loop {
+            let instruction = chunk.code[ip];
+            ip += 1;
+            match instruction {
+                Instruction::Constant(index) => self.stack.push(chunk.constants[index].clone()),
+                Instruction::Take(slot, index) => {
+                    let ident = &chunk.idents[index];
+                    let local = self.environment.take_slot(slot, ident, ident.span)?;
+                    self.stack.push(local_value(local));
+                }
+                Instruction::Global(index) => {
+                    let ident = &chunk.idents[index];
+                    let local = self.environment.take(ident, ident.span)?;
+                    self.stack.push(local_value(local));
+                }
+                Instruction::Borrow(slot, index, span) => {
+                    let value = self
+                        .environment
+                        .borrow_slot(slot, &chunk.idents[index], span)?;
+                    self.stack.push(value);
+                }
+                Instruction::BorrowGlobal(index, span) => {
+                    let value = self.borrow(&chunk.idents[index], span)?;
+                    self.stack.push(value);
+                }
+                Instruction::Variant(index, span) => {
+                    let value = self.variant(None, &chunk.idents[index], span)?;
+                    self.stack.push(value);
+                }
+                Instruction::Array(count) => {
+                    let values = self.pop_many(count);
+                    self.stack
+                        .push(Value::Array(Arc::new(values), Span::new(0, 0)));
+                }
+                Instruction::Map(span) => self.stack.push(Value::Map(Arc::default(), span)),
+                Instruction::Insert => {
+                    let value = self.pop();
+                    let key = self.pop();
+                    let Value::Map(entries, _) = self.top() else {
+                        unreachable!("The entries are inserted into a map")
+                    };
+                    maps::insert(Arc::make_mut(entries), key, value)?;
+                }
+                Instruction::Closure(index) => {
+                    let statement = Arc::clone(&chunk.closures[index]);
+                    let span = statement.span;
+                    let function = closures::capture(&mut self.environment, statement);
+                    self.stack.push(Value::Function(function, span));
+                }
+                Instruction::Respan(span) => *self.top().span_mut() = span,
+                Instruction::Binary(operator, span) => {
+                    let right = self.pop();
+                    let left = self.pop();
+                    self.stack
+                        .push(operators::binary(operator, left, right, span)?);
+                }
+                Instruction::ShortCircuit(operator, span, end) => {
+                    match (operator, self.top().is_truthy()) {
+                        (BinaryOperator::And, false) => *self.top() = Value::Bool(false, span),
+                        (BinaryOperator::Or, true) => *self.top() = Value::Bool(true, span),
+                        _ => continue,
+                    }
+                    ip = end;
+                }
+                Instruction::Unary(operator, span) => {
+                    let operand = self.pop();
+                    self.stack.push(operators::unary(operator, operand, span)?);
+                }
+                Instruction::Field(index) => {
+                    let field_code = &chunk.fields[index];
+                    let record = self.pop();
+                    let mut field =
+                        records::field(record, &field_code.field, field_code.record_span)?;
+                    *field.span_mut() = field_code.span;
+                    self.stack.push(field);
+                }
+                Instruction::RecordType(index) => {
+                    let ident = &chunk.records[index].ident;
+                    if self.environment.get_record(&ident.ident).is_none() {
+                        return Err(OYError::new(
+                            ErrorKind::UnDeclaredIdent(ident.ident.clone()),
+                            ident.span,
+                        ));
+                    }
+                }
+                Instruction::Record(index) => {
+                    let record = &chunk.records[index];
+                    let values = self.pop_many(record.fields.len());
+                    let record_type = self
+                        .environment
+                        .get_record(&record.ident.ident)
+                        .expect("The record type is checked before the fields");
+                    let value = records::create(
+                        record_type,
+                        record.ident.clone(),
+                        record.fields.iter().cloned().zip(values).collect(),
+                        record.span,
+                    )?;
+                    self.stack.push(value);
+                }
+                Instruction::Arm(index, next) => {
+                    let value = self.top().clone();
+                    match patterns::bindings(&chunk.patterns[index], value) {
+                        Some(bindings) => {
+                            for binding in bindings {
+                                self.environment.add_variable(binding)?;
+                            }
+                        }
+                        None => ip = next,
+                    }
+                }
+                Instruction::NoMatch(span) => {
+                    return Err(OYError::new(
+                        ErrorKind::NoMatchingArm(self.top().to_string()),
+                        span,
+                    ));
+                }
+                Instruction::PopUnder => {
+                    let under = self.stack.len() - 2;
+                    self.stack.swap_remove(under);
+                }
+                Instruction::Call(index) => {
+                    // The caller continues from the next instruction when the called function returns.
+                    self.frame().ip = ip;
+                    if self.call(&chunk, &chunk.calls[index])? {
+                        chunk = Arc::clone(&self.frame().chunk);
+                        ip = 0;
+                    }
+                }
+                Instruction::Pop => {
+                    self.pop();
+                }
+                Instruction::Jump(target) => ip = target,
+                Instruction::JumpIfFalsy(target) => {
+                    if !self.pop().is_truthy() {
+                        ip = target;
+                    }
+                }
+                Instruction::ExitScope(start, end) => self.environment.clear_slots(start..end),
+                Instruction::Function(index) => self
+                    .environment
+                    .add_local_function(Arc::clone(&chunk.functions[index]))?,
+                Instruction::Assign(index) => {
+                    let assign = &chunk.assignments[index];
+                    let value = self.pop();
+                    types::check(
+                        assign.type_annotation.as_ref(),
+                        value.type_name(),
+                        assign.value_span,
+                    )?;
+                    let variable = Variable {
+                        ident: assign.ident.clone(),
+                        value,
+                        span: assign.span,
+                    };
+                    if assign.is_update {
+                        self.environment.update_variable(assign.slot, variable);
+                    } else {
+                        self.environment.declare_variable(assign.slot, variable)?;
+                    }
+                }
+                Instruction::FieldAssign(index) => {
+                    let assign = &chunk.field_assignments[index];
+                    let value = self.pop();
+                    let Some(variable) = self.environment.variable_mut(&assign.ident) else {
+                        return Err(self
+                            .environment
+                            .undeclared_error(&assign.ident, assign.ident.span));
+                    };
+                    records::assign(
+                        &mut variable.value,
+                        &assign.fields,
+                        value,
+                        assign.ident.span,
+                    )?;
+                }
+                Instruction::Iterate(span) => {
+                    let iterable = self.pop();
+                    let items = interpreter::items(iterable, span)?;
+                    self.iterations.push(items.into_iter());
+                }
+                Instruction::Next(slot, index, end) => {
+                    let iteration = self
+                        .iterations
+                        .last_mut()
+                        .expect("The for loop is iterating");
+                    match iteration.next() {
+                        Some(item) => {
+                            let ident = &chunk.idents[index];
+                            self.environment.declare_variable(
+                                slot,
+                                Variable {
+                                    ident: ident.clone(),
+                                    value: item,
+                                    span: ident.span,
+                                },
+                            )?;
+                        }
+                        None => ip = end,
+                    }
+                }
+                Instruction::EndIterate => {
+                    self.iterations.pop();
+                }
+                Instruction::Try(try_index) => self.handlers.push(Handler {
+                    frame: self.frames.len() - 1,
+                    try_index,
+                    state: self.environment.state(),
+                    calls: self.calls,
+                    stack: self.stack.len(),
+                    iterations: self.iterations.len(),
+                }),
+                Instruction::EndTry => {
+                    self.handlers.pop();
+                }
+                Instruction::Return => {
+                    let value = self.pop();
+                    let value = self.leave(value)?;
+                    if self.frames.len() == depth {
+                        return Ok(value);
+                    }
+                    self.stack.push(value);
+                    chunk = Arc::clone(&self.frame().chunk);
+                    ip = self.frame().ip;
+                }
+            }
+        }
+    }
+
+    /// Executes the given call. A call of a function enters its frame, a tail call replaces the frame of the current
+    /// function, and the result of the other calls (the variants and the builtin functions) is pushed.
+    /// This will return whether a frame is entered.
+    fn call(&mut self, chunk: &Arc<Chunk>, call: &CallCode) -> OYResult<bool> {
+        match self.resolve_call(chunk, call)? {
+            Ok((function, args)) if call.is_tail => {
+                let next_call = self.prepare_call(function, args, Some(call.span))?;
+                let frame = self.frames.pop().expect("The frame is executing");
+                self.stack.truncate(frame.stack);
+                self.iterations.truncate(frame.iterations);
+                self.handlers.truncate(frame.handlers);
+                let Some(FunctionFrame {
+                    function,
+                    caller_module,
+                    mut return_types,
+                    ..
+                }) = frame.function
+                else {
+                    unreachable!("The tail calls are returned from functions")
+                };
+                self.environment.exit_frame();
+                if let Some(return_type) = &function.statement.return_type {
+                    // The calls of a tail recursion have the same return type, so it's checked once.
+                    if return_types.last() != Some(return_type) {
+                        return_types.push(return_type.clone());
+                    }
+                }
+                self.enter(next_call, caller_module, return_types)?;
+                Ok(true)
+            }
+            Ok((function, args)) => {
+                if self.calls >= self.max_call_depth {
+                    return Err(OYError::new(
+                        ErrorKind::StackOverflow(self.max_call_depth),
+                        call.span,
+                    ));
+                }
+                let next_call = self.prepare_call(function, args, Some(call.span))?;
+                self.calls += 1;
+                let caller_module = self.environment.state().0;
+                self.enter(next_call, caller_module, Vec::new())?;
+                Ok(true)
+            }
+            Err(result) => {
+                self.stack.push(result);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Resolves the called function of the given call and executes its arguments in the current scope.
+    /// This will return the function with the argument of each parameter, `None` for the parameters that use their default value.
+    /// The variants and the builtin functions are executed here, and their result is returned as `Err`.
+    fn resolve_call(
+        &mut self,
+        chunk: &Arc<Chunk>,
+        call: &CallCode,
+    ) -> OYResult<Result<ResolvedCall, Value>> {
+        let function = match &call.callee {
+            Callee::Variant(ident) => {
+                return self.variant(Some((chunk, call)), ident, call.span).map(Err)
+            }
+            Callee::Local(slot, ident) => {
+                match self.environment.take_slot(*slot, ident, call.span)? {
+                    Local::Variable(Variable {
+                        value: Value::Function(function, _),
+                        ..
+                    }) => function,
+                    Local::Variable(variable) => {
+                        return Err(OYError::new(
+                            ErrorKind::NotCallable(call.span.span()),
+                            variable.span,
+                        ));
+                    }
+                    Local::Function(function) => function,
+                }
+            }
+            Callee::Ident(ident) => match self.environment.take(ident, call.span)? {
+                Local::Variable(Variable {
+                    value: Value::Function(function, _),
+                    ..
+                }) => function,
+                Local::Variable(variable) => {
+                    return Err(OYError::new(
+                        ErrorKind::NotCallable(call.span.span()),
+                        variable.span,
+                    ));
+                }
+                Local::Function(function) => function,
+            },
+            Callee::Closure(index) => {
+                closures::capture(&mut self.environment, Arc::clone(&chunk.closures[*index]))
+            }
+            Callee::Value(span) => match self.pop() {
+                Value::Function(function, _) => function,
+                _ => {
+                    return Err(OYError::new(
+                        ErrorKind::NotCallable(call.span.span()),
+                        *span,
+                    ));
+                }
+            },
+        };
+
+        let params = &function.statement.params;
+        if call.is_positional
+            && !function.is_builtin()
+            && params.len() == call.args.len()
+            && params.iter().all(|param| !param.is_pack)
+        {
+            // Each argument is bound to the param in its position, so the arguments are not bound at runtime.
+            let args = self.positional_args(chunk, call)?;
+            return Ok(Ok((function, args)));
+        }
+        let mut call_args = CallArgs {
+            vm: self,
+            chunk,
+            call,
+        };
+        let args = utils::unpack_args(&mut call_args, &call.args)?;
+        let args = utils::bind_args(&mut call_args, &function.statement, args, call.span)?;
+        if function.is_builtin() {
+            let ident = &function.ident.as_ref().unwrap().ident;
+            // The `clone` builtin borrows its argument, so the cloned variable is not moved.
+            let borrows = ident == "clone";
+            let args = args
+                .into_iter()
+                // The builtin functions have no default values, so all their params have arguments.
+                .flatten()
+                .map(|arg| {
+                    let span = arg.span();
+                    let mut value = match arg {
+                        CallArg::Arg(Arg {
+                            expr: ExpressionStatement::Value(ValueExpression::Ident(ident)),
+                            ..
+                        }) if borrows => self.borrow(ident, ident.span)?,
+                        CallArg::Arg(arg) => self.execute_arg(chunk, call, arg)?,
+                        CallArg::Value(value, _) => value,
+                    };
+                    *value.span_mut() = span;
+                    Ok(value)
+                })
+                .collect::<OYResult<Vec<Value>>>()?;
+            self.environment
+                .builtins()
+                .execute_builtin_funtion(ident, call.span, args)
+                .map(Err)
+        } else {
+            // The arguments are executed in the caller scope, except the idents, which are moved to the function scope
+            // after the other arguments, so the other arguments can borrow them. The variants are constructed here.
+            let args = args
+                .into_iter()
+                .map(|arg| {
+                    arg.map(|arg| match arg {
+                        CallArg::Arg(Arg {
+                            expr: ExpressionStatement::Value(ValueExpression::Ident(ident)),
+                            span,
+                            ..
+                        }) if self.environment.variant(ident).is_none() => Ok(Err((ident, *span))),
+                        CallArg::Arg(arg) => Ok(Ok(Argument::Value(
+                            self.execute_arg(chunk, call, arg)?,
+                            arg.span,
+                        ))),
+                        CallArg::Value(value, span) => Ok(Ok(Argument::Value(value, span))),
+                    })
+                    .transpose()
+                })
+                .collect::<OYResult<Vec<_>>>()?;
+            let args = args
+                .into_iter()
+                .map(|arg| {
+                    arg.map(|arg| match arg {
+                        Ok(arg) => Ok(arg),
+                        Err((ident, span)) => Ok(match self.environment.take(ident, span)? {
+                            Local::Variable(variable) => Argument::Value(variable.value, span),
+                            Local::Function(function) => Argument::Function(function, span),
+                        }),
+                    })
+                    .transpose()
+                })
+                .collect::<OYResult<Vec<_>>>()?;
+            Ok(Ok((function, args)))
+        }
+    }
+
+    /// Executes the arguments of the call that are bound to the params in their order, see [`CallCode::is_positional`].
+    /// The idents are moved after the other arguments, like the arguments of the other calls.
+    fn positional_args(
+        &mut self,
+        chunk: &Arc<Chunk>,
+        call: &CallCode,
+    ) -> OYResult<Vec<Option<Argument>>> {
+        let mut args = Vec::with_capacity(call.args.len());
+        for (arg, start) in call.args.iter().zip(&call.arg_code) {
+            args.push(match &arg.expr {
+                ExpressionStatement::Value(ValueExpression::Ident(ident))
+                    if self.environment.variant(ident).is_none() =>
+                {
+                    None
+                }
+                _ => Some(Argument::Value(self.evaluate(chunk, *start)?, arg.span)),
+            });
+        }
+        for (arg, value) in call.args.iter().zip(&mut args) {
+            if let (ExpressionStatement::Value(ValueExpression::Ident(ident)), None) =
+                (&arg.expr, &value)
+            {
+                *value = Some(match self.environment.take(ident, arg.span)? {
+                    Local::Variable(variable) => Argument::Value(variable.value, arg.span),
+                    Local::Function(function) => Argument::Function(function, arg.span),
+                });
+            }
+        }
+        Ok(args)
+    }
+
+    /// Executes the code of the given argument of the call.
+    fn execute_arg(&mut self, chunk: &Arc<Chunk>, call: &CallCode, arg: &Arg) -> OYResult<Value> {
+        let index = call
+            .args
+            .iter()
+            .position(|call_arg| std::ptr::eq(call_arg, arg))
+            .expect("The argument is an argument of the call");
+        self.evaluate(chunk, call.arg_code[index])
+    }
+
+    /// Returns a copy of the value that the ident is bound to, with the span of the borrow.
+    /// Borrowing a variant constructs it, like using its ident.
+    fn borrow(&mut self, ident: &Ident, span: Span) -> OYResult<Value> {
+        if self.environment.variant(ident).is_some() {
+            return self.variant(None, ident, span);
+        }
+        let local = self.environment.borrow(ident, span)?;
+        Ok(local.into_value(span))
+    }
+
+    /// Constructs the variant of the given ident with the arguments of the call, a variant without call has no arguments.
+    /// The arguments are executed in order, and their number must be the number of the variant fields.
+    fn variant(
+        &mut self,
+        call: Option<(&Arc<Chunk>, &CallCode)>,
+        ident: &Ident,
+        span: Span,
+    ) -> OYResult<Value> {
+        let args = match call {
+            Some((chunk, call)) => utils::unpack_args(
+                &mut CallArgs {
+                    vm: self,
+                    chunk,
+                    call,
+                },
+                &call.args,
+            )?,
+            None => Vec::new(),
+        };
+        let (enum_stmt, variant) = self
+            .environment
+            .variant(ident)
+            .expect("The variants are found by their bindings");
+        if variant.fields.len() != args.len() {
+            return Err(interpreter::variant_arguments_error(
+                variant,
+                args.len(),
+                span,
+            ));
+        }
+        let enum_ident = enum_stmt.ident.clone();
+        let values = args
+            .into_iter()
+            .map(|arg| match (arg, call) {
+                (CallArg::Arg(arg), Some((chunk, call))) => {
+                    let mut value = self.execute_arg(chunk, call, arg)?;
+                    *value.span_mut() = arg.expr.span();
+                    Ok(value)
+                }
+                (CallArg::Value(value, _), _) => Ok(value),
+                (CallArg::Arg(_), None) => unreachable!("The arguments are of the call"),
+            })
+            .collect::<OYResult<Vec<_>>>()?;
+        Ok(Value::Variant(
+            Arc::new(VariantValue {
+                enum_ident,
+                ident: ident.clone(),
+                values,
+            }),
+            span,
+        ))
+    }
+}
+
+/// The arguments of a call that is being executed by the virtual machine, they are executed from their code.
+struct CallArgs<'a> {
+    /// The virtual machine.
+    vm: &'a mut Vm,
+    /// The chunk of the call.
+    chunk: &'a Arc<Chunk>,
+    /// The call.
+    call: &'a CallCode,
+}
+
+impl ArgExecutor for CallArgs<'_> {
+    fn execute_arg(&mut self, arg: &Arg) -> OYResult<Value> {
+        self.vm.execute_arg(self.chunk, self.call, arg)
+    }
+}
+
+/// Returns the value of the local, the value of a local function is the function with its span.
+fn local_value(local: Local) -> Value {
+    match local {
+        Local::Variable(variable) => variable.value,
+        Local::Function(function) => {
+            let span = function.statement.span;
+            Value::Function(function, span)
+        }
+    }
+}
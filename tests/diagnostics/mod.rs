@@ -4,6 +4,9 @@
 //! And create a file with the same name as the diagnostic, but with the extension `.oy`.
 //! This file should contain the source code that will trigger the diagnostic.
 //! Lastly, add the name of the diagnostic to the `test_diagnostics` macro. This macro is used to create all tests.
+//! The files that are imported by the diagnostics are in the `imports` directory.
+//! The warnings are before the error in the diagnostic, and a warning can be tested alone if the program has no error.
+//! The ownership and type errors and warnings are all in the diagnostic, and the program is not run if there is an error.
 //!
 //! Note: The diagnostic is without a color code, so it is not colored. Make sure when you want to add or test a diagnostic that it is not colored.
 //! Run with `NO_COLOR=1` environment variable to disable colors.
@@ -14,18 +17,30 @@ macro_rules! test_diagnostics {
         $(
             #[test]
             fn $diagnostic() {
-                let source = std::fs::read_to_string(concat!("tests/diagnostics/", stringify!($diagnostic), ".oy")).unwrap();
+                let path = concat!("tests/diagnostics/", stringify!($diagnostic), ".oy");
+                let source = std::fs::read_to_string(path).unwrap();
                 let expected = std::fs::read_to_string(concat!("tests/diagnostics/", stringify!($diagnostic), ".txt")).unwrap();
-                let program = ocypode_lang::parser::OYParser::parse_program(&source);
-                if let Err(err) = program {
-                    let diagnostic = err.as_diagnostic(&source, concat!("tests/diagnostics/", stringify!($diagnostic), ".oy"));
-                    assert_eq!(format!("{}", diagnostic), expected);
-                } else if let Err(err) = ocypode_lang::runtime::interpreter::Interpreter::new().interpret(
-                        program.unwrap(),0,vec![]
-                    )
-                {
-                    let diagnostic = err.as_diagnostic(source, concat!("tests/diagnostics/", stringify!($diagnostic), ".oy").to_string());
-                    assert_eq!(format!("{}", diagnostic), expected);
+                let mut sources = ocypode_lang::modules::Sources::default();
+                let mut diagnostics = Vec::new();
+                let result = ocypode_lang::modules::load(&mut sources, path, source).and_then(|modules| {
+                    diagnostics.extend(modules.iter().flat_map(|module| module.warnings.clone()));
+                    let builtins = ocypode_lang::runtime::builtins::Builtins::new().functions;
+                    let mut errors = ocypode_lang::ownership::check_modules(&modules, &builtins);
+                    errors.extend(ocypode_lang::types::check_modules(&modules, &builtins));
+                    let has_errors = errors.iter().any(|error| !error.is_warning());
+                    diagnostics.extend(errors);
+                    if has_errors {
+                        return Ok(1);
+                    }
+                    ocypode_lang::runtime::interpreter::Interpreter::new().interpret_modules(modules, 0, vec![])
+                });
+                diagnostics.extend(result.err());
+                if !diagnostics.is_empty() {
+                    let diagnostic = diagnostics
+                        .into_iter()
+                        .map(|diagnostic| format!("{}", sources.as_diagnostic(diagnostic)))
+                        .collect::<String>();
+                    assert_eq!(diagnostic, expected);
                 }
             }
         )+
@@ -45,11 +60,38 @@ test_diagnostics!(
     main_function_invalid_second_parameter
     main_function_cannot_be_public
     undeclared_ident
+    undeclared_variant_pattern
     already_declared_function
     already_declared_variable
     invalid_exit_code
     not_callable
+    not_callable_expression
     uncorrect_argument
-    unexpected_type
     format_error
+    break_outside_loop
+    division_by_zero
+    invalid_operands
+    invalid_comparison
+    invalid_map_key
+    invalid_import
+    circular_import
+    imported_function_error
+    private_function_import
+    no_matching_arm
+    unreachable_arm
+    uncaught_throw
+    unknown_field
+    missing_fields
+    already_declared_variant
+    unknown_argument
+    duplicate_argument
+    missing_argument
+    required_param_after_default
+    use_after_move
+    use_after_maybe_move
+    ownership_errors
+    mismatched_type
+    unknown_type
+    stack_overflow
+    traceback
 );
@@ -0,0 +1,203 @@
+use ocypode_lang::{
+    ast::*,
+    errors::{Error, ErrorKind},
+    parser::OYParser,
+    types,
+};
+
+/// Returns the type errors of the given source code.
+fn check(source: &str) -> Vec<Error> {
+    types::check(&OYParser::parse_program(source).unwrap().0)
+}
+
+/// Returns the names of the types of the given annotation.
+fn type_names(type_annotation: &Option<TypeAnnotation>) -> Vec<&str> {
+    type_annotation
+        .iter()
+        .flat_map(|ty| &ty.types)
+        .map(|ty| ty.ident.as_str())
+        .collect()
+}
+
+#[test]
+fn test_parse_annotations() {
+    let source = "~add<a: int><b: int | float = 1>: int | float {<
+        c: float = a + b;
+        return c;
+    >}";
+    let (statements, _) = OYParser::parse_program(source).unwrap();
+    let Statement::Function(function) = &statements[0] else {
+        panic!("Expected a function");
+    };
+    assert_eq!(type_names(&function.params[0].type_annotation), ["int"]);
+    assert_eq!(
+        type_names(&function.params[1].type_annotation),
+        ["int", "float"]
+    );
+    assert!(function.params[1].default.is_some());
+    assert_eq!(type_names(&function.return_type), ["int", "float"]);
+    assert_eq!(
+        function.return_type.as_ref().unwrap().span,
+        Span::new(34, 45)
+    );
+    let Statement::Assignment(assignment) = &function.block.as_ref().unwrap().statements[0] else {
+        panic!("Expected an assignment");
+    };
+    assert_eq!(type_names(&assignment.type_annotation), ["float"]);
+}
+
+#[test]
+fn test_parse_anonymous_function_annotations() {
+    let source = "~main<argc><argv>{< f = <*names: string | nil>: nil {< return nil; >}; >}";
+    let (statements, _) = OYParser::parse_program(source).unwrap();
+    let Statement::Function(main) = &statements[0] else {
+        panic!("Expected a function");
+    };
+    let Statement::Assignment(AssignmentStatement {
+        expression:
+            ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Function(function))),
+        ..
+    }) = &main.block.as_ref().unwrap().statements[0]
+    else {
+        panic!("Expected an anonymous function");
+    };
+    assert!(function.params[0].is_pack);
+    assert_eq!(
+        type_names(&function.params[0].type_annotation),
+        ["string", "nil"]
+    );
+    assert_eq!(type_names(&function.return_type), ["nil"]);
+}
+
+#[test]
+fn test_parse_invalid_annotations() {
+    for source in [
+        "~main<argc: ><argv>{< >}",
+        "~main<argc><argv>: {< >}",
+        "~main<argc><argv>{< x: int | = 1; >}",
+    ] {
+        assert!(
+            matches!(
+                OYParser::parse_program(source).map_err(|err| err.kind),
+                Err(ErrorKind::Parse(_))
+            ),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_mismatched_types() {
+    let source = r#"~add<a: int><b: int>: int {< return "sum"; >}
+    ~main<argc><argv>{<
+        x: string = 1;
+        y = add<1.5><b = true>;
+        z = len<5>;
+        return 0;
+    >}"#;
+    let errors = check(source);
+    let expected = [
+        ("int", "string", (36, 5)),
+        ("string", "int", (90, 1)),
+        ("int", "float", (109, 3)),
+        ("int", "bool", (114, 8)),
+        ("array | string | map", "int", (141, 1)),
+    ];
+    assert_eq!(errors.len(), expected.len());
+    for (error, (expected, found, span)) in errors.iter().zip(expected) {
+        assert!(
+            matches!(&error.kind, ErrorKind::MismatchedType(e, f, _) if e == expected && f == found),
+            "{:?}",
+            error.kind
+        );
+        assert_eq!(error.span, span.into());
+    }
+    // The builtin functions have no annotation span.
+    assert!(matches!(
+        errors[4].kind,
+        ErrorKind::MismatchedType(_, _, None)
+    ));
+}
+
+#[test]
+fn test_inferred_types() {
+    for source in [
+        "~main<argc><argv>{< x = 1 + 2; y: float = x; >}",
+        "~main<argc><argv>{< x = format<\"{}\"><1>; y: int = x; >}",
+        "~main<argc><argv>{< x: int = 1; y: string = clone<x>; >}",
+        "~f: bool {< return true; >} ~main<argc><argv>{< x: int = f<>; >}",
+        "~main<argc><argv>{< x: int = <a>: string {< return a; >}<1>; >}",
+        "~main<argc><argv>{< x: int = match argc {< 0 => \"zero\", _ => \"many\" >}; >}",
+        "record Point<x><y>; ~main<argc><argv>{< p: int = Point {x: 1, y: 2}; >}",
+        "enum Option<Some<value>><None>; ~main<argc><argv>{< x: int = Some<1>; >}",
+        "~main<argc><argv>{< x = 1; if argc {< x = 2; >}; y: string = x; >}",
+    ] {
+        let errors = check(source);
+        assert_eq!(errors.len(), 1, "{source}");
+        assert!(
+            matches!(errors[0].kind, ErrorKind::MismatchedType(..)),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_unknown_value_types() {
+    for source in [
+        "~f<a>{< x: string = a; >}",
+        "~main<argc><argv>{< x = get<argv><0>; y: int = x; >}",
+        "~f<a>{< x: int | string = a; y: string = x; >}",
+        "~f<a>{< x: int = match a {< 0 => 0, _ => \"many\" >}; >}",
+        "~main<argc><argv>{< f = <a>{< return a; >}; x: int = f<\"a\">; >}",
+        "~f<a: int><b: int>{< >} ~main<argc><argv>{< args = [\"a\"]; f<...args><\"b\">; >}",
+        "~main<argc><argv>{< x = 1; ~f{< y: string = x; >}; >}",
+        // The block may update the variable with a value of another type.
+        "~f<a>{< x = 1; if a {< x = \"a\"; >}; y: int = x; >}",
+    ] {
+        assert!(check(source).is_empty(), "{source}");
+    }
+}
+
+#[test]
+fn test_main_argument_types() {
+    let source = r#"~main<argc><argv>{< len<argc>; x: string = get<argv><0>; y: int = argc; z: array = argv; >}"#;
+    let errors = check(source);
+    assert_eq!(errors.len(), 1);
+    assert!(
+        matches!(&errors[0].kind, ErrorKind::MismatchedType(e, f, _) if e == "array | string | map" && f == "int")
+    );
+    let errors = check("~main<argc: string><argv: array>{< >}");
+    assert_eq!(errors.len(), 1);
+    assert!(
+        matches!(&errors[0].kind, ErrorKind::MismatchedType(e, f, Some(_)) if e == "string" && f == "int")
+    );
+    // Only the main function is given the arguments of the runtime.
+    assert!(check("~f<argc><argv>{< len<argc>; >}").is_empty());
+}
+
+#[test]
+fn test_packs_and_defaults() {
+    let source = r#"~sum<*numbers: int | float>{< >}
+    ~greet<name: string = 1>{< >}
+    ~main<argc><argv>{< sum<1><2.5><"3">; greet<>; >}"#;
+    let errors = check(source);
+    assert_eq!(errors.len(), 2);
+    assert!(
+        matches!(&errors[0].kind, ErrorKind::MismatchedType(e, f, Some(_)) if e == "string" && f == "int")
+    );
+    assert!(
+        matches!(&errors[1].kind, ErrorKind::MismatchedType(e, f, Some(_)) if e == "int | float" && f == "string")
+    );
+}
+
+#[test]
+fn test_unknown_types() {
+    let source = "record Point<x><y>; enum Shape<Circle<r>>;
+    ~main<argc><argv>{< a: Point = nil; b: Shape | Int = nil; c: nil = nil; >}";
+    let errors = check(source);
+    assert_eq!(errors.len(), 2);
+    assert!(
+        matches!(&errors[0].kind, ErrorKind::MismatchedType(e, f, _) if e == "Point" && f == "nil")
+    );
+    assert!(matches!(&errors[1].kind, ErrorKind::UnknownType(name) if name == "Int"));
+}
@@ -18,6 +18,8 @@ fn test_assign_to_int() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -25,6 +27,8 @@ fn test_assign_to_int() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -36,11 +40,13 @@ fn test_assign_to_int() {
                     expression: ExpressionStatement::Value(ValueExpression::Object(
                         ObjectExpression::Int(1.into(), Span::new(23, 24)),
                     )),
+                    type_annotation: None,
                     span: Span::new(19, 24),
                 })],
                 span: Span::new(17, 27),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 27),
         })],
         pest::Span::new(source, 0, 27).unwrap(),
@@ -67,6 +73,8 @@ fn test_assign_to_ident() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -74,6 +82,8 @@ fn test_assign_to_ident() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -86,11 +96,13 @@ fn test_assign_to_ident() {
                         ident: "b".to_owned(),
                         span: Span::new(23, 24),
                     })),
+                    type_annotation: None,
                     span: Span::new(19, 24),
                 })],
                 span: Span::new(17, 27),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 27),
         })],
         pest::Span::new(source, 0, 27).unwrap(),
@@ -117,6 +129,8 @@ fn test_assign_to_float() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -124,6 +138,8 @@ fn test_assign_to_float() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -135,11 +151,13 @@ fn test_assign_to_float() {
                     expression: ExpressionStatement::Value(ValueExpression::Object(
                         ObjectExpression::Float("1.0".parse().unwrap(), Span::new(23, 26)),
                     )),
+                    type_annotation: None,
                     span: Span::new(19, 26),
                 })],
                 span: Span::new(17, 29),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 29),
         })],
         pest::Span::new(source, 0, 29).unwrap(),
@@ -166,6 +184,8 @@ fn test_assign_to_string() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -173,6 +193,8 @@ fn test_assign_to_string() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -184,11 +206,13 @@ fn test_assign_to_string() {
                     expression: ExpressionStatement::Value(ValueExpression::Object(
                         ObjectExpression::String("hello".to_owned(), Span::new(23, 30)),
                     )),
+                    type_annotation: None,
                     span: Span::new(19, 30),
                 })],
                 span: Span::new(17, 33),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 33),
         })],
         pest::Span::new(source, 0, 33).unwrap(),
@@ -215,6 +239,8 @@ fn test_assign_to_bool() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -222,6 +248,8 @@ fn test_assign_to_bool() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -233,11 +261,13 @@ fn test_assign_to_bool() {
                     expression: ExpressionStatement::Value(ValueExpression::Object(
                         ObjectExpression::Bool(true, Span::new(23, 27)),
                     )),
+                    type_annotation: None,
                     span: Span::new(19, 27),
                 })],
                 span: Span::new(17, 30),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 30),
         })],
         pest::Span::new(source, 0, 30).unwrap(),
@@ -264,6 +294,8 @@ fn test_assign_to_array() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -271,6 +303,8 @@ fn test_assign_to_array() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -295,11 +329,13 @@ fn test_assign_to_array() {
                             Span::new(23, 32),
                         ),
                     )),
+                    type_annotation: None,
                     span: Span::new(19, 32),
                 })],
                 span: Span::new(17, 35),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 35),
         })],
         pest::Span::new(source, 0, 35).unwrap(),
@@ -326,6 +362,8 @@ fn test_assign_to_nil() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -333,6 +371,8 @@ fn test_assign_to_nil() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -344,11 +384,13 @@ fn test_assign_to_nil() {
                     expression: ExpressionStatement::Value(ValueExpression::Object(
                         ObjectExpression::Nil(Span::new(23, 26)),
                     )),
+                    type_annotation: None,
                     span: Span::new(19, 26),
                 })],
                 span: Span::new(17, 29),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 29),
         })],
         pest::Span::new(source, 0, 29).unwrap(),
@@ -375,6 +417,8 @@ fn test_assign_to_fun_call() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -382,6 +426,8 @@ fn test_assign_to_fun_call() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -391,18 +437,22 @@ fn test_assign_to_fun_call() {
                         span: Span::new(19, 20),
                     },
                     expression: ExpressionStatement::FunctionCall(FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "fun".to_owned(),
-                            span: Span::new(23, 26),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "fun".to_owned(),
+                                span: Span::new(23, 26),
+                            },
+                        ))),
                         args: vec![],
                         span: Span::new(23, 28),
                     }),
+                    type_annotation: None,
                     span: Span::new(19, 28),
                 })],
                 span: Span::new(17, 31),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 31),
         })],
         pest::Span::new(source, 0, 31).unwrap(),
@@ -429,6 +479,8 @@ fn test_assign_to_fun_call_with_args() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -436,6 +488,8 @@ fn test_assign_to_fun_call_with_args() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -445,15 +499,18 @@ fn test_assign_to_fun_call_with_args() {
                         span: Span::new(19, 20),
                     },
                     expression: ExpressionStatement::FunctionCall(FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "fun".to_owned(),
-                            span: Span::new(23, 26),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "fun".to_owned(),
+                                span: Span::new(23, 26),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Int("1".parse().unwrap(), Span::new(27, 28)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(27, 28),
                             },
@@ -461,6 +518,7 @@ fn test_assign_to_fun_call_with_args() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Int("2".parse().unwrap(), Span::new(30, 31)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(30, 31),
                             },
@@ -468,17 +526,20 @@ fn test_assign_to_fun_call_with_args() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Int("3".parse().unwrap(), Span::new(33, 34)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(33, 34),
                             },
                         ],
                         span: Span::new(23, 35),
                     }),
+                    type_annotation: None,
                     span: Span::new(19, 35),
                 })],
                 span: Span::new(17, 38),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 38),
         })],
         pest::Span::new(source, 0, 38).unwrap(),
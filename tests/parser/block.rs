@@ -18,6 +18,8 @@ fn test_single_line_block() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -25,15 +27,19 @@ fn test_single_line_block() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: Vec::new(),
                         span: Span::new(19, 25),
                     },
@@ -41,6 +47,7 @@ fn test_single_line_block() {
                 span: Span::new(17, 28),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 28),
         })],
         pest::Span::new(source, 0, 28).unwrap(),
@@ -69,6 +76,8 @@ fn test_multi_line_block() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -76,15 +85,19 @@ fn test_multi_line_block() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(28, 32),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(28, 32),
+                            },
+                        ))),
                         args: Vec::new(),
                         span: Span::new(28, 34),
                     },
@@ -92,6 +105,7 @@ fn test_multi_line_block() {
                 span: Span::new(17, 42),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 42),
         })],
         pest::Span::new(source, 0, 42).unwrap(),
@@ -0,0 +1,67 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+/// Returns the statements of the main function, which is the last statement of the program.
+fn main_statements(source: &str) -> Vec<Statement> {
+    match OYParser::parse_program(source).unwrap().0.pop().unwrap() {
+        Statement::Function(FunctionStatement {
+            block: Some(block), ..
+        }) => block.statements,
+        _ => unreachable!(),
+    }
+}
+
+fn borrow(name: &str, start: usize, end: usize) -> ExpressionStatement {
+    ExpressionStatement::Borrow(BorrowExpression {
+        ident: Ident {
+            ident: name.to_owned(),
+            span: Span::new(start + 1, end),
+        },
+        span: Span::new(start, end),
+    })
+}
+
+#[test]
+fn test_borrow() {
+    let source = "~main<argc><argv>{<print<&name>;>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Expression(ExpressionStatement::FunctionCall(
+            FunctionCallExpression {
+                callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(Ident {
+                    ident: "print".to_owned(),
+                    span: Span::new(19, 24),
+                }))),
+                args: vec![Arg {
+                    expr: borrow("name", 25, 30),
+                    name: None,
+                    is_unpack: false,
+                    span: Span::new(25, 30),
+                }],
+                span: Span::new(19, 31),
+            }
+        ))]
+    );
+}
+
+#[test]
+fn test_borrow_field_access() {
+    let source = "~main<argc><argv>{<&point.x + 1;>}";
+    assert!(matches!(
+        main_statements(source).as_slice(),
+        [Statement::Expression(ExpressionStatement::Binary(BinaryExpression { left, .. }))]
+            if matches!(left.as_ref(), ExpressionStatement::FieldAccess(field_access)
+                if *field_access.value == borrow("point", 19, 25))
+    ));
+}
+
+#[test]
+fn test_invalid_borrow() {
+    for source in [
+        "~main<argc><argv>{<& name;>}",
+        "~main<argc><argv>{<&1;>}",
+        "~main<argc><argv>{<&&name;>}",
+    ] {
+        let ast = OYParser::parse_program(source);
+        assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+    }
+}
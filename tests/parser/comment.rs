@@ -19,6 +19,8 @@ fn test_single_line_comment() {
                         span: Span::new(27, 31),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -26,6 +28,8 @@ fn test_single_line_comment() {
                         span: Span::new(50, 54),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -33,6 +37,7 @@ fn test_single_line_comment() {
                 span: Span::new(55, 59),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(21, 59),
         })],
         pest::Span::new(source, 0, 76).unwrap(),
@@ -59,6 +64,8 @@ fn test_multi_line_block_comment() {
                         span: Span::new(55, 59),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -66,6 +73,8 @@ fn test_multi_line_block_comment() {
                         span: Span::new(61, 65),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -73,6 +82,7 @@ fn test_multi_line_block_comment() {
                 span: Span::new(66, 70),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(49, 70),
         })],
         pest::Span::new(source, 0, 117).unwrap(),
@@ -99,6 +109,8 @@ fn test_block_comment_in_beginning() {
                         span: Span::new(23, 27),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -106,6 +118,8 @@ fn test_block_comment_in_beginning() {
                         span: Span::new(29, 33),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -113,6 +127,7 @@ fn test_block_comment_in_beginning() {
                 span: Span::new(34, 38),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(17, 38),
         })],
         pest::Span::new(source, 0, 38).unwrap(),
@@ -139,6 +154,8 @@ fn test_block_comment_in_end() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -146,6 +163,8 @@ fn test_block_comment_in_end() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -153,6 +172,7 @@ fn test_block_comment_in_end() {
                 span: Span::new(17, 21),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 21),
         })],
         pest::Span::new(source, 0, 38).unwrap(),
@@ -179,6 +199,8 @@ fn test_block_comment_in_beginning_and_end() {
                         span: Span::new(23, 27),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -186,6 +208,8 @@ fn test_block_comment_in_beginning_and_end() {
                         span: Span::new(29, 33),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -193,6 +217,7 @@ fn test_block_comment_in_beginning_and_end() {
                 span: Span::new(34, 38),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(17, 38),
         })],
         pest::Span::new(source, 0, 62).unwrap(),
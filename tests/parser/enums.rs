@@ -0,0 +1,97 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+fn ident(name: &str, start: usize, end: usize) -> Ident {
+    Ident {
+        ident: name.to_owned(),
+        span: Span::new(start, end),
+    }
+}
+
+#[test]
+fn test_enum() {
+    let source = "enum Option<Some<value>><None>;\n^enum Pair<Pair<left><right>>;";
+    let statements = OYParser::parse_program(source).unwrap().0;
+    assert_eq!(
+        statements,
+        vec![
+            Statement::Enum(EnumStatement {
+                ident: ident("Option", 5, 11),
+                variants: vec![
+                    Variant {
+                        ident: ident("Some", 12, 16),
+                        fields: vec![ident("value", 17, 22)],
+                        span: Span::new(11, 24),
+                    },
+                    Variant {
+                        ident: ident("None", 25, 29),
+                        fields: Vec::new(),
+                        span: Span::new(24, 30),
+                    },
+                ],
+                visibility: Visibility::Private,
+                span: Span::new(0, 31),
+            }),
+            Statement::Enum(EnumStatement {
+                ident: ident("Pair", 38, 42),
+                variants: vec![Variant {
+                    ident: ident("Pair", 43, 47),
+                    fields: vec![ident("left", 48, 52), ident("right", 54, 59)],
+                    span: Span::new(42, 61),
+                }],
+                visibility: Visibility::Public,
+                span: Span::new(32, 62),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_enum_name() {
+    for source in ["enum option<None>;", "enum Option<none>;"] {
+        let ast = OYParser::parse_program(source);
+        assert!(
+            matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_invalid_record_name() {
+    let ast = OYParser::parse_program("record point<x><y>;");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)));
+}
+
+#[test]
+fn test_duplicate_variant() {
+    let ast = OYParser::parse_program("enum Option<Some<value>><Some>;");
+    assert!(matches!(
+        ast.unwrap_err().kind,
+        ErrorKind::AlreadyDeclared(name, _) if name == "Some"
+    ));
+}
+
+#[test]
+fn test_duplicate_variant_field() {
+    let ast = OYParser::parse_program("enum Pair<Pair<left><left>>;");
+    assert!(matches!(
+        ast.unwrap_err().kind,
+        ErrorKind::AlreadyDeclared(name, _) if name == "left"
+    ));
+}
+
+#[test]
+fn test_invalid_enum() {
+    for source in [
+        "enum Option;",
+        "enum Option<Some<value>>",
+        "enum Option<Some<>>;",
+        "~main<argc><argv>{< enum Option<None>; return 0; >}",
+    ] {
+        let ast = OYParser::parse_program(source);
+        assert!(
+            matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)),
+            "{source}"
+        );
+    }
+}
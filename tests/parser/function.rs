@@ -18,6 +18,8 @@ fn test_params() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -25,15 +27,19 @@ fn test_params() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: Vec::new(),
                         span: Span::new(19, 25),
                     },
@@ -41,6 +47,7 @@ fn test_params() {
                 span: Span::new(17, 28),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 28),
         })],
         pest::Span::new(source, 0, 28).unwrap(),
@@ -67,6 +74,8 @@ fn test_more_function_args_as_ident() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -74,21 +83,26 @@ fn test_more_function_args_as_ident() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Ident(Ident {
                                     ident: "arg1".to_owned(),
                                     span: Span::new(24, 28),
                                 })),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 28),
                             },
@@ -97,6 +111,7 @@ fn test_more_function_args_as_ident() {
                                     ident: "arg2".to_owned(),
                                     span: Span::new(30, 34),
                                 })),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(30, 34),
                             },
@@ -105,6 +120,7 @@ fn test_more_function_args_as_ident() {
                                     ident: "arg3".to_owned(),
                                     span: Span::new(36, 40),
                                 })),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(36, 40),
                             },
@@ -115,6 +131,7 @@ fn test_more_function_args_as_ident() {
                 span: Span::new(17, 44),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 44),
         })],
         pest::Span::new(source, 0, 44).unwrap(),
@@ -141,6 +158,8 @@ fn test_more_function_args_as_string() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -148,20 +167,25 @@ fn test_more_function_args_as_string() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::String("arg1".to_owned(), Span::new(24, 30)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 30),
                             },
@@ -169,6 +193,7 @@ fn test_more_function_args_as_string() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::String("arg2".to_owned(), Span::new(32, 38)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(32, 38),
                             },
@@ -176,6 +201,7 @@ fn test_more_function_args_as_string() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::String("arg3".to_owned(), Span::new(40, 46)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(40, 46),
                             },
@@ -186,6 +212,7 @@ fn test_more_function_args_as_string() {
                 span: Span::new(17, 50),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 50),
         })],
         pest::Span::new(source, 0, 50).unwrap(),
@@ -212,6 +239,8 @@ fn test_more_function_args_as_int() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -219,20 +248,25 @@ fn test_more_function_args_as_int() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Int(1.into(), Span::new(24, 25)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 25),
                             },
@@ -240,6 +274,7 @@ fn test_more_function_args_as_int() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Int(2.into(), Span::new(27, 28)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(27, 28),
                             },
@@ -247,6 +282,7 @@ fn test_more_function_args_as_int() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Int(3.into(), Span::new(30, 31)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(30, 31),
                             },
@@ -257,6 +293,7 @@ fn test_more_function_args_as_int() {
                 span: Span::new(17, 35),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 35),
         })],
         pest::Span::new(source, 0, 35).unwrap(),
@@ -283,6 +320,8 @@ fn test_more_function_args_as_float() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -290,15 +329,19 @@ fn test_more_function_args_as_float() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
@@ -307,6 +350,7 @@ fn test_more_function_args_as_float() {
                                         Span::new(24, 27),
                                     ),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 27),
                             },
@@ -317,6 +361,7 @@ fn test_more_function_args_as_float() {
                                         Span::new(29, 32),
                                     ),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(29, 32),
                             },
@@ -327,6 +372,7 @@ fn test_more_function_args_as_float() {
                                         Span::new(34, 37),
                                     ),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(34, 37),
                             },
@@ -337,6 +383,7 @@ fn test_more_function_args_as_float() {
                 span: Span::new(17, 41),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 41),
         })],
         pest::Span::new(source, 0, 41).unwrap(),
@@ -363,6 +410,8 @@ fn test_more_function_args_as_bool() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -370,20 +419,25 @@ fn test_more_function_args_as_bool() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Bool(true, Span::new(24, 28)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 28),
                             },
@@ -391,6 +445,7 @@ fn test_more_function_args_as_bool() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Bool(false, Span::new(30, 35)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(30, 35),
                             },
@@ -398,6 +453,7 @@ fn test_more_function_args_as_bool() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Bool(true, Span::new(37, 41)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(37, 41),
                             },
@@ -408,6 +464,7 @@ fn test_more_function_args_as_bool() {
                 span: Span::new(17, 45),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 45),
         })],
         pest::Span::new(source, 0, 45).unwrap(),
@@ -434,6 +491,8 @@ fn test_more_function_args_as_nil() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -441,20 +500,25 @@ fn test_more_function_args_as_nil() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Nil(Span::new(24, 27)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 27),
                             },
@@ -462,6 +526,7 @@ fn test_more_function_args_as_nil() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Nil(Span::new(29, 32)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(29, 32),
                             },
@@ -469,6 +534,7 @@ fn test_more_function_args_as_nil() {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
                                     ObjectExpression::Nil(Span::new(34, 37)),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(34, 37),
                             },
@@ -479,6 +545,7 @@ fn test_more_function_args_as_nil() {
                 span: Span::new(17, 41),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 41),
         })],
         pest::Span::new(source, 0, 41).unwrap(),
@@ -505,6 +572,8 @@ fn test_more_function_args_as_array() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -512,15 +581,19 @@ fn test_more_function_args_as_array() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
                 statements: vec![Statement::Expression(ExpressionStatement::FunctionCall(
                     FunctionCallExpression {
-                        callable: ValueExpression::Ident(Ident {
-                            ident: "func".to_owned(),
-                            span: Span::new(19, 23),
-                        }),
+                        callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(
+                            Ident {
+                                ident: "func".to_owned(),
+                                span: Span::new(19, 23),
+                            },
+                        ))),
                         args: vec![
                             Arg {
                                 expr: ExpressionStatement::Value(ValueExpression::Object(
@@ -539,6 +612,7 @@ fn test_more_function_args_as_array() {
                                         Span::new(24, 31),
                                     ),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(24, 31),
                             },
@@ -559,6 +633,7 @@ fn test_more_function_args_as_array() {
                                         Span::new(33, 40),
                                     ),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(33, 40),
                             },
@@ -579,6 +654,7 @@ fn test_more_function_args_as_array() {
                                         Span::new(42, 49),
                                     ),
                                 )),
+                                name: None,
                                 is_unpack: false,
                                 span: Span::new(42, 49),
                             },
@@ -589,6 +665,7 @@ fn test_more_function_args_as_array() {
                 span: Span::new(17, 53),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 53),
         })],
         pest::Span::new(source, 0, 53).unwrap(),
@@ -615,6 +692,8 @@ fn test_more_function_params() {
                         span: Span::new(5, 7),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -622,6 +701,8 @@ fn test_more_function_params() {
                         span: Span::new(9, 11),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -629,6 +710,8 @@ fn test_more_function_params() {
                         span: Span::new(13, 15),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -636,6 +719,8 @@ fn test_more_function_params() {
                         span: Span::new(18, 20),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -643,6 +728,8 @@ fn test_more_function_params() {
                         span: Span::new(23, 25),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -650,6 +737,7 @@ fn test_more_function_params() {
                 span: Span::new(27, 31),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 31),
         })],
         pest::Span::new(source, 0, 31).unwrap(),
@@ -658,6 +746,60 @@ fn test_more_function_params() {
     assert_eq!(ast.unwrap(), program);
 }
 
+#[test]
+fn test_call_expression_result() {
+    let source = "~main<argc><argv>{<(func<1>)<2>;>}";
+    let ast = OYParser::parse_program(source);
+    let int = |value: i32, start: usize, end: usize| {
+        ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Int(
+            value.into(),
+            Span::new(start, end),
+        )))
+    };
+    let statement = match ast.unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(mut block),
+            ..
+        }) => block.statements.remove(0),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        statement,
+        Statement::Expression(ExpressionStatement::FunctionCall(FunctionCallExpression {
+            callable: Box::new(ExpressionStatement::FunctionCall(FunctionCallExpression {
+                callable: Box::new(ExpressionStatement::Value(ValueExpression::Ident(Ident {
+                    ident: "func".to_owned(),
+                    span: Span::new(20, 24),
+                }))),
+                args: vec![Arg {
+                    expr: int(1, 25, 26),
+                    name: None,
+                    is_unpack: false,
+                    span: Span::new(25, 26),
+                }],
+                span: Span::new(20, 27),
+            })),
+            args: vec![Arg {
+                expr: int(2, 29, 30),
+                name: None,
+                is_unpack: false,
+                span: Span::new(29, 30),
+            }],
+            span: Span::new(19, 31),
+        }))
+    );
+}
+
+#[test]
+fn test_invalid_call_expression() {
+    let source = "~main<argc><argv>{<()<2>;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+    let source = "~main<argc><argv>{<(func<1>;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
 #[test]
 fn test_invalid_function_params() {
     let source = "~foo<p1, p2, p3>{<>}";
@@ -684,6 +826,94 @@ fn test_invalid_function_args() {
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
 
+#[test]
+fn test_param_default() {
+    let source = "~foo<a><b = 1 + 2><*c>{<>}";
+    let params = match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(function) => function.params,
+        _ => unreachable!(),
+    };
+    assert_eq!(params[0].default, None);
+    assert!(matches!(
+        &params[1].default,
+        Some(ExpressionStatement::Binary(BinaryExpression { span, .. })) if *span == Span::new(12, 17)
+    ));
+    assert!(params[2].is_pack && params[2].default.is_none());
+}
+
+#[test]
+fn test_named_args() {
+    let source = "~main<argc><argv>{<foo<1><b = 2>;>}";
+    let args = match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(mut block),
+            ..
+        }) => match block.statements.remove(0) {
+            Statement::Expression(ExpressionStatement::FunctionCall(func_call)) => func_call.args,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        args[1],
+        Arg {
+            expr: ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Int(
+                2.into(),
+                Span::new(30, 31)
+            ))),
+            name: Some(Ident {
+                ident: "b".to_owned(),
+                span: Span::new(26, 27),
+            }),
+            is_unpack: false,
+            span: Span::new(26, 31),
+        }
+    );
+    assert_eq!(args[0].name, None);
+}
+
+#[test]
+fn test_invalid_default_params() {
+    let source = "~foo<a = 1><b>{<>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(
+        ast.unwrap_err().kind,
+        ErrorKind::RequiredParamAfterDefault(param, default) if param == "b" && default == "a"
+    ));
+    let source = "~foo<a><*b = []>{<>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(
+        ast.unwrap_err().kind,
+        ErrorKind::PackedParamWithDefault(param) if param == "b"
+    ));
+    for source in ["~foo<a =>{<>}", "~foo<a = 1 = 2>{<>}", "~foo<a == 1>{<>}"] {
+        let ast = OYParser::parse_program(source);
+        assert!(
+            matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_invalid_named_args() {
+    // The positional arguments can't be after the named arguments.
+    for source in [
+        "~main<argc><argv>{<foo<a = 1><2>;>}",
+        "~main<argc><argv>{<foo<...a = 1>;>}",
+        "~main<argc><argv>{<foo<a = >;>}",
+    ] {
+        let ast = OYParser::parse_program(source);
+        assert!(
+            matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)),
+            "{source}"
+        );
+    }
+    let source = "~main<argc><argv>{<foo<firstArg = 1>;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)));
+}
+
 #[test]
 fn test_invalid_function_with_double_function_tag() {
     let source = "~~main<argc><argv>{<>}";
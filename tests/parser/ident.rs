@@ -21,6 +21,8 @@ fn test_single_ident() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -28,6 +30,8 @@ fn test_single_ident() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -40,6 +44,7 @@ fn test_single_ident() {
                 span: Span::new(17, 27),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 27),
         })],
         pest::Span::new(source, 0, 27).unwrap(),
@@ -66,6 +71,8 @@ fn test_ident_with_underscore() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -73,6 +80,8 @@ fn test_ident_with_underscore() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -85,6 +94,7 @@ fn test_ident_with_underscore() {
                 span: Span::new(17, 32),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 32),
         })],
         pest::Span::new(source, 0, 32).unwrap(),
@@ -111,6 +121,8 @@ fn test_ident_start_with_underscore() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -118,6 +130,8 @@ fn test_ident_start_with_underscore() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -130,6 +144,7 @@ fn test_ident_start_with_underscore() {
                 span: Span::new(17, 28),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 28),
         })],
         pest::Span::new(source, 0, 28).unwrap(),
@@ -156,6 +171,8 @@ fn test_ident_end_with_underscore() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -163,6 +180,8 @@ fn test_ident_end_with_underscore() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -175,6 +194,7 @@ fn test_ident_end_with_underscore() {
                 span: Span::new(17, 28),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 28),
         })],
         pest::Span::new(source, 0, 28).unwrap(),
@@ -201,6 +221,8 @@ fn test_ident_with_multiple_underscore() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -208,6 +230,8 @@ fn test_ident_with_multiple_underscore() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -220,6 +244,7 @@ fn test_ident_with_multiple_underscore() {
                 span: Span::new(17, 33),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 33),
         })],
         pest::Span::new(source, 0, 33).unwrap(),
@@ -246,6 +271,8 @@ fn test_ident_with_number() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -253,6 +280,8 @@ fn test_ident_with_number() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -265,6 +294,7 @@ fn test_ident_with_number() {
                 span: Span::new(17, 33),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 33),
         })],
         pest::Span::new(source, 0, 33).unwrap(),
@@ -291,6 +321,8 @@ fn test_ident_with_underscore_and_number() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -298,6 +330,8 @@ fn test_ident_with_underscore_and_number() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -310,6 +344,7 @@ fn test_ident_with_underscore_and_number() {
                 span: Span::new(17, 34),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 34),
         })],
         pest::Span::new(source, 0, 34).unwrap(),
@@ -336,6 +371,8 @@ fn test_ident_with_underscore_and_tow_number() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -343,6 +380,8 @@ fn test_ident_with_underscore_and_tow_number() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -355,6 +394,7 @@ fn test_ident_with_underscore_and_tow_number() {
                 span: Span::new(17, 36),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 36),
         })],
         pest::Span::new(source, 0, 36).unwrap(),
@@ -381,6 +421,8 @@ fn test_ident_with_underscore_and_tow_number_and_underscore() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -388,6 +430,8 @@ fn test_ident_with_underscore_and_tow_number_and_underscore() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -400,6 +444,7 @@ fn test_ident_with_underscore_and_tow_number_and_underscore() {
                 span: Span::new(17, 37),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 37),
         })],
         pest::Span::new(source, 0, 37).unwrap(),
@@ -426,6 +471,8 @@ fn test_ident_with_underscore_and_tow_number_and_underscore_and_number() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -433,6 +480,8 @@ fn test_ident_with_underscore_and_tow_number_and_underscore_and_number() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -445,6 +494,7 @@ fn test_ident_with_underscore_and_tow_number_and_underscore_and_number() {
                 span: Span::new(17, 38),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 38),
         })],
         pest::Span::new(source, 0, 38).unwrap(),
@@ -469,21 +519,22 @@ fn test_invalid_ident_start_with_number() {
 
 #[test]
 fn test_invalid_ident_by_dash() {
-    let source = "~main<argc><argv>{<some-ident;>}";
+    let source = "~main<argc><argv>{<some-ident = 1;>}";
     let ast = OYParser::parse_program(source);
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
 
 #[test]
 fn test_invalid_ident_by_dot() {
-    let source = "~main<argc><argv>{<some.ident;>}";
+    // The dot after a value is a field access, so the invalid ident is a function name.
+    let source = "~main<argc><argv>{<~some.ident<>{<>};>}";
     let ast = OYParser::parse_program(source);
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
 
 #[test]
 fn test_invalid_ident_by_slash() {
-    let source = "~main<argc><argv>{<some/ident;>}";
+    let source = "~main<argc><argv>{<some/ident = 1;>}";
     let ast = OYParser::parse_program(source);
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
@@ -497,21 +548,21 @@ fn test_invalid_ident_by_backslash() {
 
 #[test]
 fn test_invalid_ident_by_plus() {
-    let source = "~main<argc><argv>{<some+ident;>}";
+    let source = "~main<argc><argv>{<some+ident = 1;>}";
     let ast = OYParser::parse_program(source);
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
 
 #[test]
 fn test_invalid_ident_by_star() {
-    let source = "~main<argc><argv>{<some*ident;>}";
+    let source = "~main<argc><argv>{<some*ident = 1;>}";
     let ast = OYParser::parse_program(source);
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
 
 #[test]
 fn test_invalid_ident_by_percent() {
-    let source = "~main<argc><argv>{<some%ident;>}";
+    let source = "~main<argc><argv>{<some%ident = 1;>}";
     let ast = OYParser::parse_program(source);
     assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
 }
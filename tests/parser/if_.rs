@@ -0,0 +1,119 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+/// Returns the statements of the main function.
+fn main_statements(source: &str) -> Vec<Statement> {
+    match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(block), ..
+        }) => block.statements,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_if() {
+    let source = "~main<argc><argv>{<if true {<return 1;>};>}";
+    let statements = main_statements(source);
+
+    assert_eq!(
+        statements,
+        vec![Statement::If(IfStatement {
+            condition: ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Bool(
+                true,
+                Span::new(22, 26)
+            ),)),
+            block: Block {
+                statements: vec![Statement::Return(ReturnStatement {
+                    value: ExpressionStatement::Value(ValueExpression::Object(
+                        ObjectExpression::Int(1.into(), Span::new(36, 37)),
+                    )),
+                    span: Span::new(29, 37),
+                })],
+                span: Span::new(27, 40),
+            },
+            else_ifs: Vec::new(),
+            else_block: None,
+            span: Span::new(19, 40),
+        })]
+    );
+}
+
+#[test]
+fn test_if_else_if_else() {
+    let source = "~main<argc><argv>{<if a {<>} else if b {<>} else if c {<>} else {<>};>}";
+    let statements = main_statements(source);
+
+    assert_eq!(
+        statements,
+        vec![Statement::If(IfStatement {
+            condition: ExpressionStatement::Value(ValueExpression::Ident(Ident {
+                ident: "a".to_owned(),
+                span: Span::new(22, 23),
+            })),
+            block: Block {
+                statements: Vec::new(),
+                span: Span::new(24, 28),
+            },
+            else_ifs: vec![
+                ElseIf {
+                    condition: ExpressionStatement::Value(ValueExpression::Ident(Ident {
+                        ident: "b".to_owned(),
+                        span: Span::new(37, 38),
+                    })),
+                    block: Block {
+                        statements: Vec::new(),
+                        span: Span::new(39, 43),
+                    },
+                    span: Span::new(29, 43),
+                },
+                ElseIf {
+                    condition: ExpressionStatement::Value(ValueExpression::Ident(Ident {
+                        ident: "c".to_owned(),
+                        span: Span::new(52, 53),
+                    })),
+                    block: Block {
+                        statements: Vec::new(),
+                        span: Span::new(54, 58),
+                    },
+                    span: Span::new(44, 58),
+                },
+            ],
+            else_block: Some(Block {
+                statements: Vec::new(),
+                span: Span::new(64, 68),
+            }),
+            span: Span::new(19, 68),
+        })]
+    );
+}
+
+#[test]
+fn test_ident_with_keyword_prefix() {
+    let source = "~main<argc><argv>{<if_value = 1; else_value = 2; return_value = 3;>}";
+    let statements = main_statements(source);
+    assert_eq!(statements.len(), 3);
+    assert!(statements
+        .iter()
+        .all(|statement| matches!(statement, Statement::Assignment(_))));
+}
+
+#[test]
+fn test_if_without_block() {
+    let source = "~main<argc><argv>{<if true;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_else_without_if() {
+    let source = "~main<argc><argv>{<else {<>};>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_if_as_ident() {
+    let source = "~main<argc><argv>{<if = 1;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
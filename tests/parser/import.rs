@@ -0,0 +1,50 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+#[test]
+fn test_import() {
+    let source = "import \"math.oy\";\nimport \"../utils/strings.oy\" ;\n~main<argc><argv>{<>}";
+    let program = OYParser::parse_program(source).unwrap().0;
+    assert_eq!(
+        program[..2],
+        [
+            Statement::Import(ImportStatement {
+                path: "math.oy".to_owned(),
+                span: Span::new(0, 17),
+            }),
+            Statement::Import(ImportStatement {
+                path: "../utils/strings.oy".to_owned(),
+                span: Span::new(18, 48),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_import_after_function() {
+    let source = "~main<argc><argv>{<>}\nimport \"math.oy\";";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_import_in_block() {
+    let source = "~main<argc><argv>{<import \"math.oy\";>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_import_without_path() {
+    for source in [
+        "import;\n~main<argc><argv>{<>}",
+        "import math;\n~main<argc><argv>{<>}",
+        "import \"math.oy\"\n~main<argc><argv>{<>}",
+    ] {
+        let ast = OYParser::parse_program(source);
+        assert!(
+            matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)),
+            "{}",
+            source
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+/// Returns the statements of the main function.
+fn main_statements(source: &str) -> Vec<Statement> {
+    match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(block), ..
+        }) => block.statements,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_loop() {
+    let source = "~main<argc><argv>{<loop {<break;>};>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Expression(ExpressionStatement::Loop(
+            LoopExpression {
+                kind: LoopKind::Loop,
+                block: Block {
+                    statements: vec![Statement::Break(BreakStatement {
+                        value: None,
+                        span: Span::new(26, 31),
+                    })],
+                    span: Span::new(24, 34),
+                },
+                span: Span::new(19, 34),
+            }
+        ))]
+    );
+}
+
+#[test]
+fn test_while() {
+    let source = "~main<argc><argv>{<while x {<continue;>};>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Expression(ExpressionStatement::Loop(
+            LoopExpression {
+                kind: LoopKind::While(Box::new(ExpressionStatement::Value(
+                    ValueExpression::Ident(Ident {
+                        ident: "x".to_owned(),
+                        span: Span::new(25, 26),
+                    })
+                ))),
+                block: Block {
+                    statements: vec![Statement::Continue(ContinueStatement {
+                        span: Span::new(29, 37),
+                    })],
+                    span: Span::new(27, 40),
+                },
+                span: Span::new(19, 40),
+            }
+        ))]
+    );
+}
+
+#[test]
+fn test_assign_loop_with_break_value() {
+    let source = "~main<argc><argv>{<x = loop {<break 1;>};>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Assignment(AssignmentStatement {
+            ident: Ident {
+                ident: "x".to_owned(),
+                span: Span::new(19, 20),
+            },
+            expression: ExpressionStatement::Loop(LoopExpression {
+                kind: LoopKind::Loop,
+                block: Block {
+                    statements: vec![Statement::Break(BreakStatement {
+                        value: Some(ExpressionStatement::Value(ValueExpression::Object(
+                            ObjectExpression::Int(1.into(), Span::new(36, 37))
+                        ))),
+                        span: Span::new(30, 37),
+                    })],
+                    span: Span::new(28, 40),
+                },
+                span: Span::new(23, 40),
+            }),
+            type_annotation: None,
+            span: Span::new(19, 40),
+        })]
+    );
+}
+
+#[test]
+fn test_ident_with_keyword_prefix() {
+    let source = "~main<argc><argv>{<breakfast; continued; looping; whilst; returned;>}";
+    assert!(main_statements(source).iter().all(|statement| matches!(
+        statement,
+        Statement::Expression(ExpressionStatement::Value(ValueExpression::Ident(_)))
+    )));
+}
+
+#[test]
+fn test_loop_as_argument() {
+    let source = "~main<argc><argv>{<foo<loop {<break;>}>;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_break_outside_loop() {
+    let source = "~main<argc><argv>{<break;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::OutsideLoop(..)));
+
+    let source = "~main<argc><argv>{<loop {<~foo{<continue;>};>};>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::OutsideLoop(..)));
+}
+
+#[test]
+fn test_for() {
+    let source = "~main<argc><argv>{<for item in list {<>};>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Expression(ExpressionStatement::Loop(
+            LoopExpression {
+                kind: LoopKind::For(
+                    Ident {
+                        ident: "item".to_owned(),
+                        span: Span::new(23, 27),
+                    },
+                    Box::new(ExpressionStatement::Value(ValueExpression::Ident(Ident {
+                        ident: "list".to_owned(),
+                        span: Span::new(31, 35),
+                    })))
+                ),
+                block: Block {
+                    statements: Vec::new(),
+                    span: Span::new(36, 40),
+                },
+                span: Span::new(19, 40),
+            }
+        ))]
+    );
+}
+
+#[test]
+fn test_for_invalid_ident() {
+    let source = "~main<argc><argv>{<for Item in list {<>};>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)));
+
+    let source = "~main<argc><argv>{<for in in list {<>};>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
@@ -0,0 +1,228 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser, warnings};
+
+/// Returns the returned expression of the main function.
+fn returned_expression(source: &str) -> ExpressionStatement {
+    match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(mut block),
+            ..
+        }) => match block.statements.remove(0) {
+            Statement::Return(return_stmt) => return_stmt.value,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Returns the patterns of the returned match expression.
+fn patterns(source: &str) -> Vec<Pattern> {
+    match returned_expression(source) {
+        ExpressionStatement::Match(match_expr) => {
+            match_expr.arms.into_iter().map(|arm| arm.pattern).collect()
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn int(value: i32, start: usize, end: usize) -> ExpressionStatement {
+    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Int(
+        value.into(),
+        Span::new(start, end),
+    )))
+}
+
+fn ident(name: &str, start: usize, end: usize) -> Ident {
+    Ident {
+        ident: name.to_owned(),
+        span: Span::new(start, end),
+    }
+}
+
+#[test]
+fn test_match() {
+    let source = "~main<argc><argv>{<return match x {< 1 => 2, n if n => 3 >};>}";
+    assert_eq!(
+        returned_expression(source),
+        ExpressionStatement::Match(MatchExpression {
+            value: Box::new(ExpressionStatement::Value(ValueExpression::Ident(ident(
+                "x", 32, 33
+            )))),
+            arms: vec![
+                MatchArm {
+                    pattern: Pattern::Literal(ObjectExpression::Int(1.into(), Span::new(37, 38))),
+                    guard: None,
+                    expression: int(2, 42, 43),
+                    span: Span::new(37, 43),
+                },
+                MatchArm {
+                    pattern: Pattern::Ident(ident("n", 45, 46)),
+                    guard: Some(ExpressionStatement::Value(ValueExpression::Ident(ident(
+                        "n", 50, 51
+                    )))),
+                    expression: int(3, 55, 56),
+                    span: Span::new(45, 56),
+                },
+            ],
+            span: Span::new(26, 59),
+        })
+    );
+}
+
+#[test]
+fn test_literal_patterns() {
+    let source = r#"~main<argc><argv>{<return match x {< nil => 1, -2 => 1, 1.5 => 1, "a" => 1, true => 1 >};>}"#;
+    assert_eq!(
+        patterns(source),
+        vec![
+            Pattern::Literal(ObjectExpression::Nil(Span::new(37, 40))),
+            Pattern::Literal(ObjectExpression::Int((-2).into(), Span::new(47, 49))),
+            Pattern::Literal(ObjectExpression::Float(
+                "1.5".parse().unwrap(),
+                Span::new(56, 59)
+            )),
+            Pattern::Literal(ObjectExpression::String("a".to_owned(), Span::new(66, 69))),
+            Pattern::Literal(ObjectExpression::Bool(true, Span::new(76, 80))),
+        ]
+    );
+}
+
+#[test]
+fn test_array_patterns() {
+    let source = "~main<argc><argv>{<return match x {< [] => 1, [_, a] => 1, [a, *rest] => 1, [*_] => 1 >};>}";
+    assert_eq!(
+        patterns(source),
+        vec![
+            Pattern::Array(Vec::new(), None, Span::new(37, 39)),
+            Pattern::Array(
+                vec![
+                    Pattern::Wildcard(Span::new(47, 48)),
+                    Pattern::Ident(ident("a", 50, 51)),
+                ],
+                None,
+                Span::new(46, 52)
+            ),
+            Pattern::Array(
+                vec![Pattern::Ident(ident("a", 60, 61))],
+                Some(Box::new(Pattern::Ident(ident("rest", 64, 68)))),
+                Span::new(59, 69)
+            ),
+            Pattern::Array(
+                Vec::new(),
+                Some(Box::new(Pattern::Wildcard(Span::new(78, 79)))),
+                Span::new(76, 80)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_variant_patterns() {
+    let source =
+        "~main<argc><argv>{<return match x {< None => 1, Some<[a, _]> => 1, Pair<1><b> => 1 >};>}";
+    assert_eq!(
+        patterns(source),
+        vec![
+            Pattern::Variant(ident("None", 37, 41), Vec::new(), Span::new(37, 41)),
+            Pattern::Variant(
+                ident("Some", 48, 52),
+                vec![Pattern::Array(
+                    vec![
+                        Pattern::Ident(ident("a", 54, 55)),
+                        Pattern::Wildcard(Span::new(57, 58)),
+                    ],
+                    None,
+                    Span::new(53, 59)
+                )],
+                Span::new(48, 60)
+            ),
+            Pattern::Variant(
+                ident("Pair", 67, 71),
+                vec![
+                    Pattern::Literal(ObjectExpression::Int(1.into(), Span::new(72, 73))),
+                    Pattern::Ident(ident("b", 75, 76)),
+                ],
+                Span::new(67, 77)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_match_in_argument() {
+    let source = r#"~main<argc><argv>{<return println<match x {< _ => "a" >}>;>}"#;
+    assert!(matches!(
+        returned_expression(source),
+        ExpressionStatement::FunctionCall(FunctionCallExpression { args, .. })
+            if matches!(args[0].expr, ExpressionStatement::Match(_))
+    ));
+}
+
+#[test]
+fn test_invalid_match() {
+    for source in [
+        "~main<argc><argv>{<return match x {< >};>}",
+        "~main<argc><argv>{<return match x {< 1 >};>}",
+        "~main<argc><argv>{<return match x {< 1 => 2 3 => 4 >};>}",
+        "~main<argc><argv>{<return match x {< [*rest, a] => 1 >};>}",
+        "~main<argc><argv>{<return match x {< [*a, *b] => 1 >};>}",
+        "~main<argc><argv>{<return match x {< 1 + 2 => 1 >};>}",
+        "~main<argc><argv>{<match = 1;>}",
+    ] {
+        let ast = OYParser::parse_program(source);
+        assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+    }
+}
+
+#[test]
+fn test_invalid_binding_name() {
+    let source = "~main<argc><argv>{<return match x {< firstItem => 1 >};>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)));
+}
+
+#[test]
+fn test_invalid_variant_pattern_name() {
+    for source in [
+        "~main<argc><argv>{<return match x {< SOME_VALUE => 1 >};>}",
+        "~main<argc><argv>{<return match x {< some<a> => 1 >};>}",
+    ] {
+        let ast = OYParser::parse_program(source);
+        assert!(ast.is_err(), "{source}");
+    }
+}
+
+#[test]
+fn test_unreachable_arms() {
+    let source = "~main<argc><argv>{<return match x {< n => 1, 2 => 2 >};>}";
+    let program = OYParser::parse_program(source).unwrap();
+    let warnings = warnings::check(&program.0);
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0].kind, ErrorKind::UnreachableArm(span) if span == (37, 6)));
+    assert_eq!(warnings[0].span, (45, 6).into());
+
+    for source in [
+        "~main<argc><argv>{<return match x {< 1 => 1, 1 => 2 >};>}",
+        "~main<argc><argv>{<return match x {< [_, *r] => 1, [a, b] => 2 >};>}",
+        "~main<argc><argv>{<return match x {< [a, 1] => 1, [b, 1] => 2 >};>}",
+        "~main<argc><argv>{<~foo{< return match x {< _ => 1, _ => 2 >}; >}; return 0;>}",
+        "~main<argc><argv>{<return match x {< Some<_> => 1, Some<1> => 2 >};>}",
+    ] {
+        let program = OYParser::parse_program(source).unwrap();
+        assert_eq!(warnings::check(&program.0).len(), 1, "{source}");
+    }
+}
+
+#[test]
+fn test_reachable_arms() {
+    for source in [
+        "~main<argc><argv>{<return match x {< n if n => 1, 2 => 2 >};>}",
+        "~main<argc><argv>{<return match x {< 1 => 1, 2 => 2, _ => 3 >};>}",
+        "~main<argc><argv>{<return match x {< [a] => 1, [a, b] => 2 >};>}",
+        "~main<argc><argv>{<return match x {< [a, b] => 1, [a, *b] => 2 >};>}",
+        "~main<argc><argv>{<return match x {< [1, *r] => 1, [2] => 2 >};>}",
+        "~main<argc><argv>{<return match x {< Some<1> => 1, Some<_> => 2, None => 3 >};>}",
+    ] {
+        let program = OYParser::parse_program(source).unwrap();
+        assert!(warnings::check(&program.0).is_empty(), "{source}");
+    }
+}
@@ -1,8 +1,20 @@
+mod annotations;
 mod assign;
 mod block;
+mod borrow;
 mod comment;
+mod enums;
 mod function;
 mod ident;
+mod if_;
+mod import;
+mod loops;
+mod match_;
+mod operators;
+mod ownership;
+mod records;
+mod resolver;
 mod return_;
+mod try_;
 mod types;
 mod visibility;
@@ -0,0 +1,178 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+/// Returns the returned expression of the main function.
+fn returned_expression(source: &str) -> ExpressionStatement {
+    match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(mut block),
+            ..
+        }) => match block.statements.remove(0) {
+            Statement::Return(return_stmt) => return_stmt.value,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn int(value: i32, start: usize, end: usize) -> Box<ExpressionStatement> {
+    Box::new(ExpressionStatement::Value(ValueExpression::Object(
+        ObjectExpression::Int(value.into(), Span::new(start, end)),
+    )))
+}
+
+#[test]
+fn test_precedence() {
+    let source = "~main<argc><argv>{<return 1 + 2 * 3;>}";
+    assert_eq!(
+        returned_expression(source),
+        ExpressionStatement::Binary(BinaryExpression {
+            left: int(1, 26, 27),
+            operator: BinaryOperator::Add,
+            right: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                left: int(2, 30, 31),
+                operator: BinaryOperator::Mul,
+                right: int(3, 34, 35),
+                span: Span::new(30, 35),
+            })),
+            span: Span::new(26, 35),
+        })
+    );
+}
+
+#[test]
+fn test_left_associativity() {
+    let source = "~main<argc><argv>{<return 1 - 2 - 3;>}";
+    assert_eq!(
+        returned_expression(source),
+        ExpressionStatement::Binary(BinaryExpression {
+            left: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                left: int(1, 26, 27),
+                operator: BinaryOperator::Sub,
+                right: int(2, 30, 31),
+                span: Span::new(26, 31),
+            })),
+            operator: BinaryOperator::Sub,
+            right: int(3, 34, 35),
+            span: Span::new(26, 35),
+        })
+    );
+}
+
+#[test]
+fn test_parentheses_and_negation() {
+    let source = "~main<argc><argv>{<return -(1 % 2);>}";
+    assert_eq!(
+        returned_expression(source),
+        ExpressionStatement::Unary(UnaryExpression {
+            operator: UnaryOperator::Neg,
+            operand: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                left: int(1, 28, 29),
+                operator: BinaryOperator::Mod,
+                right: int(2, 32, 33),
+                span: Span::new(28, 33),
+            })),
+            span: Span::new(26, 33),
+        })
+    );
+}
+
+#[test]
+fn test_operator_in_argument() {
+    let source = "~main<argc><argv>{<return foo<1 / 2>;>}";
+    assert!(matches!(
+        returned_expression(source),
+        ExpressionStatement::FunctionCall(FunctionCallExpression { args, .. })
+            if matches!(args[0].expr, ExpressionStatement::Binary(_))
+    ));
+}
+
+#[test]
+fn test_missing_operand() {
+    let source = "~main<argc><argv>{<return 1 +;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+
+    let source = "~main<argc><argv>{<return (1 + 2;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+fn ident(name: &str, start: usize, end: usize) -> Box<ExpressionStatement> {
+    Box::new(ExpressionStatement::Value(ValueExpression::Ident(Ident {
+        ident: name.to_owned(),
+        span: Span::new(start, end),
+    })))
+}
+
+#[test]
+fn test_logic_precedence() {
+    let source = "~main<argc><argv>{<return a < b && c > d || !e;>}";
+    assert_eq!(
+        returned_expression(source),
+        ExpressionStatement::Binary(BinaryExpression {
+            left: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                left: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                    left: ident("a", 26, 27),
+                    operator: BinaryOperator::Lt,
+                    right: ident("b", 30, 31),
+                    span: Span::new(26, 31),
+                })),
+                operator: BinaryOperator::And,
+                right: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                    left: ident("c", 35, 36),
+                    operator: BinaryOperator::Gt,
+                    right: ident("d", 39, 40),
+                    span: Span::new(35, 40),
+                })),
+                span: Span::new(26, 40),
+            })),
+            operator: BinaryOperator::Or,
+            right: Box::new(ExpressionStatement::Unary(UnaryExpression {
+                operator: UnaryOperator::Not,
+                operand: ident("e", 45, 46),
+                span: Span::new(44, 46),
+            })),
+            span: Span::new(26, 46),
+        })
+    );
+}
+
+#[test]
+fn test_equality_precedence() {
+    let source = "~main<argc><argv>{<return 1 + 1 == 2;>}";
+    assert_eq!(
+        returned_expression(source),
+        ExpressionStatement::Binary(BinaryExpression {
+            left: Box::new(ExpressionStatement::Binary(BinaryExpression {
+                left: int(1, 26, 27),
+                operator: BinaryOperator::Add,
+                right: int(1, 30, 31),
+                span: Span::new(26, 31),
+            })),
+            operator: BinaryOperator::Eq,
+            right: int(2, 35, 36),
+            span: Span::new(26, 36),
+        })
+    );
+}
+
+#[test]
+fn test_comparison_in_argument() {
+    let source = "~main<argc><argv>{<return foo<(a > b)>;>}";
+    assert!(matches!(
+        returned_expression(source),
+        ExpressionStatement::FunctionCall(FunctionCallExpression { args, .. })
+            if matches!(args[0].expr, ExpressionStatement::Binary(BinaryExpression { operator: BinaryOperator::Gt, .. }))
+    ));
+
+    let source = "~main<argc><argv>{<return foo<a == b>;>}";
+    assert!(matches!(
+        returned_expression(source),
+        ExpressionStatement::FunctionCall(FunctionCallExpression { args, .. })
+            if matches!(args[0].expr, ExpressionStatement::Binary(BinaryExpression { operator: BinaryOperator::Eq, .. }))
+    ));
+
+    let source = "~main<argc><argv>{<return foo<a > b>;>}";
+    let ast = OYParser::parse_program(source);
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
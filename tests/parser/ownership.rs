@@ -0,0 +1,219 @@
+use ocypode_lang::{
+    errors::{Error, ErrorKind},
+    ownership,
+    parser::OYParser,
+};
+
+/// Returns the ownership errors of the given source code, without the warnings.
+fn check(source: &str) -> Vec<Error> {
+    check_all(source)
+        .into_iter()
+        .filter(|error| !error.is_warning())
+        .collect()
+}
+
+/// Returns the ownership errors and warnings of the given source code.
+fn check_all(source: &str) -> Vec<Error> {
+    ownership::check(&OYParser::parse_program(source).unwrap().0)
+}
+
+#[test]
+fn test_use_after_move() {
+    let source = "~main<argc><argv>{<x = 1; y = x; return x;>}";
+    let errors = check(source);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0].kind, ErrorKind::UseAfterMove(name, (30, 1)) if name == "x"));
+    assert_eq!(errors[0].span, (40, 1).into());
+}
+
+#[test]
+fn test_all_errors() {
+    let source = "~add<a><b>{< return a + b; >}
+    ~main<argc><argv>{<
+        x = 1;
+        y = add<x><x>;
+        println<y><y>;
+        return z;
+    >}";
+    let errors = check(source);
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(&errors[0].kind, ErrorKind::UseAfterMove(name, _) if name == "x"));
+    assert!(matches!(&errors[1].kind, ErrorKind::UseAfterMove(name, _) if name == "y"));
+    assert!(matches!(&errors[2].kind, ErrorKind::UnDeclaredIdent(name) if name == "z"));
+}
+
+#[test]
+fn test_valid_uses() {
+    for source in [
+        "~main<argc><argv>{<x = 1; y = &x + clone<x>; return x;>}",
+        "~main<argc><argv>{<x = 1; y = x; x = 2; return x;>}",
+        "~main<argc><argv>{<~double<n>{< return n * 2; >}; x = double<1>; return double<x>;>}",
+        "~add<a><b>{< return a + b; >} ~main<argc><argv>{<x = 1; return add<x><&x>;>}",
+        "~main<argc><argv>{<f = <x>{< return x; >}; return f<argc>;>}",
+        "enum Option<Some<value>><None>; ~main<argc><argv>{<x = None; y = None; return 0;>}",
+        "~main<argc><argv>{<a = 1; x = false && a; return a;>}",
+    ] {
+        assert!(check(source).is_empty(), "{source}");
+    }
+}
+
+#[test]
+fn test_branches() {
+    for (source, errors) in [
+        (
+            "~main<argc><argv>{<x = 1; if argc {< y = x; >} else {< z = x; >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; if argc {< y = x; >}; return x;>}",
+            0,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; if argc {< return x; >}; return x;>}",
+            0,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; if argc {< x = 2; y = x; return x; >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; y = match argc {< 1 => x, _ => x >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; try {< y = x; >} catch err {< >}; return x;>}",
+            0,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; try {< y = x; >} catch err {< z = x; >}; return x;>}",
+            1,
+        ),
+    ] {
+        assert_eq!(check(source).len(), errors, "{source}");
+    }
+}
+
+#[test]
+fn test_use_after_maybe_move() {
+    let source = "~main<argc><argv>{<x = 1; if argc {< y = x; >} else {< >}; println<x>;>}";
+    let warnings = check_all(source);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].is_warning());
+    assert!(
+        matches!(&warnings[0].kind, ErrorKind::UseAfterMaybeMove(name, (41, 1)) if name == "x")
+    );
+    assert_eq!(warnings[0].span, (67, 1).into());
+
+    for (source, warnings) in [
+        (
+            "~main<argc><argv>{<x = 1; if argc {< y = x; >}; return &x + x;>}",
+            2,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; try {< y = x; >} catch err {< >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; y = match argc {< 1 => x, _ => 0 >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; while argc {< y = x; break; >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; if argc {< y = x; >}; x = 2; return x;>}",
+            0,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; if argc {< return x; >}; return x;>}",
+            0,
+        ),
+    ] {
+        let errors = check_all(source);
+        assert!(errors.iter().all(|error| error.is_warning()), "{source}");
+        assert_eq!(errors.len(), warnings, "{source}");
+    }
+}
+
+#[test]
+fn test_loops() {
+    for (source, errors) in [
+        ("~main<argc><argv>{<x = 1; loop {< y = x; >};>}", 1),
+        ("~main<argc><argv>{<x = 1; loop {< y = x; break; >};>}", 0),
+        // The outer `x` is updated in the block, so it owns a value in the next iteration.
+        ("~main<argc><argv>{<x = 1; loop {< y = x; x = 2; >};>}", 0),
+        (
+            "~main<argc><argv>{<x = 1; loop {< y = x; if x {< x = 2; >}; >};>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; for i in [1, 2] {< y = x; >};>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; for i in [1] {< if i {< continue; >}; y = x; >};>}",
+            0,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; loop {< y = x; break; >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<~fail{< throw<1>; >}; x = 1; loop {< y = x; fail<>; >};>}",
+            0,
+        ),
+    ] {
+        assert_eq!(check(source).len(), errors, "{source}");
+    }
+}
+
+#[test]
+fn test_closures() {
+    for (source, errors) in [
+        (
+            "~main<argc><argv>{<x = 1; f = <>{< return x; >}; return x;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; f = <>{< return &x; >}; return x;>}",
+            0,
+        ),
+        (
+            "~main<argc><argv>{<x = 1; y = x; f = <>{< return x; >}; return 0;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<f = <x>{< y = x; return x; >}; return 0;>}",
+            1,
+        ),
+        (
+            "~main<argc><argv>{<~g{< return 1; >}; f = <>{< return g<>; >}; return g<>;>}",
+            0,
+        ),
+    ] {
+        assert_eq!(check(source).len(), errors, "{source}");
+    }
+}
+
+#[test]
+fn test_packs_and_unpacks() {
+    for (source, errors) in [
+        ("~main<argc><argv>{<x = [1]; println<...x><x>;>}", 1),
+        ("~main<argc><argv>{<x = [1]; println<...&x><x>;>}", 0),
+        (
+            "~f<*args>{< return 0; >} ~main<argc><argv>{<x = 1; return f<x><&x>;>}",
+            1,
+        ),
+        (
+            "~f<a><*args>{< return 0; >} ~main<argc><argv>{<x = 1; return f<x><&x>;>}",
+            0,
+        ),
+        (
+            "~f<a><b = 1>{< return 0; >} ~main<argc><argv>{<x = 1; return f<x><b = x>;>}",
+            1,
+        ),
+    ] {
+        assert_eq!(check(source).len(), errors, "{source}");
+    }
+}
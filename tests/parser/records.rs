@@ -0,0 +1,157 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+/// Returns the statements of the main function, which is the last statement of the program.
+fn main_statements(source: &str) -> Vec<Statement> {
+    match OYParser::parse_program(source).unwrap().0.pop().unwrap() {
+        Statement::Function(FunctionStatement {
+            block: Some(block), ..
+        }) => block.statements,
+        _ => unreachable!(),
+    }
+}
+
+fn ident(name: &str, start: usize, end: usize) -> Ident {
+    Ident {
+        ident: name.to_owned(),
+        span: Span::new(start, end),
+    }
+}
+
+fn value(ident: Ident) -> ExpressionStatement {
+    ExpressionStatement::Value(ValueExpression::Ident(ident))
+}
+
+fn int(value: i32, start: usize, end: usize) -> ExpressionStatement {
+    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::Int(
+        value.into(),
+        Span::new(start, end),
+    )))
+}
+
+#[test]
+fn test_record() {
+    let source = "record Point<x><y>;\n^record Empty;";
+    let statements = OYParser::parse_program(source).unwrap().0;
+    assert_eq!(
+        statements,
+        vec![
+            Statement::Record(RecordStatement {
+                ident: ident("Point", 7, 12),
+                fields: vec![ident("x", 13, 14), ident("y", 16, 17)],
+                visibility: Visibility::Private,
+                span: Span::new(0, 19),
+            }),
+            Statement::Record(RecordStatement {
+                ident: ident("Empty", 28, 33),
+                fields: Vec::new(),
+                visibility: Visibility::Public,
+                span: Span::new(20, 34),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_field_name() {
+    let ast = OYParser::parse_program("record Point<X><y>;");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)));
+}
+
+#[test]
+fn test_duplicate_field() {
+    let err = OYParser::parse_program("record Point<x><x>;").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::AlreadyDeclared(ref name, (13, 1)) if name == "x"));
+    assert_eq!(err.span, (16, 1).into());
+
+    let source = "~main<argc><argv>{<p = Point {x: 1, x: 2};>}";
+    let err = OYParser::parse_program(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::AlreadyDeclared(ref name, _) if name == "x"));
+}
+
+#[test]
+fn test_record_inside_function() {
+    let ast = OYParser::parse_program("~main<argc><argv>{<record Point<x>;>}");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_record_as_ident() {
+    let ast = OYParser::parse_program("~main<argc><argv>{<record = 1;>}");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_record_expression() {
+    let source = "~main<argc><argv>{<Point {x: 1, y: a,};>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Expression(ExpressionStatement::Record(
+            RecordExpression {
+                ident: ident("Point", 19, 24),
+                fields: vec![
+                    (ident("x", 26, 27), int(1, 29, 30)),
+                    (ident("y", 32, 33), value(ident("a", 35, 36))),
+                ],
+                span: Span::new(19, 38),
+            }
+        ))]
+    );
+}
+
+#[test]
+fn test_block_after_ident() {
+    // The block after an identifier is not a record expression.
+    let source = "~main<argc><argv>{<while running {<>};>}";
+    assert!(matches!(
+        main_statements(source).as_slice(),
+        [Statement::Expression(ExpressionStatement::Loop(_))]
+    ));
+}
+
+#[test]
+fn test_field_access() {
+    let source = "~main<argc><argv>{<-line.start.x;>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::Expression(ExpressionStatement::Unary(
+            UnaryExpression {
+                operator: UnaryOperator::Neg,
+                operand: Box::new(ExpressionStatement::FieldAccess(FieldAccessExpression {
+                    value: Box::new(ExpressionStatement::FieldAccess(FieldAccessExpression {
+                        value: Box::new(value(ident("line", 20, 24))),
+                        field: ident("start", 25, 30),
+                        span: Span::new(20, 30),
+                    })),
+                    field: ident("x", 31, 32),
+                    span: Span::new(20, 32),
+                })),
+                span: Span::new(19, 32),
+            }
+        ))]
+    );
+}
+
+#[test]
+fn test_field_access_of_call() {
+    let source = "~main<argc><argv>{<origin<>.x + 1;>}";
+    assert!(matches!(
+        main_statements(source).as_slice(),
+        [Statement::Expression(ExpressionStatement::Binary(BinaryExpression { left, .. }))]
+            if matches!(left.as_ref(), ExpressionStatement::FieldAccess(field_access)
+                if matches!(field_access.value.as_ref(), ExpressionStatement::FunctionCall(_)))
+    ));
+}
+
+#[test]
+fn test_field_assignment() {
+    let source = "~main<argc><argv>{<line.end.x = 1;>}";
+    assert_eq!(
+        main_statements(source),
+        vec![Statement::FieldAssignment(FieldAssignmentStatement {
+            ident: ident("line", 19, 23),
+            fields: vec![ident("end", 24, 27), ident("x", 28, 29)],
+            expression: int(1, 32, 33),
+            span: Span::new(19, 33),
+        })]
+    );
+}
@@ -0,0 +1,108 @@
+use ocypode_lang::{
+    ast::{Ident, Span},
+    errors::{Error, ErrorKind},
+    parser::OYParser,
+    resolver::{self, Binding, Resolutions},
+    runtime::symbols::Symbols,
+};
+
+/// Resolves the idents of the given source code.
+fn resolve(source: &str) -> Result<Resolutions, Vec<Error>> {
+    resolver::resolve(
+        &OYParser::parse_program(source).unwrap().0,
+        &mut Symbols::default(),
+    )
+}
+
+/// Returns the ident with the given name at the given offset of the source code.
+fn ident(name: &str, offset: usize) -> Ident {
+    Ident {
+        ident: name.to_owned(),
+        span: Span::new(offset, offset + name.len()),
+    }
+}
+
+#[test]
+fn test_undeclared_idents() {
+    let source = "~main<argc><argv>{<
+        if false {< return x; >};
+        f = <a>{< return a + y; >};
+        return z<argc>;
+    >}";
+    let errors = resolve(source).unwrap_err();
+    assert_eq!(errors.len(), 3);
+    for (error, name) in errors.iter().zip(["x", "y", "z"]) {
+        assert!(matches!(&error.kind, ErrorKind::UnDeclaredIdent(ident) if ident == name));
+    }
+    assert_eq!(errors[0].span, (47, 1).into());
+}
+
+#[test]
+fn test_bindings() {
+    let source = "enum Option<Some<value>><None>;
+    ~one{< return 1; >}
+    ~main<argc><argv>{< x = one<>; println<x>; return Some<argc>; >}";
+    let resolutions = resolve(source).unwrap();
+    let binding = |name: &str, from: usize| {
+        let offset = source[from..].find(name).unwrap() + from;
+        resolutions.binding(&ident(name, offset)).cloned()
+    };
+    let body = source.find("{< x").unwrap();
+    assert!(matches!(binding("one", body), Some(Binding::Global(_))));
+    assert!(matches!(
+        binding("println", body),
+        Some(Binding::Builtin(_))
+    ));
+    assert!(matches!(binding("Some", body), Some(Binding::Variant(_))));
+    assert_eq!(
+        binding("argc", body),
+        Some(Binding::Local {
+            slot: 0,
+            global: None
+        })
+    );
+    assert_eq!(
+        binding("x", body + 16),
+        Some(Binding::Local {
+            slot: 2,
+            global: None
+        })
+    );
+}
+
+#[test]
+fn test_innermost_binding() {
+    let source = "~main<argc><argv>{< x = 1; for x in [2] {< y = x; >}; return x; >}";
+    let resolutions = resolve(source).unwrap();
+    let binding = |offset: usize| resolutions.binding(&ident("x", offset)).cloned();
+    let inner = source.find("y = x").unwrap() + 4;
+    let outer = source.find("return x").unwrap() + 7;
+    assert_eq!(
+        binding(inner),
+        Some(Binding::Local {
+            slot: 3,
+            global: None
+        })
+    );
+    assert_eq!(
+        binding(outer),
+        Some(Binding::Local {
+            slot: 2,
+            global: None
+        })
+    );
+}
+
+#[test]
+fn test_valid_programs() {
+    for source in [
+        "~main<argc><argv>{< ~double<n>{< return n * 2; >}; return double<argc>; >}",
+        "~main<argc><argv>{< f = <x>{< return x + argc; >}; return f<1>; >}",
+        "~main<argc><argv>{< for item in argv {< println<item>; >}; return 0; >}",
+        "~main<argc><argv>{< try {< throw<1>; >} catch err {< println<err>; >}; return 0; >}",
+        "~main<argc><argv>{< return match argv {< [first, *rest] => first, _ => argc >}; >}",
+        "~add<a><b = a>{< return a + b; >} ~main<argc><argv>{< return add<1>; >}",
+    ] {
+        assert!(resolve(source).is_ok(), "{source}");
+    }
+}
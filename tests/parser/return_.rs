@@ -19,6 +19,8 @@ fn test_return_integer() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -26,6 +28,8 @@ fn test_return_integer() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -38,6 +42,7 @@ fn test_return_integer() {
                 span: Span::new(17, 30),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 30),
         })],
         pest::Span::new(source, 0, 30).unwrap(),
@@ -65,6 +70,8 @@ fn test_return_float() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -72,6 +79,8 @@ fn test_return_float() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -84,6 +93,7 @@ fn test_return_float() {
                 span: Span::new(17, 32),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 32),
         })],
         pest::Span::new(source, 0, 32).unwrap(),
@@ -111,6 +121,8 @@ fn test_return_string() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -118,6 +130,8 @@ fn test_return_string() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -130,6 +144,7 @@ fn test_return_string() {
                 span: Span::new(17, 36),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 36),
         })],
         pest::Span::new(source, 0, 36).unwrap(),
@@ -157,6 +172,8 @@ fn test_return_array() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -164,6 +181,8 @@ fn test_return_array() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -189,6 +208,7 @@ fn test_return_array() {
                 span: Span::new(17, 38),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 38),
         })],
         pest::Span::new(source, 0, 38).unwrap(),
@@ -216,6 +236,8 @@ fn test_return_bool() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -223,6 +245,8 @@ fn test_return_bool() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -235,6 +259,7 @@ fn test_return_bool() {
                 span: Span::new(17, 33),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 33),
         })],
         pest::Span::new(source, 0, 33).unwrap(),
@@ -262,6 +287,8 @@ fn test_return_nil() {
                         span: Span::new(6, 10),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
                 Param {
                     ident: Ident {
@@ -269,6 +296,8 @@ fn test_return_nil() {
                         span: Span::new(12, 16),
                     },
                     is_pack: false,
+                    default: None,
+                    type_annotation: None,
                 },
             ],
             block: Some(Block {
@@ -281,6 +310,7 @@ fn test_return_nil() {
                 span: Span::new(17, 32),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 32),
         })],
         pest::Span::new(source, 0, 32).unwrap(),
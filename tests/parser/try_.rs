@@ -0,0 +1,65 @@
+use ocypode_lang::{ast::*, errors::ErrorKind, parser::OYParser};
+
+/// Returns the statements of the main function.
+fn main_statements(source: &str) -> Vec<Statement> {
+    match OYParser::parse_program(source).unwrap().0.remove(0) {
+        Statement::Function(FunctionStatement {
+            block: Some(block), ..
+        }) => block.statements,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_try() {
+    let source = "~main<argc><argv>{<try {<a;>} catch err {<>};>}";
+    let statements = main_statements(source);
+
+    assert_eq!(
+        statements,
+        vec![Statement::Try(TryStatement {
+            block: Block {
+                statements: vec![Statement::Expression(ExpressionStatement::Value(
+                    ValueExpression::Ident(Ident {
+                        ident: "a".to_owned(),
+                        span: Span::new(25, 26),
+                    })
+                ))],
+                span: Span::new(23, 29),
+            },
+            ident: Ident {
+                ident: "err".to_owned(),
+                span: Span::new(36, 39),
+            },
+            catch_block: Block {
+                statements: Vec::new(),
+                span: Span::new(40, 44),
+            },
+            span: Span::new(19, 44),
+        })]
+    );
+}
+
+#[test]
+fn test_try_without_catch() {
+    let ast = OYParser::parse_program("~main<argc><argv>{<try {<>};>}");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_catch_without_ident() {
+    let ast = OYParser::parse_program("~main<argc><argv>{<try {<>} catch {<>};>}");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)));
+}
+
+#[test]
+fn test_invalid_catch_ident() {
+    let ast = OYParser::parse_program("~main<argc><argv>{<try {<>} catch Err {<>};>}");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::InvalidName(..)));
+}
+
+#[test]
+fn test_break_in_try_outside_loop() {
+    let ast = OYParser::parse_program("~main<argc><argv>{<try {<>} catch err {<break;>};>}");
+    assert!(matches!(ast.unwrap_err().kind, ErrorKind::OutsideLoop(..)));
+}
@@ -24,6 +24,8 @@ mod nil {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -31,6 +33,8 @@ mod nil {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -40,6 +44,7 @@ mod nil {
                     span: Span::new(17, 25),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 25),
             })],
             pest::Span::new(source, 0, 25).unwrap(),
@@ -67,6 +72,8 @@ mod nil {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -74,6 +81,8 @@ mod nil {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -86,6 +95,7 @@ mod nil {
                     span: Span::new(17, 29),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 29),
             })],
             pest::Span::new(source, 0, 29).unwrap(),
@@ -113,6 +123,8 @@ mod nil {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -120,6 +132,8 @@ mod nil {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -132,6 +146,7 @@ mod nil {
                     span: Span::new(17, 28),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 28),
             })],
             pest::Span::new(source, 0, 28).unwrap(),
@@ -163,6 +178,8 @@ mod bool {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -170,6 +187,8 @@ mod bool {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -184,6 +203,7 @@ mod bool {
                     span: Span::new(17, 32),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 32),
             })],
             pest::Span::new(source, 0, 32).unwrap(),
@@ -211,6 +231,8 @@ mod bool {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -218,6 +240,8 @@ mod bool {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -238,6 +262,7 @@ mod bool {
                     span: Span::new(17, 40),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 40),
             })],
             pest::Span::new(source, 0, 40).unwrap(),
@@ -265,6 +290,8 @@ mod bool {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -272,6 +299,8 @@ mod bool {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -292,6 +321,7 @@ mod bool {
                     span: Span::new(17, 40),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 40),
             })],
             pest::Span::new(source, 0, 40).unwrap(),
@@ -323,6 +353,8 @@ mod int {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -330,6 +362,8 @@ mod int {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -368,6 +402,7 @@ mod int {
                     span: Span::new(17, 41),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 41),
             })],
             pest::Span::new(source, 0, 41).unwrap(),
@@ -402,6 +437,8 @@ mod int {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -409,6 +446,8 @@ mod int {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -421,6 +460,7 @@ mod int {
                     span: Span::new(17, 32),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 32),
             })],
             pest::Span::new(source, 0, 32).unwrap(),
@@ -447,6 +487,8 @@ mod int {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -454,6 +496,8 @@ mod int {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -466,6 +510,7 @@ mod int {
                     span: Span::new(17, 52),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 52),
             })],
             pest::Span::new(source, 0, 52).unwrap(),
@@ -504,6 +549,8 @@ mod float {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -511,6 +558,8 @@ mod float {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -523,6 +572,7 @@ mod float {
                     span: Span::new(17, 25),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 25),
             })],
             pest::Span::new(source, 0, 25).unwrap(),
@@ -556,6 +606,8 @@ mod float {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -563,6 +615,8 @@ mod float {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -575,6 +629,7 @@ mod float {
                     span: Span::new(17, 43),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 43),
             })],
             pest::Span::new(source, 0, 43).unwrap(),
@@ -609,6 +664,8 @@ mod float {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -616,6 +673,8 @@ mod float {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -630,6 +689,7 @@ mod float {
                     span: Span::new(17, 83),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 83),
             })],
             pest::Span::new(source, 0, 83).unwrap(),
@@ -668,6 +728,8 @@ mod string {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -675,6 +737,8 @@ mod string {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -687,6 +751,7 @@ mod string {
                     span: Span::new(17, 24),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 24),
             })],
             pest::Span::new(source, 0, 24).unwrap(),
@@ -720,6 +785,8 @@ mod string {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -727,6 +794,8 @@ mod string {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -739,6 +808,7 @@ mod string {
                     span: Span::new(17, 25),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 25),
             })],
             pest::Span::new(source, 0, 25).unwrap(),
@@ -765,6 +835,8 @@ mod string {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -772,6 +844,8 @@ mod string {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -784,6 +858,7 @@ mod string {
                     span: Span::new(17, 28),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 28),
             })],
             pest::Span::new(source, 0, 28).unwrap(),
@@ -817,6 +892,8 @@ mod string {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -824,6 +901,8 @@ mod string {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -836,6 +915,7 @@ mod string {
                     span: Span::new(17, 30),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 30),
             })],
             pest::Span::new(source, 0, 30).unwrap(),
@@ -869,6 +949,8 @@ mod string {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -876,6 +958,8 @@ mod string {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -888,6 +972,7 @@ mod string {
                     span: Span::new(17, 38),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 38),
             })],
             pest::Span::new(source, 0, 38).unwrap(),
@@ -914,6 +999,8 @@ mod string {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -921,6 +1008,8 @@ mod string {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -933,6 +1022,7 @@ mod string {
                     span: Span::new(17, 41),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 41),
             })],
             pest::Span::new(source, 0, 41).unwrap(),
@@ -959,6 +1049,8 @@ ident: Some(Ident {
                             span: Span::new(6, 10),
                         },
 is_pack: false,
+default: None,
+                        type_annotation: None,
                     },
                 Param {
                         ident: Ident {
@@ -966,6 +1058,8 @@ is_pack: false,
                             span: Span::new(12, 16),
                         },
 is_pack: false,
+default: None,
+                        type_annotation: None,
                     },
             ],
             block: Some(
@@ -981,6 +1075,7 @@ is_pack: false,
 ),
 visibility: Visibility::Private,
             span: Span::new(0, 163),
+return_type: None,
         })],
         pest::Span::new(source, 0, 163).unwrap(),
     );
@@ -1006,6 +1101,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1013,6 +1110,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1025,6 +1124,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 19 + lorem.len() + 5),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 19 + lorem.len() + 5),
             })],
             pest::Span::new(&source, 0, 19 + lorem.len() + 5).unwrap(),
@@ -1059,6 +1159,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1066,6 +1168,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1078,6 +1182,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 19 + lorem.len() + 5),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 19 + lorem.len() + 5),
             })],
             pest::Span::new(&source, 0, 19 + lorem.len() + 5).unwrap(),
@@ -1108,6 +1213,8 @@ mod array {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1115,6 +1222,8 @@ mod array {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1124,6 +1233,7 @@ mod array {
                     span: Span::new(17, 24),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 24),
             })],
             pest::Span::new(source, 0, 24).unwrap(),
@@ -1150,6 +1260,8 @@ mod array {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1157,6 +1269,8 @@ mod array {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1171,6 +1285,7 @@ mod array {
                     span: Span::new(17, 25),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 25),
             })],
             pest::Span::new(source, 0, 25).unwrap(),
@@ -1197,6 +1312,8 @@ mod array {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1204,6 +1321,8 @@ mod array {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1226,6 +1345,7 @@ mod array {
                     span: Span::new(17, 31),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 31),
             })],
             pest::Span::new(source, 0, 31).unwrap(),
@@ -1252,6 +1372,8 @@ mod array {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1259,6 +1381,8 @@ mod array {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1302,6 +1426,7 @@ mod array {
                     span: Span::new(17, 53),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 53),
             })],
             pest::Span::new(source, 0, 53).unwrap(),
@@ -1328,6 +1453,8 @@ mod array {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1335,6 +1462,8 @@ mod array {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1370,6 +1499,7 @@ mod array {
                     span: Span::new(17, 39),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 39),
             })],
             pest::Span::new(source, 0, 39).unwrap(),
@@ -1396,6 +1526,8 @@ mod array {
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1403,6 +1535,8 @@ mod array {
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1460,6 +1594,7 @@ mod array {
                     span: Span::new(17, 57),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 57),
             })],
             pest::Span::new(source, 0, 57).unwrap(),
@@ -1486,6 +1621,8 @@ ident: Some(Ident {
                             span: Span::new(6, 10),
                         },
 is_pack: false,
+default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1493,6 +1630,8 @@ is_pack: false,
                             span: Span::new(12, 16),
                         },
 is_pack: false,
+default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(
@@ -1622,6 +1761,7 @@ is_pack: false,
 ),
 visibility: Visibility::Private,
                 span: Span::new(0, 65),
+return_type: None,
             })],
             pest::Span::new(source, 0, 65).unwrap(),
         );
@@ -1646,6 +1786,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1653,6 +1795,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1726,6 +1870,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 64),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 64),
             })],
             pest::Span::new(source, 0, 64).unwrap(),
@@ -1752,6 +1897,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1759,6 +1906,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1892,6 +2041,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 114),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 114),
             })],
             pest::Span::new(source, 0, 114).unwrap(),
@@ -1918,6 +2068,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1925,6 +2077,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -1963,6 +2117,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 37),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 37),
             })],
             pest::Span::new(source, 0, 37).unwrap(),
@@ -1989,6 +2144,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -1996,6 +2153,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2053,6 +2212,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 47),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 47),
             })],
             pest::Span::new(source, 0, 47).unwrap(),
@@ -2079,6 +2239,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2086,6 +2248,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2181,6 +2345,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 67),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 67),
             })],
             pest::Span::new(source, 0, 67).unwrap(),
@@ -2207,6 +2372,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2214,6 +2381,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2233,6 +2402,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 39),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 39),
             })],
             pest::Span::new(source, 0, 39).unwrap(),
@@ -2260,6 +2430,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2267,6 +2439,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2307,6 +2481,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 81),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 81),
             })],
             pest::Span::new(source, 0, 81).unwrap(),
@@ -2333,6 +2508,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2340,6 +2517,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2367,6 +2546,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 41),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 41),
             })],
             pest::Span::new(source, 0, 41).unwrap(),
@@ -2393,6 +2573,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2400,6 +2582,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2438,6 +2622,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 47),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 47),
             })],
             pest::Span::new(source, 0, 47).unwrap(),
@@ -2464,6 +2649,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2471,6 +2658,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2513,6 +2702,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 47),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 47),
             })],
             pest::Span::new(source, 0, 47).unwrap(),
@@ -2539,6 +2729,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2546,6 +2738,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2565,6 +2759,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 31),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 31),
             })],
             pest::Span::new(source, 0, 31).unwrap(),
@@ -2591,6 +2786,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2598,6 +2795,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2622,6 +2821,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 33),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 33),
             })],
             pest::Span::new(source, 0, 33).unwrap(),
@@ -2648,6 +2848,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2655,6 +2857,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2687,6 +2891,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 39),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 39),
             })],
             pest::Span::new(source, 0, 39).unwrap(),
@@ -2713,6 +2918,8 @@ visibility: Visibility::Private,
                             span: Span::new(6, 10),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                     Param {
                         ident: Ident {
@@ -2720,6 +2927,8 @@ visibility: Visibility::Private,
                             span: Span::new(12, 16),
                         },
                         is_pack: false,
+                        default: None,
+                        type_annotation: None,
                     },
                 ],
                 block: Some(Block {
@@ -2758,6 +2967,7 @@ visibility: Visibility::Private,
                     span: Span::new(17, 41),
                 }),
                 visibility: Visibility::Private,
+                return_type: None,
                 span: Span::new(0, 41),
             })],
             pest::Span::new(source, 0, 41).unwrap(),
@@ -2766,3 +2976,86 @@ visibility: Visibility::Private,
         assert_eq!(ast.unwrap(), program);
     }
 }
+
+#[cfg(test)]
+mod map {
+    use super::*;
+
+    /// Returns the first statement of the main function.
+    fn main_statement(source: &str) -> Statement {
+        match OYParser::parse_program(source).unwrap().0.remove(0) {
+            Statement::Function(FunctionStatement {
+                block: Some(mut block),
+                ..
+            }) => block.statements.remove(0),
+            _ => unreachable!(),
+        }
+    }
+
+    fn object(object: ObjectExpression) -> ExpressionStatement {
+        ExpressionStatement::Value(ValueExpression::Object(object))
+    }
+
+    #[test]
+    fn test_empty_map() {
+        let source = "~main<argc><argv>{<{};>}";
+        assert_eq!(
+            main_statement(source),
+            Statement::Expression(object(ObjectExpression::Map(vec![], Span::new(19, 21))))
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let source = r#"~main<argc><argv>{<{"a": 1, 2: [nil],};>}"#;
+        assert_eq!(
+            main_statement(source),
+            Statement::Expression(object(ObjectExpression::Map(
+                vec![
+                    (
+                        object(ObjectExpression::String("a".to_owned(), Span::new(20, 23))),
+                        object(ObjectExpression::Int(1.into(), Span::new(25, 26))),
+                    ),
+                    (
+                        object(ObjectExpression::Int(2.into(), Span::new(28, 29))),
+                        object(ObjectExpression::Array(
+                            vec![object(ObjectExpression::Nil(Span::new(32, 35)))],
+                            Span::new(31, 36)
+                        )),
+                    ),
+                ],
+                Span::new(19, 38)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_map_is_not_block() {
+        let source = "~main<argc><argv>{<if {} {<return 1;>};>}";
+        assert!(matches!(
+            main_statement(source),
+            Statement::If(IfStatement {
+                condition: ExpressionStatement::Value(ValueExpression::Object(
+                    ObjectExpression::Map(..)
+                )),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        for source in [
+            "~main<argc><argv>{<{,};>}",
+            r#"~main<argc><argv>{<{"a"};>}"#,
+            r#"~main<argc><argv>{<{"a": 1 "b": 2};>}"#,
+        ] {
+            let ast = OYParser::parse_program(source);
+            assert!(
+                matches!(ast.unwrap_err().kind, ErrorKind::Parse(..)),
+                "{}",
+                source
+            );
+        }
+    }
+}
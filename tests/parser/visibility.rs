@@ -18,6 +18,7 @@ fn test_public_visibility() {
                 span: Span::new(5, 9),
             }),
             visibility: Visibility::Public,
+            return_type: None,
             span: Span::new(0, 9),
         })],
         pest::Span::new(source, 0, 9).unwrap(),
@@ -44,6 +45,7 @@ fn test_private_visibility() {
                 span: Span::new(4, 8),
             }),
             visibility: Visibility::Private,
+            return_type: None,
             span: Span::new(0, 8),
         })],
         pest::Span::new(source, 0, 8).unwrap(),
@@ -0,0 +1,91 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_annotated_program() {
+    let source = "record Point<x><y>;
+    ~count<*numbers: int | float>: int {< return len<numbers>; >}
+    ~apply<f: function><point: Point>: int {< return f<&point.x><point.y>; >}
+    ~main<argc><argv>{<
+        name: string | nil = nil;
+        return apply<<a><b>{< return &a + &b + count<a><b><3.5>; >}><Point {x: 1, y: 2}>;
+    >}";
+    assert_eq!(run(source).unwrap(), 6);
+}
+
+#[test]
+fn test_param_type() {
+    let source = r#"~double<n: int>{< return n * 2; >}
+    ~main<argc><argv>{<
+        x = "2";
+        return double<x>;
+    >}"#;
+    let err = run(source).unwrap_err();
+    assert!(
+        matches!(&err.kind, ErrorKind::MismatchedType(expected, found, Some((11, 3))) if expected == "int" && found == "string")
+    );
+    assert_eq!(err.span, (98, 1).into());
+}
+
+#[test]
+fn test_pack_param_type() {
+    let source = r#"~sum<*numbers: int>{< return 0; >}
+    ~main<argc><argv>{<
+        return sum<1><2><"3">;
+    >}"#;
+    let err = run(source).unwrap_err();
+    assert!(
+        matches!(&err.kind, ErrorKind::MismatchedType(expected, found, _) if expected == "int" && found == "string")
+    );
+    assert_eq!(err.span, (84, 3).into());
+}
+
+#[test]
+fn test_default_param_type() {
+    let source = r#"~greet<name: string = 1>{< return name; >}
+    ~main<argc><argv>{<
+        greet<"Ocypode">;
+        greet<>;
+        return 0;
+    >}"#;
+    let err = run(source).unwrap_err();
+    assert!(
+        matches!(&err.kind, ErrorKind::MismatchedType(expected, found, _) if expected == "string" && found == "int")
+    );
+}
+
+#[test]
+fn test_return_type() {
+    for source in [
+        "~id<x>: int {< return x; >} ~main<argc><argv>{< id<\"a\">; return 0; >}",
+        "~nothing: int {< >} ~main<argc><argv>{< nothing<>; return 0; >}",
+        "~main<argc><argv>{< f = <x>: string {< return x; >}; return f<1>; >}",
+    ] {
+        assert!(
+            matches!(run(source).unwrap_err().kind, ErrorKind::MismatchedType(..)),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_assignment_type() {
+    let source = "~main<argc><argv>{< x: string | nil = argc; return 0; >}";
+    let err = run(source).unwrap_err();
+    assert!(
+        matches!(&err.kind, ErrorKind::MismatchedType(expected, found, Some((23, 12))) if expected == "string | nil" && found == "int")
+    );
+    assert_eq!(err.span, (38, 4).into());
+}
+
+#[test]
+fn test_catch_mismatched_type() {
+    let source = r#"~half<n: int | float>{< return n / 2; >}
+    ~main<argc><argv>{<
+        try {< half<"4">; >} catch err {<
+            if get<err><"kind"> == "runtime::types::mismatched_type" {< return 1; >};
+        >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
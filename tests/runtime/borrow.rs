@@ -0,0 +1,148 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_borrow_keeps_variable() {
+    let source = "~main<argc><argv>{<
+        count = 4;
+        double = &count + &count;
+        return double + count;
+    >}";
+    assert_eq!(run(source).unwrap(), 12);
+}
+
+#[test]
+fn test_borrow_argument() {
+    let source = "~square<x>{< return &x * x; >}
+    ~main<argc><argv>{<
+        value = 3;
+        return square<&value> + value;
+    >}";
+    assert_eq!(run(source).unwrap(), 12);
+}
+
+#[test]
+fn test_borrow_moved_argument() {
+    // The ident arguments are moved after the other arguments, so the other arguments can borrow them.
+    let source = "~sub<a><b>{< return a - b; >}
+    ~main<argc><argv>{<
+        value = 5;
+        return sub<value><&value - 3>;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_borrow_field() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 3, y: 4};
+        sum = &point.x + &point.y;
+        return sum + point.x;
+    >}";
+    assert_eq!(run(source).unwrap(), 10);
+}
+
+#[test]
+fn test_clone() {
+    let source = "~main<argc><argv>{<
+        list = [1, 2];
+        copy = clone<list>;
+        copy = push<copy><3>;
+        return len<list> * 10 + len<copy>;
+    >}";
+    assert_eq!(run(source).unwrap(), 23);
+}
+
+#[test]
+fn test_clone_record() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 1, y: 2};
+        copy = clone<point>;
+        copy.x = 5;
+        return point.x * 10 + copy.x;
+    >}";
+    assert_eq!(run(source).unwrap(), 15);
+}
+
+#[test]
+fn test_clone_map() {
+    let source = r#"~main<argc><argv>{<
+        ages = {"Ali": 20};
+        copy = clone<ages>;
+        copy = set<copy><"Sara"><22>;
+        return len<ages> * 10 + len<copy>;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 12);
+}
+
+#[test]
+fn test_clone_expression() {
+    let source = "~main<argc><argv>{<
+        return clone<2 + 3>;
+    >}";
+    assert_eq!(run(source).unwrap(), 5);
+}
+
+#[test]
+fn test_closure_borrow_copies_variable() {
+    let source = "~main<argc><argv>{<
+        factor = 3;
+        triple = <x>{< return x * &factor; >};
+        return triple<2> + factor;
+    >}";
+    assert_eq!(run(source).unwrap(), 9);
+}
+
+#[test]
+fn test_closure_use_after_borrow_moves_variable() {
+    let source = "~main<argc><argv>{<
+        factor = 3;
+        closure = <>{< return &factor + factor; >};
+        return factor;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(ident, _) if ident == "factor"
+    ));
+}
+
+#[test]
+fn test_borrow_moved_variable() {
+    let source = "~main<argc><argv>{<
+        count = 1;
+        other = count;
+        return &count;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(ident, _) if ident == "count"
+    ));
+}
+
+#[test]
+fn test_use_after_move_span() {
+    let source = "~main<argc><argv>{<
+        count = 1;
+        other = count;
+        return count;
+    >}";
+    let error = run(source).unwrap_err();
+    assert!(matches!(
+        error.kind,
+        ErrorKind::UseAfterMove(ident, (55, 5)) if ident == "count"
+    ));
+    assert_eq!(error.span, (77, 5).into());
+}
+
+#[test]
+fn test_use_after_redeclare() {
+    let source = "~main<argc><argv>{<
+        count = 1;
+        other = count;
+        count = 2;
+        return count + other;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
@@ -1,4 +1,10 @@
-use ocypode_lang::{ast::*, runtime::builtins::functions::*};
+use super::run;
+use std::sync::Arc;
+
+use ocypode_lang::{
+    ast::*,
+    runtime::{builtins::functions::*, value::Value},
+};
 
 #[cfg(test)]
 mod format {
@@ -7,165 +13,145 @@ mod format {
     #[test]
     fn test_one_empty_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::String("Hello {}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "World".into(),
+                    Span { start: 0, end: 0 },
+                )]),
                 Span { start: 0, end: 0 },
             ),
         ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String("Hello World".to_owned(), Span { start: 0, end: 0 })
+            Value::String("Hello World".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_tow_empty_placeholder() {
-        let args =
-            vec![
-                ObjectExpression::String("Hello {}{}".to_owned(), Span { start: 0, end: 0 }),
-                ObjectExpression::Array(
-                    vec![
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                        )),
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::String("!".to_owned(), Span { start: 0, end: 0 }),
-                        )),
-                    ],
-                    Span { start: 0, end: 0 },
-                ),
-            ];
+        let args = vec![
+            Value::String("Hello {}{}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![
+                    Value::String("World".into(), Span { start: 0, end: 0 }),
+                    Value::String("!".into(), Span { start: 0, end: 0 }),
+                ]),
+                Span { start: 0, end: 0 },
+            ),
+        ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String("Hello World!".to_owned(), Span { start: 0, end: 0 })
+            Value::String("Hello World!".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_one_index_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {0}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::String("Hello {0}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "World".into(),
+                    Span { start: 0, end: 0 },
+                )]),
                 Span { start: 0, end: 0 },
             ),
         ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String("Hello World".to_owned(), Span { start: 0, end: 0 })
+            Value::String("Hello World".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_tow_index_placeholder() {
-        let args =
-            vec![
-                ObjectExpression::String("Hello {0}{1}".to_owned(), Span { start: 0, end: 0 }),
-                ObjectExpression::Array(
-                    vec![
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                        )),
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::String("!".to_owned(), Span { start: 0, end: 0 }),
-                        )),
-                    ],
-                    Span { start: 0, end: 0 },
-                ),
-            ];
+        let args = vec![
+            Value::String("Hello {0}{1}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![
+                    Value::String("World".into(), Span { start: 0, end: 0 }),
+                    Value::String("!".into(), Span { start: 0, end: 0 }),
+                ]),
+                Span { start: 0, end: 0 },
+            ),
+        ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String("Hello World!".to_owned(), Span { start: 0, end: 0 })
+            Value::String("Hello World!".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_one_index_placeholder_with_empty_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {0}, yes {}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::String("Hello {0}, yes {}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "World".into(),
+                    Span { start: 0, end: 0 },
+                )]),
                 Span { start: 0, end: 0 },
             ),
         ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String(
-                "Hello World, yes World".to_owned(),
-                Span { start: 0, end: 0 }
-            )
+            Value::String("Hello World, yes World".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_one_empty_placeholder_with_index_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {}, yes {0}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::String("Hello {}, yes {0}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "World".into(),
+                    Span { start: 0, end: 0 },
+                )]),
                 Span { start: 0, end: 0 },
             ),
         ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String(
-                "Hello World, yes World".to_owned(),
-                Span { start: 0, end: 0 }
-            )
+            Value::String("Hello World, yes World".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_one_index_placeholder_with_empty_placeholder_with_index_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {0}, yes {}{1}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "World".to_owned(),
-                        Span { start: 0, end: 0 },
-                    ))),
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "!".to_owned(),
-                        Span { start: 0, end: 0 },
-                    ))),
-                ],
+            Value::String("Hello {0}, yes {}{1}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![
+                    Value::String("World".into(), Span { start: 0, end: 0 }),
+                    Value::String("!".into(), Span { start: 0, end: 0 }),
+                ]),
                 Span { start: 0, end: 0 },
             ),
         ];
         let result = format(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::String(
-                "Hello World, yes World!".to_owned(),
-                Span { start: 0, end: 0 }
-            )
+            Value::String("Hello World, yes World!".into(), Span { start: 0, end: 0 })
         );
     }
 
     #[test]
     fn test_invalid_index_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {0}, yes {1}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::String("Hello {0}, yes {1}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "World".into(),
+                    Span { start: 0, end: 0 },
+                )]),
                 Span { start: 0, end: 0 },
             ),
         ];
@@ -176,11 +162,12 @@ mod format {
     #[test]
     fn test_invalid_empty_placeholder() {
         let args = vec![
-            ObjectExpression::String("Hello {}, yes {}".to_owned(), Span { start: 0, end: 0 }),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::String("Hello {}, yes {}".into(), Span { start: 0, end: 0 }),
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "World".into(),
+                    Span { start: 0, end: 0 },
+                )]),
                 Span { start: 0, end: 0 },
             ),
         ];
@@ -195,60 +182,39 @@ mod len {
 
     #[test]
     fn test_empty_string() {
-        let args = vec![ObjectExpression::String(
-            "".to_owned(),
-            Span { start: 0, end: 0 },
-        )];
+        let args = vec![Value::String("".into(), Span { start: 0, end: 0 })];
         let result = len(args, Span { start: 0, end: 0 }).unwrap();
-        assert_eq!(
-            result,
-            ObjectExpression::Int(0.into(), Span { start: 0, end: 0 })
-        );
+        assert_eq!(result, Value::Int(0.into(), Span { start: 0, end: 0 }));
     }
 
     #[test]
     fn test_string() {
-        let args = vec![ObjectExpression::String(
-            "Hello World".to_owned(),
+        let args = vec![Value::String(
+            "Hello World".into(),
             Span { start: 0, end: 0 },
         )];
         let result = len(args, Span { start: 0, end: 0 }).unwrap();
-        assert_eq!(
-            result,
-            ObjectExpression::Int(11.into(), Span { start: 0, end: 0 })
-        );
+        assert_eq!(result, Value::Int(11.into(), Span { start: 0, end: 0 }));
     }
 
     #[test]
     fn test_empty_array() {
-        let args = vec![ObjectExpression::Array(vec![], Span { start: 0, end: 0 })];
+        let args = vec![Value::Array(Arc::new(vec![]), Span { start: 0, end: 0 })];
         let result = len(args, Span { start: 0, end: 0 }).unwrap();
-        assert_eq!(
-            result,
-            ObjectExpression::Int(0.into(), Span { start: 0, end: 0 })
-        );
+        assert_eq!(result, Value::Int(0.into(), Span { start: 0, end: 0 }));
     }
 
     #[test]
     fn test_array() {
-        let args = vec![ObjectExpression::Array(
-            vec![
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "Hello".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "World".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-            ],
+        let args = vec![Value::Array(
+            Arc::new(vec![
+                Value::String("Hello".into(), Span { start: 0, end: 0 }),
+                Value::String("World".into(), Span { start: 0, end: 0 }),
+            ]),
             Span { start: 0, end: 0 },
         )];
         let result = len(args, Span { start: 0, end: 0 }).unwrap();
-        assert_eq!(
-            result,
-            ObjectExpression::Int(2.into(), Span { start: 0, end: 0 })
-        );
+        assert_eq!(result, Value::Int(2.into(), Span { start: 0, end: 0 }));
     }
 }
 
@@ -259,16 +225,17 @@ mod push {
     #[test]
     fn test_empty_array() {
         let args = vec![
-            ObjectExpression::Array(vec![], Span { start: 0, end: 0 }),
-            ObjectExpression::String("Hello".to_owned(), Span { start: 0, end: 0 }),
+            Value::Array(Arc::new(vec![]), Span { start: 0, end: 0 }),
+            Value::String("Hello".into(), Span { start: 0, end: 0 }),
         ];
         let result = push(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("Hello".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "Hello".into(),
+                    Span { start: 0, end: 0 }
+                ),]),
                 Span { start: 0, end: 0 },
             )
         );
@@ -276,39 +243,25 @@ mod push {
 
     #[test]
     fn test_array() {
-        let args =
-            vec![
-                ObjectExpression::Array(
-                    vec![
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::String("Hello".to_owned(), Span { start: 0, end: 0 }),
-                        )),
-                        ExpressionStatement::Value(ValueExpression::Object(
-                            ObjectExpression::String("World".to_owned(), Span { start: 0, end: 0 }),
-                        )),
-                    ],
-                    Span { start: 0, end: 0 },
-                ),
-                ObjectExpression::String("!".to_owned(), Span { start: 0, end: 0 }),
-            ];
+        let args = vec![
+            Value::Array(
+                Arc::new(vec![
+                    Value::String("Hello".into(), Span { start: 0, end: 0 }),
+                    Value::String("World".into(), Span { start: 0, end: 0 }),
+                ]),
+                Span { start: 0, end: 0 },
+            ),
+            Value::String("!".into(), Span { start: 0, end: 0 }),
+        ];
         let result = push(args, Span { start: 0, end: 0 }).unwrap();
         assert_eq!(
             result,
-            ObjectExpression::Array(
-                vec![
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "Hello".to_owned(),
-                        Span { start: 0, end: 0 }
-                    ),)),
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "World".to_owned(),
-                        Span { start: 0, end: 0 }
-                    ),)),
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "!".to_owned(),
-                        Span { start: 0, end: 0 }
-                    ),)),
-                ],
+            Value::Array(
+                Arc::new(vec![
+                    Value::String("Hello".into(), Span { start: 0, end: 0 }),
+                    Value::String("World".into(), Span { start: 0, end: 0 }),
+                    Value::String("!".into(), Span { start: 0, end: 0 }),
+                ]),
                 Span { start: 0, end: 0 },
             )
         );
@@ -321,38 +274,33 @@ mod pop {
 
     #[test]
     fn test_empty_array() {
-        let args = vec![ObjectExpression::Array(vec![], Span { start: 0, end: 0 })];
+        let args = vec![Value::Array(Arc::new(vec![]), Span { start: 0, end: 0 })];
         let result = pop(args, Span { start: 0, end: 0 });
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
-            ObjectExpression::Array(vec![], Span { start: 0, end: 0 },)
+            Value::Array(Arc::new(vec![]), Span { start: 0, end: 0 },)
         )
     }
 
     #[test]
     fn test_array() {
-        let args = vec![ObjectExpression::Array(
-            vec![
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "Hello".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "World".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-            ],
+        let args = vec![Value::Array(
+            Arc::new(vec![
+                Value::String("Hello".into(), Span { start: 0, end: 0 }),
+                Value::String("World".into(), Span { start: 0, end: 0 }),
+            ]),
             Span { start: 0, end: 0 },
         )];
         let result = pop(args, Span { start: 0, end: 0 });
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
-            ObjectExpression::Array(
-                vec![ExpressionStatement::Value(ValueExpression::Object(
-                    ObjectExpression::String("Hello".to_owned(), Span { start: 0, end: 0 }),
-                ))],
+            Value::Array(
+                Arc::new(vec![Value::String(
+                    "Hello".into(),
+                    Span { start: 0, end: 0 }
+                ),]),
                 Span { start: 0, end: 0 },
             )
         );
@@ -360,40 +308,238 @@ mod pop {
 
     #[test]
     fn test_array_with_multiple_expressions() {
-        let args = vec![ObjectExpression::Array(
-            vec![
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "Hello".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "World".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-                ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                    "!".to_owned(),
-                    Span { start: 0, end: 0 },
-                ))),
-            ],
+        let args = vec![Value::Array(
+            Arc::new(vec![
+                Value::String("Hello".into(), Span { start: 0, end: 0 }),
+                Value::String("World".into(), Span { start: 0, end: 0 }),
+                Value::String("!".into(), Span { start: 0, end: 0 }),
+            ]),
             Span { start: 0, end: 0 },
         )];
         let result = pop(args, Span { start: 0, end: 0 });
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
-            ObjectExpression::Array(
-                vec![
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "Hello".to_owned(),
-                        Span { start: 0, end: 0 }
-                    ),)),
-                    ExpressionStatement::Value(ValueExpression::Object(ObjectExpression::String(
-                        "World".to_owned(),
-                        Span { start: 0, end: 0 }
-                    ),)),
-                ],
+            Value::Array(
+                Arc::new(vec![
+                    Value::String("Hello".into(), Span { start: 0, end: 0 }),
+                    Value::String("World".into(), Span { start: 0, end: 0 }),
+                ]),
+                Span { start: 0, end: 0 },
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod get {
+    use super::*;
+    use ocypode_lang::errors::ErrorKind;
+
+    fn int(int: i32) -> Value {
+        Value::Int(int.into(), Span { start: 0, end: 0 })
+    }
+
+    /// Creates an array `[1, 2]`.
+    fn array() -> Value {
+        Value::Array(Arc::new(vec![int(1), int(2)]), Span { start: 0, end: 0 })
+    }
+
+    #[test]
+    fn test_array_index() {
+        assert_eq!(
+            get(vec![array(), int(0)], Span { start: 0, end: 0 }).unwrap(),
+            int(1)
+        );
+        assert_eq!(
+            get(vec![array(), int(1)], Span { start: 0, end: 0 }).unwrap(),
+            int(2)
+        );
+    }
+
+    #[test]
+    fn test_out_of_array() {
+        for index in [int(2), int(-1)] {
+            assert_eq!(
+                get(vec![array(), index], Span { start: 0, end: 0 }).unwrap(),
+                Value::Nil(Span { start: 0, end: 0 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_index() {
+        let index = Value::String("0".into(), Span { start: 0, end: 0 });
+        assert!(matches!(
+            get(vec![array(), index], Span { start: 0, end: 0 }).unwrap_err().kind,
+            ErrorKind::UnexpectedType(expected, actual) if expected == "Int" && actual == "string"
+        ));
+        assert!(matches!(
+            get(vec![int(1), int(0)], Span { start: 0, end: 0 }).unwrap_err().kind,
+            ErrorKind::UnexpectedType(expected, actual) if expected == "Array or Map" && actual == "int"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod map {
+    use super::*;
+    use ocypode_lang::errors::ErrorKind;
+
+    fn string(string: &str) -> Value {
+        Value::String(string.into(), Span { start: 0, end: 0 })
+    }
+
+    fn int(int: i32) -> Value {
+        Value::Int(int.into(), Span { start: 0, end: 0 })
+    }
+
+    /// Creates a map `{"a": 1, 2: "b"}`.
+    fn map() -> Value {
+        Value::Map(
+            Arc::new(vec![(string("a"), int(1)), (int(2), string("b"))]),
+            Span { start: 0, end: 0 },
+        )
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(map().to_string(), r#"{"a": 1, 2: "b"}"#);
+        let string_values = Value::Map(
+            Arc::new(vec![
+                (string("a"), string("b c")),
+                (int(1), string("say \"hi\"\n")),
+            ]),
+            Span { start: 0, end: 0 },
+        );
+        assert_eq!(
+            string_values.to_string(),
+            r#"{"a": "b c", 1: "say \"hi\"\n"}"#
+        );
+        let string_key = Value::Map(
+            Arc::new(vec![(string("2"), int(1))]),
+            Span { start: 0, end: 0 },
+        );
+        let int_key = Value::Map(Arc::new(vec![(int(2), int(1))]), Span { start: 0, end: 0 });
+        assert_ne!(string_key.to_string(), int_key.to_string());
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(
+            get(vec![map(), string("a")], Span { start: 0, end: 0 }).unwrap(),
+            int(1)
+        );
+        assert_eq!(
+            get(vec![map(), int(2)], Span { start: 0, end: 0 }).unwrap(),
+            string("b")
+        );
+        assert_eq!(
+            get(vec![map(), string("b")], Span { start: 0, end: 0 }).unwrap(),
+            Value::Nil(Span { start: 0, end: 0 })
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        assert_eq!(
+            set(vec![map(), string("a"), int(3)], Span { start: 0, end: 0 }).unwrap(),
+            Value::Map(
+                Arc::new(vec![(string("a"), int(3)), (int(2), string("b")),]),
+                Span { start: 0, end: 0 },
+            )
+        );
+        assert_eq!(
+            set(vec![map(), int(3), int(3)], Span { start: 0, end: 0 }).unwrap(),
+            Value::Map(
+                Arc::new(vec![
+                    (string("a"), int(1)),
+                    (int(2), string("b")),
+                    (int(3), int(3)),
+                ]),
+                Span { start: 0, end: 0 },
+            )
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        assert_eq!(
+            remove(vec![map(), string("a")], Span { start: 0, end: 0 }).unwrap(),
+            Value::Map(
+                Arc::new(vec![(int(2), string("b"))]),
                 Span { start: 0, end: 0 },
             )
         );
+        assert_eq!(
+            remove(vec![map(), string("b")], Span { start: 0, end: 0 }).unwrap(),
+            map()
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(
+            keys(vec![map()], Span { start: 0, end: 0 }).unwrap(),
+            Value::Array(
+                Arc::new(vec![string("a"), int(2)]),
+                Span { start: 0, end: 0 },
+            )
+        );
+    }
+
+    #[test]
+    fn test_has() {
+        for (key, expected) in [(string("a"), true), (int(2), true), (string("2"), false)] {
+            assert_eq!(
+                has(vec![map(), key], Span { start: 0, end: 0 }).unwrap(),
+                Value::Bool(expected, Span { start: 0, end: 0 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(len(vec![map()], Span { start: 0, end: 0 }).unwrap(), int(2));
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let key = Value::Float("1.5".parse().unwrap(), Span { start: 0, end: 0 });
+        for result in [
+            get(vec![map(), key.clone()], Span { start: 0, end: 0 }),
+            set(vec![map(), key.clone(), int(1)], Span { start: 0, end: 0 }),
+            remove(vec![map(), key.clone()], Span { start: 0, end: 0 }),
+            has(vec![map(), key], Span { start: 0, end: 0 }),
+        ] {
+            assert!(matches!(
+                result.unwrap_err().kind,
+                ErrorKind::UnexpectedType(expected, actual) if expected == "String or Int" && actual == "float"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_not_a_map() {
+        let result = keys(vec![int(1)], Span { start: 0, end: 0 });
+        assert!(matches!(
+            result.unwrap_err().kind,
+            ErrorKind::UnexpectedType(expected, actual) if expected == "Map" && actual == "int"
+        ));
+    }
+
+    #[test]
+    fn test_map_literal() {
+        let source = r#"~main<argc><argv>{<
+            m = {"a": 1, "b": 2, "a": 3};
+            if m == {"b": 2, "a": 3} {< return 1; >};
+            return 0;
+        >}"#;
+        assert_eq!(run(source).unwrap(), 1);
+
+        let source = "~main<argc><argv>{< m = {[]: 1}; return 0; >}";
+        let err = run(source).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::UnexpectedType(..)));
+        assert_eq!(err.span, (25, 2).into());
     }
 }
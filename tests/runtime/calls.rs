@@ -0,0 +1,208 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_call_returned_function() {
+    let source = "~make_adder<n>{<
+        return <x>{< return x + n; >};
+    >}
+    ~main<argc><argv>{<
+        return (make_adder<1>)<2>;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_chained_calls() {
+    let source = "~curry<a>{<
+        return <b>{< return <c>{< return a * b * c; >}; >};
+    >}
+    ~main<argc><argv>{<
+        return ((curry<2>)<3>)<4>;
+    >}";
+    assert_eq!(run(source).unwrap(), 24);
+}
+
+#[test]
+fn test_call_array_element() {
+    let source = "~main<argc><argv>{<
+        functions = [<x>{< return x + 1; >}, <x>{< return x * 2; >}];
+        return (get<functions><1>)<5>;
+    >}";
+    assert_eq!(run(source).unwrap(), 10);
+}
+
+#[test]
+fn test_call_map_value() {
+    let source = r#"~main<argc><argv>{<
+        operations = {"neg": <x>{< return -x; >}};
+        return (get<operations><"neg">)<-7>;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 7);
+}
+
+#[test]
+fn test_call_anonymous_function_in_parentheses() {
+    let source = "~main<argc><argv>{<
+        return (<x>{< return x; >})<9>;
+    >}";
+    assert_eq!(run(source).unwrap(), 9);
+}
+
+#[test]
+fn test_call_non_function() {
+    let source = "~main<argc><argv>{<
+        return (1 + 2)<3>;
+    >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::NotCallable(span) if span == (35, 10)));
+    assert_eq!(err.span, (36, 5).into());
+
+    let source = "~main<argc><argv>{<
+        functions = [1];
+        return (get<functions><0>)<3>;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::NotCallable(_)
+    ));
+}
+
+#[test]
+fn test_default_params() {
+    let source = "~scale<value><factor = 2><offset = 0>{<
+        return value * factor + offset;
+    >}
+    ~main<argc><argv>{<
+        return scale<3> + scale<2><3> + scale<2><1><10>;
+    >}";
+    assert_eq!(run(source).unwrap(), 24);
+}
+
+#[test]
+fn test_default_uses_params() {
+    // The default values are executed in the function scope when the function is called.
+    let source = "~base{< return 10; >}
+    ~area<width><height = width + base<>>{<
+        return height;
+    >}
+    ~main<argc><argv>{<
+        return area<2> + area<1><height = 5>;
+    >}";
+    assert_eq!(run(source).unwrap(), 17);
+}
+
+#[test]
+fn test_named_args() {
+    let source = r#"~greet<name><greeting = "Hello"><punctuation = "!">{<
+        return format<"{}, {}{}"><greeting><name><punctuation>;
+    >}
+    ~main<argc><argv>{<
+        first = greet<"Ali"><punctuation = "?">;
+        second = greet<punctuation = "."><name = "Sara"><greeting = "Hi">;
+        if first == "Hello, Ali?" && second == "Hi, Sara." {< return 1; >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_named_args_with_pack() {
+    let source = "~count<first><flag = 0><*rest>{<
+        return len<rest> * 10 + flag;
+    >}
+    ~main<argc><argv>{<
+        return count<1> + count<1><2><3><4> + count<first = 1><flag = 5>;
+    >}";
+    assert_eq!(run(source).unwrap(), 27);
+}
+
+#[test]
+fn test_anonymous_function_defaults() {
+    let source = "~main<argc><argv>{<
+        offset = 4;
+        add = <x><y = offset>{< return x + y; >};
+        return add<1>;
+    >}";
+    assert_eq!(run(source).unwrap(), 5);
+}
+
+#[test]
+fn test_unknown_argument() {
+    let source = "~foo<a><b = 1>{< return a; >}
+    ~main<argc><argv>{< return foo<1><c = 2>; >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnknownArgument(name, _, func) if name == "c" && func == "foo"
+    ));
+}
+
+#[test]
+fn test_duplicate_argument() {
+    for source in [
+        "~foo<a><b = 1>{< return a; >}
+        ~main<argc><argv>{< return foo<1><a = 2>; >}",
+        "~foo<a><b = 1>{< return a; >}
+        ~main<argc><argv>{< return foo<b = 1><a = 1><b = 2>; >}",
+        "~foo<a><*rest>{< return a; >}
+        ~main<argc><argv>{< return foo<1><2><rest = [3]>; >}",
+    ] {
+        assert!(
+            matches!(
+                run(source).unwrap_err().kind,
+                ErrorKind::DuplicateArgument(..)
+            ),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_missing_argument() {
+    let source = "~foo<a><b><c = 1>{< return a; >}
+    ~main<argc><argv>{< return foo<b = 1>; >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::MissingArgument(name, _, func) if name == "a" && func == "foo"
+    ));
+
+    let source = "~main<argc><argv>{< return get<[1]>; >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::MissingArgument(name, _, func) if name == "key" && func == "get"
+    ));
+}
+
+#[test]
+fn test_too_many_arguments() {
+    let source = "~foo<a><b = 1>{< return a; >}
+    ~main<argc><argv>{< return foo<1><2><3>; >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UncorrectArguments(3, ..)
+    ));
+}
+
+#[test]
+fn test_locals_shadow_global_functions() {
+    let source = "~double<x>{< return x * 2; >}
+    ~apply<double>{< return double; >}
+    ~main<argc><argv>{<
+        ~double<x>{< return x * 3; >};
+        return double<2> + apply<10>;
+    >}";
+    assert_eq!(run(source).unwrap(), 16);
+}
+
+#[test]
+fn test_already_declared_variable() {
+    let source = "~main<argc><argv>{<
+        x = 1;
+        x = 2;
+        return x;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::AlreadyDeclared(name, _) if name == "x"
+    ));
+}
@@ -0,0 +1,101 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_function_factory() {
+    let source = "~make_adder<n>{<
+        return <x>{< return x + n; >};
+    >}
+    ~main<argc><argv>{<
+        add5 = make_adder<5>;
+        return add5<3>;
+    >}";
+    assert_eq!(run(source).unwrap(), 8);
+}
+
+#[test]
+fn test_callback() {
+    let source = "~apply<f><value>{<
+        return f<value>;
+    >}
+    ~main<argc><argv>{<
+        factor = 3;
+        return apply<<x>{< return x * factor; >}><7>;
+    >}";
+    assert_eq!(run(source).unwrap(), 21);
+}
+
+#[test]
+fn test_capture_moves_variable() {
+    let source = "~main<argc><argv>{<
+        count = 10;
+        closure = <>{< return count; >};
+        return count;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(ident, _) if ident == "count"
+    ));
+}
+
+#[test]
+fn test_capture_local_function() {
+    let source = "~main<argc><argv>{<
+        ~double<x>{< return x * 2; >};
+        closure = <x>{< return double<x>; >};
+        value = closure<4>;
+        return double<value>;
+    >}";
+    assert_eq!(run(source).unwrap(), 16);
+}
+
+#[test]
+fn test_capture_from_block_scope() {
+    let source = "~main<argc><argv>{<
+        closure = nil;
+        if true {<
+            offset = 2;
+            closure = <x>{< return x + offset; >};
+            return closure<1>;
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_nested_closure() {
+    let source = "~main<argc><argv>{<
+        base = 10;
+        outer = <>{<
+            inner = <x>{< return base + x; >};
+            return inner;
+        >};
+        inner = outer<>;
+        return inner<5>;
+    >}";
+    assert_eq!(run(source).unwrap(), 15);
+}
+
+#[test]
+fn test_params_and_locals_are_not_captured() {
+    let source = "~main<argc><argv>{<
+        x = 1;
+        y = 2;
+        closure = <x>{<
+            y = 5;
+            return x + y;
+        >};
+        return closure<3> + x + y;
+    >}";
+    assert_eq!(run(source).unwrap(), 11);
+}
+
+#[test]
+fn test_immediately_called_closure() {
+    let source = "~main<argc><argv>{<
+        value = 6;
+        return <x>{< return value * x; >}<2>;
+    >}";
+    assert_eq!(run(source).unwrap(), 12);
+}
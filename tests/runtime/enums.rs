@@ -0,0 +1,175 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_variant_match() {
+    let source = "enum Shape<Circle<radius>><Rect<width><height>><Empty>;
+    ~area<shape>{<
+        return match shape {<
+            Circle<r> => r * 3,
+            Rect<w><h> => w * h,
+            Empty => 0,
+        >};
+    >}
+    ~main<argc><argv>{<
+        return area<Circle<2>> + area<Rect<2><5>> + area<Empty>;
+    >}";
+    assert_eq!(run(source).unwrap(), 16);
+}
+
+#[test]
+fn test_nested_variant_patterns() {
+    let source = "enum Option<Some<value>><None>;
+    ~main<argc><argv>{<
+        value = Some<Some<[1, 2]>>;
+        return match value {<
+            Some<None> => 1,
+            Some<Some<[1, b]>> => b * 10,
+            _ => 0,
+        >};
+    >}";
+    assert_eq!(run(source).unwrap(), 20);
+}
+
+#[test]
+fn test_variant_pattern_guard() {
+    let source = "enum Option<Some<value>><None>;
+    ~main<argc><argv>{<
+        return match Some<3> {<
+            Some<n> if n > 5 => 1,
+            Some<1> => 2,
+            Some<n> => n,
+            None => 0,
+        >};
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_unit_variant_value() {
+    let source = r#"enum Option<Some<value>><None>;
+    ~main<argc><argv>{<
+        none = None;
+        text = format<"{} {}"><none><Some<"a">>;
+        if text == "None Some<\"a\">" {< return 1; >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_variants_equality() {
+    let source = "enum Option<Some<value>><None>;
+    enum Other<Some2<value>>;
+    ~main<argc><argv>{<
+        same = Some<[1, 2]> == Some<[1, 2.0]> && None == None;
+        different = Some<1> != Some<2> && Some<1> != None;
+        other_type = Some<1> != Some2<1>;
+        if same && different && other_type {< return 1; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_variant_moves_value() {
+    let source = "enum Option<Some<value>><None>;
+    ~main<argc><argv>{<
+        value = [1];
+        option = Some<value>;
+        return len<value>;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(name, _) if name == "value"
+    ));
+}
+
+#[test]
+fn test_variant_arguments() {
+    for (source, args) in [
+        ("return Some<1><2>;", 2),
+        ("x = Some; return 0;", 0),
+        ("return None<1>;", 1),
+    ] {
+        let source = format!("enum Option<Some<value>><None>;\n~main<argc><argv>{{< {source} >}}");
+        assert!(
+            matches!(
+                run(&source).unwrap_err().kind,
+                ErrorKind::UncorrectArguments(count, _, _, ref name) if count == args && (name == "Some" || name == "None")
+            ),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_invalid_variant_patterns() {
+    // A pattern of an undeclared variant (e.g. a misspelled one) or with a wrong number of fields never matches.
+    let source = |pattern| {
+        format!(
+            "enum Option<Some<value>><None>;
+            ~main<argc><argv>{{< return match Some<1> {{< {pattern} => 1, _ => 2 >}}; >}}"
+        )
+    };
+    assert!(matches!(
+        run(&source("Sone<v>")).unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(ref name) if name == "Sone"
+    ));
+    assert!(matches!(
+        run(&source("Some<a><b>")).unwrap_err().kind,
+        ErrorKind::UncorrectArguments(2, _, _, ref name) if name == "Some"
+    ));
+}
+
+#[test]
+fn test_variant_unpack() {
+    let source = "enum Pair<Pair<left><right>>;
+    ~main<argc><argv>{<
+        values = [3, 4];
+        return match Pair<...values> {< Pair<a><b> => a * b >};
+    >}";
+    assert_eq!(run(source).unwrap(), 12);
+}
+
+#[test]
+fn test_already_declared_variant() {
+    for source in [
+        "enum Option<Some<value>><None>;\nenum Maybe<Just<value>><None>;",
+        "enum Option<Some<value>><None>;\nenum Option<Just<value>>;",
+        "record Point<x><y>;\nenum Point<Point<x><y>>;",
+    ] {
+        let source = format!("{source}\n~main<argc><argv>{{< return 0; >}}");
+        assert!(
+            matches!(
+                run(&source).unwrap_err().kind,
+                ErrorKind::AlreadyDeclared(..)
+            ),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_type_name() {
+    let source = "enum Option<Some<value>><None>;
+    ~main<argc><argv>{<
+        return len<None>;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnexpectedType(_, actual) if actual == "Option"
+    ));
+}
+
+#[test]
+fn test_variant_closure() {
+    let source = "enum Option<Some<value>><None>;
+    ~main<argc><argv>{<
+        wrap = <value>{< return Some<value>; >};
+        option = wrap<5>;
+        unwrap = <>{< return match option {< Some<v> => v, None => 0 >}; >};
+        return unwrap<>;
+    >}";
+    assert_eq!(run(source).unwrap(), 5);
+}
@@ -0,0 +1,139 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_if_true() {
+    let source = "~main<argc><argv>{<
+        if true {< return 1; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_if_false() {
+    let source = "~main<argc><argv>{<
+        if false {< return 1; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 0);
+}
+
+#[test]
+fn test_else() {
+    let source = "~main<argc><argv>{<
+        if nil {< return 1; >} else {< return 2; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_else_if() {
+    let source = "~main<argc><argv>{<
+        if 0 {<
+            return 1;
+        >} else if \"\" {<
+            return 2;
+        >} else if [1] {<
+            return 3;
+        >} else {<
+            return 4;
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_truthiness() {
+    for (value, expected) in [
+        ("true", 1),
+        ("false", 0),
+        ("nil", 0),
+        ("0", 0),
+        ("1", 1),
+        ("0.0", 0),
+        ("0.5", 1),
+        ("\"\"", 0),
+        ("\"a\"", 1),
+        ("[]", 0),
+        ("[nil]", 1),
+        ("<>{<>}", 1),
+    ] {
+        let source = format!(
+            "~main<argc><argv>{{<
+                if {value} {{< return 1; >}};
+                return 0;
+            >}}"
+        );
+        assert_eq!(run(&source).unwrap(), expected, "`{}` truthiness", value);
+    }
+}
+
+#[test]
+fn test_if_condition_call() {
+    let source = "~is_empty<list>{<
+        if len<list> {< return false; >};
+        return true;
+    >}
+    ~main<argc><argv>{<
+        if is_empty<[]> {< return 7; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 7);
+}
+
+#[test]
+fn test_if_block_scope() {
+    let source = "~main<argc><argv>{<
+        x = 1;
+        if true {<
+            x = 2;
+            return x;
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+
+    let source = "~main<argc><argv>{<
+        if true {< x = 2; >};
+        return x;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(..)
+    ));
+}
+
+#[test]
+fn test_if_moved_inner_variable() {
+    // The moved inner variable shadows the outer one until the end of the block.
+    let source = "~main<argc><argv>{<
+        x = 1;
+        if true {<
+            for x in [2] {<
+                y = x;
+                return x;
+            >};
+        >};
+        return 0;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(name, _) if name == "x"
+    ));
+}
+
+#[test]
+fn test_if_moves_outer_variable() {
+    let source = "~main<argc><argv>{<
+        x = 3;
+        if true {< y = x; >};
+        return x;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(..)
+    ));
+}
@@ -0,0 +1,104 @@
+use ocypode_lang::{
+    errors::{ErrorKind, Result as OYResult},
+    modules::{self, Sources},
+    parser::OYParser,
+    runtime::{interpreter::Interpreter, vm::Vm},
+};
+
+/// Loads and runs the given file from the `tests/runtime/modules` directory, with the interpreter and the virtual machine.
+fn run_file(name: &str) -> (Sources, OYResult<u8>) {
+    let path = format!("tests/runtime/modules/{name}");
+    let source = std::fs::read_to_string(&path).unwrap();
+    let mut sources = Sources::default();
+    let modules = modules::load(&mut sources, &path, source);
+    let result = modules
+        .clone()
+        .and_then(|modules| Interpreter::new().interpret_modules(modules, 0, vec![]));
+    let vm_result = modules.and_then(|modules| Vm::new().interpret_modules(modules, 0, vec![]));
+    assert_eq!(format!("{result:?}"), format!("{vm_result:?}"));
+    (sources, result)
+}
+
+#[test]
+fn test_import() {
+    // `numbers.oy` is imported twice, by the main file and by `shapes/square.oy`.
+    let (_, result) = run_file("main.oy");
+    assert_eq!(result.unwrap(), 14);
+}
+
+#[test]
+fn test_sources() {
+    let (sources, _) = run_file("main.oy");
+    let main = sources.find(0);
+    assert_eq!(main.name, "tests/runtime/modules/main.oy");
+    let square = sources.find(main.code.len() + 1);
+    assert_eq!(square.name, "tests/runtime/modules/shapes/square.oy");
+    assert_eq!(square.offset, main.code.len() + 1);
+    let numbers = sources.find(square.offset + square.code.len() + 1);
+    assert_eq!(numbers.name, "tests/runtime/modules/shapes/../numbers.oy");
+}
+
+#[test]
+fn test_imported_function_conflict() {
+    let (sources, result) = run_file("conflict.oy");
+    let err = result.unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::AlreadyDeclared(ref name, _) if name == "double"));
+    assert_eq!(
+        sources.find(err.span.offset()).name,
+        "tests/runtime/modules/conflict.oy"
+    );
+}
+
+#[test]
+fn test_import_without_loader() {
+    let source = "import \"numbers.oy\";\n~main<argc><argv>{< return 0; >}";
+    let program = OYParser::parse_program(source).unwrap();
+    let err = Interpreter::new()
+        .interpret(program.clone(), 0, vec![])
+        .unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::InvalidImport(..)));
+    let err = Vm::new().interpret(program, 0, vec![]).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::InvalidImport(..)));
+}
+
+#[test]
+fn test_import_records() {
+    // The records of `square` are created in `geometry.oy`, where `Size` is declared.
+    let (_, result) = run_file("records.oy");
+    assert_eq!(result.unwrap(), 4);
+}
+
+#[test]
+fn test_private_record_not_imported() {
+    let (_, result) = run_file("private_record.oy");
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(ref name) if name == "Size"
+    ));
+}
+
+#[test]
+fn test_import_enums() {
+    let (_, result) = run_file("enums.oy");
+    assert_eq!(result.unwrap(), 4);
+}
+
+#[test]
+fn test_private_enum_not_imported() {
+    let (_, result) = run_file("private_enum.oy");
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(ref name) if name == "Red"
+    ));
+}
+
+#[test]
+fn test_imported_variant_conflict() {
+    let (sources, result) = run_file("variant_conflict.oy");
+    let err = result.unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::AlreadyDeclared(ref name, _) if name == "None"));
+    assert_eq!(
+        sources.find(err.span.offset()).name,
+        "tests/runtime/modules/variant_conflict.oy"
+    );
+}
@@ -0,0 +1,248 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_loop_break() {
+    let source = "~main<argc><argv>{<
+        loop {< break; >};
+        return 1;
+    >}";
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_loop_break_value() {
+    let source = "~main<argc><argv>{<
+        value = loop {< break 5; >};
+        return value;
+    >}";
+    assert_eq!(run(source).unwrap(), 5);
+
+    let source = "~main<argc><argv>{<
+        return loop {< break 6; >};
+    >}";
+    assert_eq!(run(source).unwrap(), 6);
+}
+
+#[test]
+fn test_break_without_value() {
+    let source = "~main<argc><argv>{<
+        value = loop {< break; >};
+        if value {< return 1; >};
+        return 2;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_return_inside_loop() {
+    let source = "~main<argc><argv>{<
+        loop {<
+            if true {< return 3; >};
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+
+    let source = "~main<argc><argv>{<
+        value = loop {< return 4; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 4);
+}
+
+#[test]
+fn test_while_false() {
+    let source = "~main<argc><argv>{<
+        value = while false {< return 1; >};
+        if value {< return 2; >};
+        return 3;
+    >}";
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_while_break_value() {
+    let source = "~main<argc><argv>{<
+        return while true {< break 7; >};
+    >}";
+    assert_eq!(run(source).unwrap(), 7);
+}
+
+#[test]
+fn test_continue() {
+    // The first iteration continues, so the second iteration uses the moved `skip` instead of breaking.
+    let source = "~main<argc><argv>{<
+        skip = true;
+        return loop {<
+            if skip {< continue; >};
+            break 8;
+        >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(ident, _) if ident == "skip"
+    ));
+}
+
+#[test]
+fn test_nested_loops() {
+    let source = "~main<argc><argv>{<
+        return loop {<
+            inner = loop {< break 4; >};
+            break inner;
+        >};
+    >}";
+    assert_eq!(run(source).unwrap(), 4);
+}
+
+#[test]
+fn test_loop_scope() {
+    // Each iteration has its own scope, so `value` is declared again in the second iteration,
+    // and the error is about the moved `first`.
+    let source = "~main<argc><argv>{<
+        first = true;
+        loop {<
+            value = 9;
+            if first {< continue; >};
+        >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(ident, _) if ident == "first"
+    ));
+}
+
+#[test]
+fn test_while_counter() {
+    // The assignments of the block update the variables of the function, so the condition changes and
+    // the moved values are moved back into them.
+    let source = "~main<argc><argv>{<
+        first = true;
+        second = true;
+        counted = [];
+        while first {<
+            first = second;
+            second = false;
+            counted = push<counted><true>;
+        >};
+        return len<counted>;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_loop_moves_outer_variable() {
+    let source = "~main<argc><argv>{<
+        value = 1;
+        loop {<
+            moved = value;
+        >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(..)
+    ));
+}
+
+#[test]
+fn test_break_outside_loop() {
+    let source = "~main<argc><argv>{<
+        if true {< break; >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::OutsideLoop(..)
+    ));
+
+    let source = "~main<argc><argv>{<
+        loop {< <>{< continue; >}<>; >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::OutsideLoop(..)
+    ));
+}
+
+#[test]
+fn test_for_array() {
+    let source = "~main<argc><argv>{<
+        for list in [[], [], [1]] {<
+            if len<list> {< return 4; >};
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 4);
+}
+
+#[test]
+fn test_for_order() {
+    let source = "~main<argc><argv>{<
+        for item in [1, 0] {<
+            if item {< continue; >};
+            return 5;
+        >};
+        return 6;
+    >}";
+    assert_eq!(run(source).unwrap(), 5);
+}
+
+#[test]
+fn test_for_string() {
+    let source = "~main<argc><argv>{<
+        for char in \"ab\" {< return len<char>; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 1);
+
+    let source = "~main<argc><argv>{<
+        for char in \"\" {< return 1; >};
+        return 2;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_for_break_value() {
+    let source = "~main<argc><argv>{<
+        return for item in [0, 5] {<
+            if item {< break 9; >};
+        >};
+    >}";
+    assert_eq!(run(source).unwrap(), 9);
+}
+
+#[test]
+fn test_for_shadow_item() {
+    let source = "~main<argc><argv>{<
+        for item in [1] {<
+            item = 2;
+            return item;
+        >};
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_for_moves_iterable() {
+    let source = "~main<argc><argv>{<
+        list = [1];
+        for item in list {< >};
+        return len<list>;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(ident, _) if ident == "list"
+    ));
+}
+
+#[test]
+fn test_for_not_iterable() {
+    let source = "~main<argc><argv>{<
+        for item in 5 {< >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnexpectedType(..)
+    ));
+}
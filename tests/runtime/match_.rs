@@ -0,0 +1,114 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_literal_patterns() {
+    let source = r#"~describe<value>{<
+        return match value {<
+            nil => 1,
+            0 => 2,
+            -1 => 4,
+            "a" => 8,
+            true => 16,
+            _ => 32,
+        >};
+    >}
+    ~main<argc><argv>{<
+        return describe<nil> + describe<0> + describe<-1> + describe<"a"> + describe<true> + describe<"b">;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 63);
+}
+
+#[test]
+fn test_wildcard() {
+    let source = "~main<argc><argv>{<
+        return match [1, 2] {< 1 => 1, _ => 2 >};
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_ident_binding() {
+    let source = "~main<argc><argv>{<
+        return match 5 {< 1 => 1, n => n * 2 >};
+    >}";
+    assert_eq!(run(source).unwrap(), 10);
+}
+
+#[test]
+fn test_array_patterns() {
+    let source = "~first_or_length<array>{<
+        return match array {<
+            [] => 0,
+            [single] => single,
+            [first, second] => first + second,
+            [_, *rest] => len<rest>,
+        >};
+    >}
+    ~main<argc><argv>{<
+        return first_or_length<[]> + first_or_length<[3]> + first_or_length<[4, 5]> + first_or_length<[1, 1, 1, 1]>;
+    >}";
+    assert_eq!(run(source).unwrap(), 3 + 9 + 3);
+}
+
+#[test]
+fn test_nested_array_patterns() {
+    let source = "~main<argc><argv>{<
+        return match [[1, 2], 3] {< [[1, x], y] => x * y, _ => 0 >};
+    >}";
+    assert_eq!(run(source).unwrap(), 6);
+}
+
+#[test]
+fn test_guard() {
+    let source = "~sign<number>{<
+        return match number {<
+            n if n > 0 => 1,
+            n if n < 0 => 2,
+            _ => 4,
+        >};
+    >}
+    ~main<argc><argv>{<
+        return sign<5> + sign<-5> + sign<0>;
+    >}";
+    assert_eq!(run(source).unwrap(), 7);
+}
+
+#[test]
+fn test_bindings_scope() {
+    let source = "~main<argc><argv>{<
+        value = match 1 {< n => n >};
+        return n;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(ident) if ident == "n"
+    ));
+}
+
+#[test]
+fn test_no_matching_arm() {
+    let source = "~main<argc><argv>{<
+        return match 3 {< 1 => 1, 2 => 2 >};
+    >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::NoMatchingArm(value) if value == "3"));
+    assert_eq!(err.span, (41, 1).into());
+
+    let source = "~main<argc><argv>{<
+        return match [1, 2, 3] {< [a, b] => 1, [a] => 2 >};
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::NoMatchingArm(_)
+    ));
+}
+
+#[test]
+fn test_match_statement() {
+    let source = "~main<argc><argv>{<
+        match 1 {< 1 => println<\"one\">, _ => println<\"other\"> >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 0);
+}
@@ -1 +1,35 @@
+mod annotations;
+mod borrow;
 mod builtins;
+mod calls;
+mod closures;
+mod enums;
+mod if_;
+mod imports;
+mod loops;
+mod match_;
+mod natives;
+mod operators;
+mod records;
+mod recursion;
+mod traceback;
+mod try_;
+
+use ocypode_lang::{
+    errors::Result as OYResult,
+    parser::OYParser,
+    runtime::{interpreter::Interpreter, vm::Vm},
+};
+
+/// Parse and run the given source code with the interpreter and the virtual machine, returns the exit code of the program.
+/// Both of them must have the same result, the same exit code or the same error.
+pub fn run(source: &str) -> OYResult<u8> {
+    let result = Interpreter::new().interpret(OYParser::parse_program(source)?, 0, vec![]);
+    let vm_result = Vm::new().interpret(OYParser::parse_program(source)?, 0, vec![]);
+    assert_eq!(
+        format!("{result:?}"),
+        format!("{vm_result:?}"),
+        "The virtual machine has a different result"
+    );
+    result
+}
@@ -0,0 +1,187 @@
+use bigdecimal::{BigDecimal, Zero};
+use ocypode_lang::{
+    ast::Span,
+    errors::{Error as OYError, ErrorKind, Result as OYResult},
+    modules::Module,
+    ownership,
+    parser::OYParser,
+    runtime::{interpreter::Interpreter, value::Value, vm::Vm},
+    types,
+};
+
+/// Registers the native functions of the tests on the given interpreter or virtual machine.
+macro_rules! with_natives {
+    ($engine:expr) => {{
+        let mut engine = $engine;
+        engine
+            .register_function("double", &[("number", false)], double)
+            .unwrap();
+        engine
+            .register_function("sum", &[("first", false), ("rest", true)], sum)
+            .unwrap();
+        engine.register_function("fail", &[], fail).unwrap();
+        engine
+    }};
+}
+
+/// Doubles the given integer.
+fn double(args: Vec<Value>, span: Span) -> OYResult<Value> {
+    match &args[0] {
+        Value::Int(number, _) => Ok(Value::Int(number * BigDecimal::from(2), span)),
+        value => Err(OYError::new(
+            ErrorKind::UnexpectedType("int".to_owned(), value.type_name().to_owned()),
+            value.span(),
+        )),
+    }
+}
+
+/// Sums the first integer and the packed integers.
+fn sum(args: Vec<Value>, span: Span) -> OYResult<Value> {
+    let Value::Array(rest, _) = &args[1] else {
+        unreachable!("The pack param is an array")
+    };
+    let mut total = BigDecimal::zero();
+    for value in std::iter::once(&args[0]).chain(rest.iter()) {
+        match value {
+            Value::Int(number, _) => total += number,
+            value => {
+                return Err(OYError::new(
+                    ErrorKind::UnexpectedType("int".to_owned(), value.type_name().to_owned()),
+                    value.span(),
+                ))
+            }
+        }
+    }
+    Ok(Value::Int(total, span))
+}
+
+/// Fails with a runtime error at the call.
+fn fail(_: Vec<Value>, span: Span) -> OYResult<Value> {
+    Err(OYError::new(
+        ErrorKind::Runtime("The service is unavailable".to_owned()),
+        span,
+    ))
+}
+
+/// Parse and run the given source code with the native functions, on the interpreter and the virtual machine.
+fn run(source: &str) -> OYResult<u8> {
+    let result =
+        with_natives!(Interpreter::new()).interpret(OYParser::parse_program(source)?, 0, vec![]);
+    let vm_result = with_natives!(Vm::new()).interpret(OYParser::parse_program(source)?, 0, vec![]);
+    assert_eq!(
+        format!("{result:?}"),
+        format!("{vm_result:?}"),
+        "The virtual machine has a different result"
+    );
+    result
+}
+
+#[test]
+fn test_native_function() {
+    let source = "~main<argc><argv>{< return double<21>; >}";
+    assert_eq!(run(source).unwrap(), 42);
+}
+
+#[test]
+fn test_native_pack_param() {
+    let source = "~main<argc><argv>{< return sum<1><2><3><4> + sum<5>; >}";
+    assert_eq!(run(source).unwrap(), 15);
+}
+
+#[test]
+fn test_native_error_span() {
+    let source = "~main<argc><argv>{< double<\"one\">; >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedType(..)));
+    assert_eq!(err.span, (27, 5).into());
+
+    let source = "~main<argc><argv>{< x = 1; fail<>; >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Runtime(_)));
+    assert_eq!(err.span, (27, 6).into());
+}
+
+#[test]
+fn test_catch_native_error() {
+    let source = r#"~main<argc><argv>{<
+        try {< fail<>; >} catch err {<
+            if get<err><"message"> == "The service is unavailable" {< return 1; >};
+        >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_native_missing_argument() {
+    let source = "~main<argc><argv>{< return double<>; >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::MissingArgument(..)
+    ));
+}
+
+#[test]
+fn test_register_declared_function() {
+    let result = Interpreter::new().register_function("print", &[("value", false)], double);
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::AlreadyDeclared(..)
+    ));
+    let mut interpreter = with_natives!(Interpreter::new());
+    let result = interpreter.register_function("double", &[("number", false)], double);
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::AlreadyDeclared(..)
+    ));
+}
+
+#[test]
+fn test_register_invalid_params() {
+    let result = Vm::new().register_function("sum", &[("rest", true), ("last", false)], sum);
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::PackedParamNotLast(_)
+    ));
+    let result = Vm::new().register_function("sum", &[("first", true), ("rest", true)], sum);
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::MultiplePackedParams(_)
+    ));
+}
+
+#[test]
+fn test_register_invalid_names() {
+    for name in ["Bad-Name", "badName", "1st", "return", ""] {
+        let result = Interpreter::new().register_function(name, &[], fail);
+        assert!(
+            matches!(result.unwrap_err().kind, ErrorKind::InvalidName(..)),
+            "{name}"
+        );
+    }
+    let result = Vm::new().register_function("double", &[("Number", false)], double);
+    assert!(matches!(
+        result.unwrap_err().kind,
+        ErrorKind::InvalidName(..)
+    ));
+}
+
+#[test]
+fn test_check_with_natives() {
+    let source = "~main<argc><argv>{< x = double<2>; return sum<x><x>; >}";
+    let modules = vec![Module::from_program(OYParser::parse_program(source).unwrap()).unwrap()];
+    let interpreter = with_natives!(Interpreter::new());
+    let builtins = &interpreter.builtins().functions;
+    let errors = ownership::check_modules(&modules, builtins);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind, ErrorKind::UseAfterMove(..)));
+    assert!(types::check_modules(&modules, builtins).is_empty());
+
+    let source = "~main<argc><argv>{< x = double<2>; return double<&x> + x; >}";
+    let modules = vec![Module::from_program(OYParser::parse_program(source).unwrap()).unwrap()];
+    assert!(ownership::check_modules(&modules, builtins).is_empty());
+    assert_eq!(
+        interpreter.interpret_modules(modules, 0, vec![]).unwrap(),
+        12
+    );
+}
@@ -0,0 +1,338 @@
+use super::run;
+use std::sync::Arc;
+
+use ocypode_lang::{
+    ast::*,
+    errors::ErrorKind,
+    runtime::{operators::*, value::Value},
+};
+
+/// Creates a number value from the given string, which is float if it contains a dot.
+fn number(number: &str) -> Value {
+    if number.contains('.') {
+        Value::Float(number.parse().unwrap(), Span::new(0, 0))
+    } else {
+        Value::Int(number.parse().unwrap(), Span::new(0, 0))
+    }
+}
+
+#[cfg(test)]
+mod arithmetic {
+    use super::*;
+
+    #[test]
+    fn test_int_arithmetic() {
+        for (operator, left, right, expected) in [
+            (BinaryOperator::Add, "7", "2", "9"),
+            (BinaryOperator::Sub, "7", "9", "-2"),
+            (BinaryOperator::Mul, "7", "2", "14"),
+            (BinaryOperator::Div, "7", "2", "3"),
+            (BinaryOperator::Div, "-7", "2", "-3"),
+            (BinaryOperator::Mod, "7", "2", "1"),
+            (BinaryOperator::Mod, "-7", "2", "-1"),
+        ] {
+            assert_eq!(
+                binary(operator, number(left), number(right), Span::new(0, 0)).unwrap(),
+                number(expected),
+                "{} {} {}",
+                left,
+                operator,
+                right
+            );
+        }
+    }
+
+    #[test]
+    fn test_float_arithmetic() {
+        for (operator, left, right, expected) in [
+            (BinaryOperator::Add, "0.1", "0.2", "0.3"),
+            (BinaryOperator::Sub, "1.5", "0.5", "1.0"),
+            (BinaryOperator::Mul, "1.5", "2.0", "3.00"),
+            (BinaryOperator::Div, "6.0", "2.0", "3.0"),
+            (BinaryOperator::Div, "1.0", "4.0", "0.25"),
+            (BinaryOperator::Mod, "7.5", "2.0", "1.5"),
+        ] {
+            assert_eq!(
+                binary(operator, number(left), number(right), Span::new(0, 0)).unwrap(),
+                number(expected),
+                "{} {} {}",
+                left,
+                operator,
+                right
+            );
+        }
+    }
+
+    #[test]
+    fn test_int_float_promotion() {
+        for (operator, left, right, expected) in [
+            (BinaryOperator::Add, "1", "2.5", "3.5"),
+            (BinaryOperator::Sub, "2.5", "1", "1.5"),
+            (BinaryOperator::Mul, "2", "1.5", "3.0"),
+            (BinaryOperator::Div, "7", "2.0", "3.5"),
+            (BinaryOperator::Div, "6", "2.0", "3.0"),
+        ] {
+            let result = binary(operator, number(left), number(right), Span::new(0, 0)).unwrap();
+            assert_eq!(result.type_name(), "float");
+            assert_eq!(result, number(expected), "{} {} {}", left, operator, right);
+        }
+    }
+
+    #[test]
+    fn test_float_display() {
+        let result = binary(
+            BinaryOperator::Div,
+            number("6"),
+            number("2.0"),
+            Span::new(0, 0),
+        )
+        .unwrap();
+        assert_eq!(result.to_string(), "3.0");
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        for (operator, left, right) in [
+            (BinaryOperator::Div, "1", "0"),
+            (BinaryOperator::Div, "1.0", "0.0"),
+            (BinaryOperator::Mod, "1", "0"),
+            (BinaryOperator::Mod, "1", "0.0"),
+        ] {
+            assert!(matches!(
+                binary(operator, number(left), number(right), Span::new(0, 0))
+                    .unwrap_err()
+                    .kind,
+                ErrorKind::DivisionByZero
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid_operands() {
+        let err = binary(
+            BinaryOperator::Add,
+            Value::String("1".into(), Span::new(0, 0)),
+            number("1"),
+            Span::new(0, 0),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            ErrorKind::InvalidOperands(operator, left, right)
+                if operator == "+" && left == "string" && right == "int"
+        ));
+    }
+
+    #[test]
+    fn test_negation() {
+        assert_eq!(
+            unary(UnaryOperator::Neg, number("5"), Span::new(0, 0)).unwrap(),
+            number("-5")
+        );
+        assert_eq!(
+            unary(UnaryOperator::Neg, number("-1.5"), Span::new(0, 0)).unwrap(),
+            number("1.5")
+        );
+        assert!(matches!(
+            unary(
+                UnaryOperator::Neg,
+                Value::Nil(Span::new(0, 0)),
+                Span::new(0, 0)
+            )
+            .unwrap_err()
+            .kind,
+            ErrorKind::UnexpectedType(..)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod precedence {
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        for (expression, expected) in [
+            ("1 + 2 * 3", 7),
+            ("(1 + 2) * 3", 9),
+            ("10 - 4 - 3", 3),
+            ("20 / 2 / 5", 2),
+            ("2 * 7 % 4", 2),
+            ("-2 + 5", 3),
+            ("-(2 - 5)", 3),
+            ("--4", 4),
+            ("1 + 2 * (3 - 1) / 4", 2),
+        ] {
+            let source = format!("~main<argc><argv>{{< return {expression}; >}}");
+            assert_eq!(run(&source).unwrap(), expected, "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_operands() {
+        let source = "~double<x>{< return x * 2; >}
+        ~main<argc><argv>{<
+            a = 3;
+            b = [1, 2];
+            return double<a + 1> + len<b> - <>{< return 1; >}<>;
+        >}";
+        assert_eq!(run(source).unwrap(), 9);
+    }
+
+    #[test]
+    fn test_invalid_operand_span() {
+        let source = "~main<argc><argv>{< return 1 + \"1\"; >}";
+        let err = run(source).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidOperands(..)));
+        assert_eq!(err.span, (27, 7).into());
+    }
+}
+
+#[cfg(test)]
+mod comparison {
+    use super::*;
+
+    /// Returns whether the given condition is true.
+    fn condition(expression: &str) -> bool {
+        let source =
+            format!("~main<argc><argv>{{< if {expression} {{< return 1; >}}; return 0; >}}");
+        run(&source).unwrap() == 1
+    }
+
+    #[test]
+    fn test_number_comparison() {
+        for (operator, left, right, expected) in [
+            (BinaryOperator::Lt, "1", "2", true),
+            (BinaryOperator::Lt, "2", "2", false),
+            (BinaryOperator::Le, "2", "2", true),
+            (BinaryOperator::Gt, "2.5", "2", true),
+            (BinaryOperator::Ge, "2", "2.0", true),
+            (BinaryOperator::Ge, "-1", "0", false),
+            (BinaryOperator::Eq, "2", "2.0", true),
+            (BinaryOperator::Ne, "2", "2.5", true),
+        ] {
+            assert_eq!(
+                binary(operator, number(left), number(right), Span::new(0, 0)).unwrap(),
+                Value::Bool(expected, Span::new(0, 0)),
+                "{} {} {}",
+                left,
+                operator,
+                right
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_comparison() {
+        for (expression, expected) in [
+            ("\"abc\" < \"abd\"", true),
+            ("\"b\" > \"abc\"", true),
+            ("\"abc\" <= \"ab\"", false),
+            ("\"abc\" == \"abc\"", true),
+            ("\"abc\" != \"abc\"", false),
+        ] {
+            assert_eq!(condition(expression), expected, "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_equality() {
+        for (expression, expected) in [
+            ("[1, [2, \"a\"]] == [1.0, [2, \"a\"]]", true),
+            ("[1, 2] == [1, 2, 3]", false),
+            ("[1, 2] != [2, 1]", true),
+            ("[] == []", true),
+            ("nil == nil", true),
+            ("true == true", true),
+            ("1 == \"1\"", false),
+            ("nil != false", true),
+            ("[1] == 1", false),
+            ("println == println", true),
+            ("println == print", false),
+        ] {
+            assert_eq!(condition(expression), expected, "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_mismatched_types() {
+        for (left, right) in [
+            (Value::String("a".into(), Span::new(0, 0)), number("1")),
+            (number("1"), Value::Nil(Span::new(0, 0))),
+            (
+                Value::Array(Arc::new(vec![]), Span::new(0, 0)),
+                Value::Array(Arc::new(vec![]), Span::new(0, 0)),
+            ),
+        ] {
+            let err = binary(BinaryOperator::Lt, left, right, Span::new(0, 0)).unwrap_err();
+            assert!(matches!(err.kind, ErrorKind::InvalidOperands(..)));
+        }
+    }
+
+    #[test]
+    fn test_comparison_with_calls() {
+        let source = "~main<argc><argv>{<
+            a = 1;
+            b = 2;
+            c = 3;
+            d = 4;
+            x = a < b && c < d;
+            y = len<[1, 2]> >= 2;
+            if x && y {< return 1; >};
+            return 0;
+        >}";
+        assert_eq!(run(source).unwrap(), 1);
+    }
+}
+
+#[cfg(test)]
+mod logic {
+    use super::*;
+
+    #[test]
+    fn test_logic() {
+        for (expression, expected) in [
+            ("true && 1", 1),
+            ("true && 0", 0),
+            ("nil || \"a\"", 1),
+            ("nil || []", 0),
+            ("!nil", 1),
+            ("!!5", 1),
+            ("!(1 < 2)", 0),
+            ("false || true && false", 0),
+            ("1 < 2 == 2 < 3", 1),
+        ] {
+            let source = format!(
+                "~main<argc><argv>{{< x = {expression}; if x == true {{< return 1; >}}; return 0; >}}"
+            );
+            assert_eq!(run(&source).unwrap(), expected, "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        let source = "~main<argc><argv>{<
+            x = false && 1 / 0;
+            y = true || 1 / 0;
+            if !x && y {< return 1; >};
+            return 0;
+        >}";
+        assert_eq!(run(source).unwrap(), 1);
+
+        let source = "~main<argc><argv>{< x = true && 1 / 0; return 0; >}";
+        assert!(matches!(
+            run(source).unwrap_err().kind,
+            ErrorKind::DivisionByZero
+        ));
+    }
+
+    #[test]
+    fn test_skipped_operand_is_not_moved() {
+        let source = "~main<argc><argv>{<
+            a = 1;
+            x = false && a;
+            return a;
+        >}";
+        assert_eq!(run(source).unwrap(), 1);
+    }
+}
@@ -0,0 +1,236 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_field_access() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 3, y: 4};
+        return point.y;
+    >}";
+    assert_eq!(run(source).unwrap(), 4);
+}
+
+#[test]
+fn test_nested_field_access() {
+    let source = "record Point<x><y>;
+    record Line<start><end>;
+    ~main<argc><argv>{<
+        line = Line {start: Point {x: 1, y: 2}, end: Point {x: 3, y: 4}};
+        return line.end.x * 10 + Point {x: 5, y: 6}.y;
+    >}";
+    assert_eq!(run(source).unwrap(), 36);
+}
+
+#[test]
+fn test_fields_order() {
+    // The fields are written in any order, but they are stored in the declaration order.
+    let source = r#"record Point<x><y>;
+    ~main<argc><argv>{<
+        text = format<"{}"><Point {y: 2, x: 1}>;
+        if text == "Point {x: 1, y: 2}" {< return 1; >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_field_assignment() {
+    let source = "record Point<x><y>;
+    record Line<start><end>;
+    ~main<argc><argv>{<
+        line = Line {start: Point {x: 1, y: 2}, end: Point {x: 3, y: 4}};
+        if true {<
+            line.end.y = 9;
+        >};
+        line.start = Point {x: 5, y: 6};
+        expected = Line {start: Point {x: 5, y: 6}, end: Point {x: 3, y: 9}};
+        if line == expected {< return 1; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_field_access_moves_record() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 3, y: 4};
+        x = point.x;
+        return point.y;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(name, _) if name == "point"
+    ));
+}
+
+#[test]
+fn test_field_assignment_moves_value() {
+    let source = "record Box<value>;
+    ~main<argc><argv>{<
+        first = Box {value: 1};
+        value = [2];
+        first.value = value;
+        return len<value>;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(name, _) if name == "value"
+    ));
+}
+
+#[test]
+fn test_records_equality() {
+    let source = "record Point<x><y>;
+    record Size<x><y>;
+    ~main<argc><argv>{<
+        same = Point {x: 1, y: 2} == Point {y: 2, x: 1.0};
+        different = Point {x: 1, y: 2} != Point {x: 1, y: 3};
+        other_type = Point {x: 1, y: 2} != Size {x: 1, y: 2};
+        if same && different && other_type {< return 1; >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_record_in_function() {
+    let source = "record Counter<count>;
+    ~increment<counter>{<
+        counter.count = counter_count<counter> + 1;
+        return counter;
+    >}
+    ~counter_count<counter>{< return counter.count; >}
+    ~main<argc><argv>{<
+        counter = Counter {count: 1};
+        return increment<counter>.count;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UseAfterMove(name, _) if name == "counter"
+    ));
+
+    let source = "record Counter<count>;
+    ~increment<counter>{<
+        counter.count = 2;
+        return counter;
+    >}
+    ~main<argc><argv>{<
+        counter = Counter {count: 1};
+        return increment<counter>.count;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_missing_fields() {
+    let source = "record Point<x><y><z>;
+    ~main<argc><argv>{<
+        point = Point {y: 1};
+        return 0;
+    >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        ErrorKind::MissingFields(name, fields) if name == "Point" && fields == ["x", "z"]
+    ));
+}
+
+#[test]
+fn test_unknown_field() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 1, y: 2, z: 3};
+        return 0;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnknownField(name, field, _) if name == "Point" && field == "z"
+    ));
+
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 1, y: 2};
+        return point.z;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnknownField(name, field, fields) if name == "Point" && field == "z" && fields == ["x", "y"]
+    ));
+
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 1, y: 2};
+        point.z = 3;
+        return 0;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnknownField(name, field, _) if name == "Point" && field == "z"
+    ));
+}
+
+#[test]
+fn test_undeclared_record() {
+    let source = "~main<argc><argv>{<
+        point = Point {x: 1, y: 2};
+        return 0;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(name) if name == "Point"
+    ));
+}
+
+#[test]
+fn test_already_declared_record() {
+    let source = "record Point<x><y>;
+    record Point<x>;
+    ~main<argc><argv>{< return 0; >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::AlreadyDeclared(name, _) if name == "Point"
+    ));
+}
+
+#[test]
+fn test_field_of_not_record() {
+    let source = "~main<argc><argv>{<
+        number = 1;
+        return number.x;
+    >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        ErrorKind::UnexpectedType(expected, actual) if expected == "Record" && actual == "int"
+    ));
+    assert_eq!(err.span, (55, 6).into());
+}
+
+#[test]
+fn test_type_name() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 1, y: 2};
+        return len<point>;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnexpectedType(_, actual) if actual == "Point"
+    ));
+}
+
+#[test]
+fn test_record_closure() {
+    let source = "record Point<x><y>;
+    ~main<argc><argv>{<
+        point = Point {x: 1, y: 2};
+        move_x = <dx>{<
+            point.x = dx;
+            return point;
+        >};
+        return move_x<7>.x;
+    >}";
+    assert_eq!(run(source).unwrap(), 7);
+}
@@ -0,0 +1,162 @@
+use super::run;
+use ocypode_lang::{
+    errors::ErrorKind,
+    parser::OYParser,
+    runtime::{
+        interpreter::{Interpreter, MAX_CALL_DEPTH},
+        vm::Vm,
+    },
+};
+
+#[test]
+fn test_tail_recursion() {
+    let source = "~count<n><acc>{<
+        if &n == 0 {< return acc; >};
+        return count<n - 1><acc + 1>;
+    >}
+    ~main<argc><argv>{<
+        result = count<20000><0>;
+        return result - 19958;
+    >}";
+    assert_eq!(run(source).unwrap(), 42);
+}
+
+#[test]
+fn test_tail_recursion_with_array() {
+    let source = "~fill<n><list>{<
+        if &n == 0 {< return list; >};
+        return fill<&n - 1><push<list><n>>;
+    >}
+    ~main<argc><argv>{<
+        list = fill<5000><[]>;
+        return len<list> - 4958;
+    >}";
+    assert_eq!(run(source).unwrap(), 42);
+}
+
+#[test]
+fn test_mutual_tail_recursion() {
+    let source = "~is_even<n>{<
+        if &n == 0 {< return true; >};
+        return is_odd<n - 1>;
+    >}
+    ~is_odd<n>{<
+        if &n == 0 {< return false; >};
+        return is_even<n - 1>;
+    >}
+    ~main<argc><argv>{<
+        if is_even<5001> {< return 1; >};
+        return 2;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_tail_call_from_loop() {
+    let source = "~down<n>{<
+        loop {<
+            if &n == 0 {< return 7; >};
+            return down<n - 1>;
+        >};
+    >}
+    ~main<argc><argv>{<
+        result = down<5000>;
+        return result;
+    >}";
+    assert_eq!(run(source).unwrap(), 7);
+}
+
+#[test]
+fn test_stack_overflow() {
+    let source = "~depth<n>{<
+        if &n == 0 {< return 0; >};
+        return 1 + depth<n - 1>;
+    >}
+    ~main<argc><argv>{<
+        result = depth<10>;
+        return result;
+    >}";
+    let program = || OYParser::parse_program(source).unwrap();
+    let run_with_depth = |depth| {
+        let result = Interpreter::new()
+            .with_max_call_depth(depth)
+            .interpret(program(), 0, vec![]);
+        let vm_result = Vm::new()
+            .with_max_call_depth(depth)
+            .interpret(program(), 0, vec![]);
+        assert_eq!(format!("{result:?}"), format!("{vm_result:?}"));
+        result
+    };
+    assert_eq!(run_with_depth(12).unwrap(), 10);
+    let err = run_with_depth(8).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::StackOverflow(8)));
+    assert_eq!(err.span, (67, 12).into());
+    // The traceback is the call chain, the main function and the 7 calls of `depth` before the overflowing call.
+    assert_eq!(err.traceback.len(), 8);
+}
+
+#[test]
+fn test_default_max_call_depth() {
+    let source = "~depth<n>{<
+        if &n == 0 {< return 0; >};
+        return 1 + depth<n - 1>;
+    >}
+    ~main<argc><argv>{<
+        result = depth<990>;
+        depth<1000>;
+        return 0;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::StackOverflow(1000)
+    ));
+}
+
+#[test]
+fn test_catch_returned_call() {
+    // A call returned from a try block is not a tail call, so its error is caught.
+    let source = r#"~fail{< throw<"failed">; >}
+    ~main<argc><argv>{<
+        try {< return fail<>; >} catch err {< return 3; >};
+    >}"#;
+    assert_eq!(run(source).unwrap(), 3);
+}
+
+#[test]
+fn test_tail_call_return_type() {
+    let source = r#"~name: string {< return id<1>; >}
+    ~id<x>{< return x; >}
+    ~main<argc><argv>{<
+        name<>;
+        return 0;
+    >}"#;
+    assert!(
+        matches!(run(source).unwrap_err().kind, ErrorKind::MismatchedType(expected, found, _) if expected == "string" && found == "int")
+    );
+}
+
+#[test]
+fn test_oversized_max_call_depth() {
+    let source = "~depth<n>{<
+        if &n == 0 {< return 0; >};
+        return 1 + depth<n - 1>;
+    >}
+    ~main<argc><argv>{<
+        return depth<10>;
+    >}";
+    // The depth is clamped to the maximum call depth, and a stack that can't be allocated is an error, not a panic.
+    for depth in [100_000_000, usize::MAX] {
+        let result = Interpreter::new().with_max_call_depth(depth).interpret(
+            OYParser::parse_program(source).unwrap(),
+            0,
+            vec![],
+        );
+        match result {
+            Ok(exit_code) => assert_eq!(exit_code, 10),
+            Err(err) => assert!(matches!(
+                err.kind,
+                ErrorKind::StackUnavailable(MAX_CALL_DEPTH, ..)
+            )),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use super::run;
+use ocypode_lang::errors::{CallSite, ErrorKind};
+
+/// Returns the names of the called functions of the traceback, `?` for an anonymous function.
+fn names(traceback: &[CallSite]) -> Vec<&str> {
+    traceback
+        .iter()
+        .map(|call| call.name.as_deref().unwrap_or("?"))
+        .collect()
+}
+
+#[test]
+fn test_traceback() {
+    let source = "~half<n>{<
+        result = n / 0;
+        return result;
+    >}
+    ~main<argc><argv>{<
+        value = half<4>;
+        return 0;
+    >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::DivisionByZero));
+    assert_eq!(names(&err.traceback), ["main", "half"]);
+    assert_eq!(err.traceback[0].span, None);
+    assert_eq!(err.traceback[1].span, Some((105, 7).into()));
+}
+
+#[test]
+fn test_anonymous_function_traceback() {
+    let source = "~main<argc><argv>{<
+        fail = <x>{< result = x / 0; return result; >};
+        value = fail<1>;
+        return 0;
+    >}";
+    let err = run(source).unwrap_err();
+    assert_eq!(names(&err.traceback), ["main", "?"]);
+    // The anonymous function is labelled by its definition.
+    assert_eq!(err.traceback[1].function_span, (35, 39).into());
+}
+
+#[test]
+fn test_tail_call_traceback() {
+    // The tail call replaces its caller in the traceback.
+    let source = "~first{< return second<>; >}
+    ~second{< result = 1 / 0; return result; >}
+    ~main<argc><argv>{<
+        value = first<>;
+        return 0;
+    >}";
+    assert_eq!(
+        names(&run(source).unwrap_err().traceback),
+        ["main", "second"]
+    );
+}
+
+#[test]
+fn test_main_traceback() {
+    let source = "~main<argc><argv>{< return 1 / 0; >}";
+    assert_eq!(names(&run(source).unwrap_err().traceback), ["main"]);
+}
+
+#[test]
+fn test_caught_error_traceback() {
+    // The calls of the caught error are removed from the stack.
+    let source = "~fail{< result = 1 / 0; return result; >}
+    ~main<argc><argv>{<
+        try {< fail<>; >} catch err {< >};
+        value = 1 / 0;
+        return 0;
+    >}";
+    assert_eq!(names(&run(source).unwrap_err().traceback), ["main"]);
+}
@@ -0,0 +1,137 @@
+use super::run;
+use ocypode_lang::errors::ErrorKind;
+
+#[test]
+fn test_try_without_error() {
+    let source = "~main<argc><argv>{<
+        value = 1;
+        try {< value = 2; >} catch err {< return 3; >};
+        return value;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_catch_thrown_value() {
+    let source = "~main<argc><argv>{<
+        try {<
+            throw<5>;
+            return 1;
+        >} catch err {<
+            return get<err><\"value\">;
+        >};
+    >}";
+    assert_eq!(run(source).unwrap(), 5);
+}
+
+#[test]
+fn test_catch_runtime_error() {
+    let source = r#"~main<argc><argv>{<
+        try {< x = 1 / 0; >} catch err {<
+            if get<err><"kind"> == "runtime::division_by_zero" {< return 1; >};
+        >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_catch_builtin_error() {
+    let source = r#"~main<argc><argv>{<
+        try {< len<1>; >} catch err {<
+            if get<err><"message"> == "Unexpected type `int`" {< return 1; >};
+        >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+
+    let source = r#"~main<argc><argv>{<
+        try {< len<"a"><"b">; >} catch err {<
+            if get<err><"kind"> == "runtime::functions::uncorrect_arguments" {< return 1; >};
+        >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_error_span() {
+    let source = r#"~main<argc><argv>{<
+        try {< 10 / 0; >} catch err {<
+            span = get<err><"span">;
+            if span == [40, 41] {< return 1; >};
+        >};
+        return 0;
+    >}"#;
+    assert_eq!(run(source).unwrap(), 1);
+}
+
+#[test]
+fn test_catch_error_from_nested_function() {
+    let source = "~fail<value>{<
+        ~inner<x>{< throw<x>; >};
+        loop {< inner<value>; >};
+    >}
+    ~main<argc><argv>{<
+        before = 2;
+        try {< fail<3>; >} catch err {<
+            value = get<err><\"value\">;
+            return before * value;
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 6);
+}
+
+#[test]
+fn test_nested_try() {
+    let source = "~main<argc><argv>{<
+        try {<
+            try {< throw<1>; >} catch inner {< throw<2>; >};
+        >} catch outer {<
+            return get<outer><\"value\">;
+        >};
+        return 0;
+    >}";
+    assert_eq!(run(source).unwrap(), 2);
+}
+
+#[test]
+fn test_control_flow_in_try() {
+    let source = "~main<argc><argv>{<
+        count = 0;
+        for item in [1, 2, 3] {<
+            try {<
+                if item == 2 {< continue; >};
+                throw<item>;
+            >} catch err {<
+                break;
+            >};
+        >};
+        return 4;
+    >}";
+    assert_eq!(run(source).unwrap(), 4);
+}
+
+#[test]
+fn test_error_scope() {
+    let source = "~main<argc><argv>{<
+        try {< throw<1>; >} catch err {< >};
+        return err;
+    >}";
+    assert!(matches!(
+        run(source).unwrap_err().kind,
+        ErrorKind::UnDeclaredIdent(ident) if ident == "err"
+    ));
+}
+
+#[test]
+fn test_uncaught_throw() {
+    let source = "~main<argc><argv>{<
+        throw<\"failed\">;
+        return 0;
+    >}";
+    let err = run(source).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Thrown(value) if value.to_string() == "failed"));
+    assert_eq!(err.span, (28, 15).into());
+}